@@ -0,0 +1,374 @@
+use super::{CliffSearch, Describe, Refine};
+use alloc::vec::Vec;
+
+/// An iterator that determines the maximum supported load by probabilistic bisection.
+///
+/// Rather than keeping a single bracket, this searcher maintains a posterior distribution over
+/// where in a known range the cliff lies, and assumes that any individual benchmark run may report
+/// the wrong outcome with some probability (the [`error_rate`](BayesianSearcher::error_rate)).
+/// Each probe is placed at the posterior median, which is the probe that maximizes the expected
+/// information gained from a noisy pass/fail outcome. The search ends once the central credible
+/// interval (95% by default) is narrower than the requested width.
+///
+/// Since a single contradictory result only shifts probability mass rather than permanently
+/// discarding half the range, this searcher is a good fit for very expensive runs that are also
+/// somewhat noisy. Results from earlier searches can be fed in with
+/// [`observe`](BayesianSearcher::observe) before the first probe.
+///
+/// With an error rate of zero, this searcher behaves like a plain binary search.
+///
+/// ```rust
+/// use cliff::BayesianSearcher;
+///
+/// let mut load = BayesianSearcher::until(0..1024, 64);
+/// load.error_rate(0.0);
+/// assert_eq!(load.next(), Some(512));
+/// assert_eq!(load.next(), Some(768));
+/// load.overloaded();
+/// assert_eq!(load.next(), Some(640));
+/// assert_eq!(load.next(), Some(704));
+/// assert_eq!(load.next(), None);
+/// assert_eq!(load.estimate(), 704..768);
+/// ```
+///
+/// This type is only available with the `alloc` feature.
+///
+/// See also the [crate-level documentation](..) for details.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct BayesianSearcher {
+    range: core::ops::Range<usize>,
+    cell: usize,
+    weights: Vec<f64>,
+    error_rate: f64,
+    confidence: f64,
+    fidelity: usize,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+    done: bool,
+}
+
+impl BayesianSearcher {
+    /// Search for the cliff within `range`, ending when the credible interval for its location is
+    /// no wider than `min_width`.
+    ///
+    /// The start of `range` is assumed to be supported, and its end is assumed not to be.
+    pub fn until(range: core::ops::Range<usize>, min_width: usize) -> Self {
+        let width = range.end.saturating_sub(range.start).max(1);
+        // rounding up by adding `b - 1` first would overflow for ranges near `usize::MAX`
+        let div_ceil = |a: usize, b: usize| a / b + usize::from(a % b != 0);
+        // track the posterior at a resolution somewhat finer than the fidelity,
+        // but don't let a tiny fidelity make us allocate huge amounts of memory.
+        let cell = (min_width / 4).max(1).max(div_ceil(width, 4096));
+        let cells = div_ceil(width, cell);
+        let mut weights = Vec::with_capacity(cells);
+        weights.resize(cells, 1.0 / cells as f64);
+        Self {
+            range,
+            cell,
+            weights,
+            error_rate: 0.05,
+            confidence: 0.95,
+            fidelity: min_width,
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+            done: false,
+        }
+    }
+
+    /// Set the probability that any given benchmark run reports the wrong outcome.
+    ///
+    /// Defaults to `0.05`.
+    pub fn error_rate(&mut self, p: f64) {
+        self.error_rate = p;
+    }
+
+    /// Set how much of the posterior probability mass the final estimate must cover.
+    ///
+    /// `p` is clamped to lie strictly between `0.0` and `1.0`, since an estimate that covers none
+    /// of the probability mass would be empty, and one that must cover all of it may never narrow
+    /// down at all. A `NaN` is taken to be the default.
+    ///
+    /// Defaults to `0.95`.
+    pub fn confidence(&mut self, p: f64) {
+        self.confidence = if p.is_nan() {
+            0.95
+        } else {
+            p.clamp(f64::EPSILON, 1.0 - f64::EPSILON)
+        };
+    }
+
+    /// Incorporate the outcome of a benchmark run that was not yielded by this searcher.
+    ///
+    /// This is useful for seeding the search with results from earlier runs.
+    pub fn observe(&mut self, load: usize, overloaded: bool) {
+        let boundary = load.saturating_sub(self.range.start) / self.cell;
+        let hit = 1.0 - self.error_rate;
+        let miss = self.error_rate;
+
+        let mut total = 0.0;
+        let mut updated = self.weights.clone();
+        for (i, w) in updated.iter_mut().enumerate() {
+            // cell i holds the probability that the cliff is in that cell,
+            // and a cliff there means every load at or above the next boundary overloads.
+            let predicts_overload = i < boundary;
+            *w *= if predicts_overload == overloaded {
+                hit
+            } else {
+                miss
+            };
+            total += *w;
+        }
+
+        if total > 0.0 {
+            for w in &mut updated {
+                *w /= total;
+            }
+            self.weights = updated;
+        }
+        // otherwise the observation contradicts everything we're certain of, so ignore it
+    }
+
+    // NOTE: we provide inherent methods for CliffSearch so that those who do not need LoadIterator
+    // do not need to think about the trait at all.
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This will affect what value the next call to [`Iterator::next`] yields.
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// The run is disregarded, so the next call to [`Iterator::next`] yields the same load again.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
+    /// Give the current credible interval for the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        let tail = (1.0 - self.confidence) / 2.0;
+        let mut lo = None;
+        let mut hi = self.weights.len() - 1;
+        let mut cum = 0.0;
+        for (i, w) in self.weights.iter().enumerate() {
+            cum += w;
+            if lo.is_none() && cum > tail {
+                lo = Some(i);
+            }
+            if cum >= 1.0 - tail {
+                hi = i;
+                break;
+            }
+        }
+        let lo = lo.unwrap_or(0);
+        let start = self.range.start + lo * self.cell;
+        // the last cell may extend past the end of the range, and so past `usize::MAX`
+        let end = self
+            .range
+            .start
+            .saturating_add((hi + 1).saturating_mul(self.cell))
+            .min(self.range.end);
+        start..end
+    }
+
+    /// Change the required width of the credible interval, and resume the search if it had ended
+    /// with a wider interval than that.
+    ///
+    /// This provides [`Refine::refine`] without having to `use` the trait.
+    pub fn refine(&mut self, min_width: usize) {
+        self.fidelity = min_width;
+        let estimate = self.estimate();
+        if self.done && estimate.end - estimate.start > min_width {
+            self.done = false;
+        }
+    }
+
+    fn median(&self) -> usize {
+        // find the cell boundary with as close to half the probability mass below it as possible
+        let mut best = (1, f64::INFINITY);
+        let mut below = 0.0;
+        for (i, w) in self.weights.iter().enumerate().take(self.weights.len() - 1) {
+            below += w;
+            let d = if below > 0.5 {
+                below - 0.5
+            } else {
+                0.5 - below
+            };
+            if d < best.1 {
+                best = (i + 1, d);
+            }
+        }
+        self.range.start + best.0 * self.cell
+    }
+}
+
+impl CliffSearch for BayesianSearcher {
+    fn overloaded(&mut self) {
+        BayesianSearcher::overloaded(self)
+    }
+
+    fn inconclusive(&mut self) {
+        BayesianSearcher::inconclusive(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        BayesianSearcher::estimate(self)
+    }
+}
+
+impl Refine for BayesianSearcher {
+    fn refine(&mut self, min_width: usize) {
+        BayesianSearcher::refine(self, min_width)
+    }
+}
+
+impl Iterator for BayesianSearcher {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if let Some(last) = self.last.take() {
+            if !self.inconclusive {
+                self.observe(last, self.overloaded);
+            }
+        }
+        // if no probe was outstanding (like after a refine), there is nothing to adjudicate
+        self.overloaded = false;
+        self.inconclusive = false;
+
+        let estimate = self.estimate();
+        if self.weights.len() < 2 || estimate.end - estimate.start <= self.fidelity {
+            self.done = true;
+            return None;
+        }
+
+        self.last = Some(self.median());
+        self.last
+    }
+}
+
+impl Describe for BayesianSearcher {
+    fn name(&self) -> &'static str {
+        "bayesian"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("error_rate", &self.error_rate);
+        setting("confidence", &self.confidence);
+        setting("min_width", &self.fidelity);
+    }
+}
+
+#[test]
+fn noise_free() {
+    let mut scale = BayesianSearcher::until(0..1024, 64);
+    scale.error_rate(0.0);
+    assert_eq!(scale.next(), Some(512));
+    assert_eq!(scale.next(), Some(768));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(640));
+    assert_eq!(scale.next(), Some(704));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 704..768);
+
+    // check that it continues to be terminated
+    assert_eq!(scale.next(), None);
+    // even after another "failed"
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    // and the estimate is still the same
+    assert_eq!(scale.estimate(), 704..768);
+}
+
+#[test]
+fn recovers_from_noise() {
+    let cliff = 300;
+    let mut scale = BayesianSearcher::until(0..1024, 64);
+    let mut probes = 0;
+    while let Some(load) = scale.next() {
+        probes += 1;
+        // the second run is a spurious failure
+        if load > cliff || probes == 2 {
+            scale.overloaded();
+        }
+        assert!(probes < 100);
+    }
+    assert!(scale.estimate().contains(&cliff), "{:?}", scale.estimate());
+}
+
+#[test]
+fn prior_observations() {
+    let mut scale = BayesianSearcher::until(0..1024, 64);
+    scale.error_rate(0.0);
+    scale.observe(256, false);
+    scale.observe(512, true);
+    assert_eq!(scale.next(), Some(384));
+}
+
+#[test]
+fn through_trait() {
+    let mut scale = BayesianSearcher::until(0..1024, 64);
+    scale.error_rate(0.0);
+    let scale: &mut dyn CliffSearch = &mut scale;
+    assert_eq!(scale.next(), Some(512));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(256));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(128));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(64));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 64..128);
+}
+
+#[test]
+fn extremes() {
+    let cliff = usize::MAX / 3;
+    let mut scale = BayesianSearcher::until(0..usize::MAX, usize::MAX / 64);
+    scale.error_rate(0.0);
+    while let Some(load) = scale.next() {
+        if load > cliff {
+            scale.overloaded();
+        }
+    }
+    let estimate = scale.estimate();
+    assert!(estimate.contains(&cliff), "{:?}", estimate);
+    assert!(estimate.end - estimate.start <= usize::MAX / 64);
+}
+
+#[test]
+fn odd_confidence() {
+    for &p in &[0.0, -1.0, f64::NAN, 1.0, 2.0, f64::NEG_INFINITY] {
+        let mut scale = BayesianSearcher::until(0..1024, 64);
+        scale.error_rate(0.0);
+        scale.confidence(p);
+        assert!(scale.confidence > 0.0 && scale.confidence < 1.0);
+        let mut probes = 0;
+        while let Some(load) = scale.next() {
+            probes += 1;
+            if load > 300 {
+                scale.overloaded();
+            }
+            assert!(probes < 100);
+        }
+        let estimate = scale.estimate();
+        assert!(estimate.start <= estimate.end, "{}: {:?}", p, estimate);
+        // and refining does not trip over the estimate either
+        scale.refine(estimate.end - estimate.start);
+        assert_eq!(scale.next(), None);
+    }
+}
@@ -1,4 +1,5 @@
-use super::CliffSearch;
+use super::{value, CliffSearch, Describe, Refine, SearchValue, Status, Suspend};
+use core::num::NonZeroUsize;
 
 /// An iterator that determines the _minimum_ value of a system parameter by binary search.
 ///
@@ -35,27 +36,226 @@ use super::CliffSearch;
 ///
 /// See also the [crate-level documentation](..) for details.
 #[derive(Debug, Clone)]
-pub struct BinaryMinSearcher {
-    min_in: core::ops::Range<usize>,
-    last: Option<usize>,
-    fidelity: usize,
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct BinaryMinSearcher<V = usize> {
+    min_in: core::ops::Range<V>,
+    last: Option<V>,
+    started: bool,
+    fidelity: V,
+    fidelity_fn: Option<fn(core::ops::Range<V>) -> V>,
     overloaded: bool,
+    inconclusive: bool,
     done: bool,
+    fill_above: bool,
+    top: V,
+    cap: Option<V>,
+    found: bool,
+    unviable: bool,
+    floor: V,
 }
 
 impl BinaryMinSearcher {
     /// Perform a minimum search starting at `start`, and ending when the minimum has been
     /// determined to within a range of `min_width`.
+    ///
+    /// With a `min_width` of `0`, the search also finds out whether `0` is itself sufficient, which
+    /// takes at most one more probe than a `min_width` of `1`. With a
+    /// [`floor`](BinaryMinSearcher::floor), the floor is probed if need be either way.
     pub fn until(start: usize, min_width: usize) -> Self {
+        Self::starting_at(start, min_width)
+    }
+
+    /// Perform a minimum search starting at `start`, and ending when the minimum has been
+    /// determined to within a range of `min_width`, without ever probing `0`.
+    ///
+    /// This is equivalent to calling [`floor`](BinaryMinSearcher::floor) with `1`, and guarantees
+    /// that [`estimate_nonzero`](BinaryMinSearcher::estimate_nonzero) returns `Some`.
+    pub fn until_nonzero(start: NonZeroUsize, min_width: usize) -> Self {
+        let mut s = Self::until(start.get(), min_width);
+        s.floor(1);
+        s
+    }
+
+    /// Give the current estimate as a range of non-zero values.
+    ///
+    /// Since nothing below the [`floor`](BinaryMinSearcher::floor) is ever probed, a lower bound
+    /// just below the floor is given as the floor itself, so that an estimate of `floor..floor`
+    /// means that the floor is sufficient. Returns `None` if the estimate includes `0`, which can
+    /// only happen if no floor was set.
+    pub fn estimate_nonzero(&self) -> Option<core::ops::Range<NonZeroUsize>> {
+        let start = self.min_in.start.max(self.floor);
+        Some(NonZeroUsize::new(start)?..NonZeroUsize::new(self.min_in.end)?)
+    }
+}
+
+impl<V> BinaryMinSearcher<V>
+where
+    V: SearchValue,
+{
+    /// Perform a minimum search over values of any [`SearchValue`] type, starting at `start`, and
+    /// ending when the minimum has been determined to within a range of `min_width`.
+    ///
+    /// This is [`until`](BinaryMinSearcher::until) for values that are not `usize`, like an `f64`
+    /// fraction of a resource.
+    ///
+    /// ```rust
+    /// use cliff::BinaryMinSearcher;
+    ///
+    /// // the system needs at least 30% of a core
+    /// let mut cores = BinaryMinSearcher::starting_at(1.0, 0.05);
+    /// while let Some(c) = cores.next() {
+    ///     if c < 0.3 {
+    ///         cores.overloaded();
+    ///     }
+    /// }
+    /// assert_eq!(cores.estimate(), 0.28125..0.3125);
+    /// ```
+    pub fn starting_at(start: V, min_width: V) -> Self {
         Self {
-            min_in: 0..start,
+            min_in: V::ZERO..start,
             fidelity: min_width,
+            fidelity_fn: None,
             last: None,
+            started: false,
             overloaded: false,
+            inconclusive: false,
             done: false,
+            fill_above: false,
+            top: start,
+            cap: None,
+            found: false,
+            unviable: false,
+            floor: V::ZERO,
         }
     }
 
+    /// Never probe values below `floor`.
+    ///
+    /// Values below the floor are treated as known to be insufficient, so the lower bound of the
+    /// final estimate, which is the highest value known to be insufficient, is never below the
+    /// value just under `floor`. The floor itself may still be sufficient, and is probed if need be.
+    ///
+    /// ```rust
+    /// use cliff::BinaryMinSearcher;
+    ///
+    /// let mut replicas = BinaryMinSearcher::until(8, 1);
+    /// replicas.floor(3);
+    /// assert_eq!(replicas.next(), Some(8));
+    /// assert_eq!(replicas.next(), Some(5));
+    /// assert_eq!(replicas.next(), Some(3));
+    /// assert_eq!(replicas.next(), None);
+    /// // 2 is below the floor, and 3 is enough
+    /// assert_eq!(replicas.estimate(), 2..3);
+    /// ```
+    pub fn floor(&mut self, floor: V) {
+        self.floor = value::max(self.floor, floor);
+        // the start of the estimate is the highest value known to be insufficient
+        let below = if floor > V::ONE {
+            floor - V::ONE
+        } else {
+            V::ZERO
+        };
+        self.min_in.start = value::max(self.min_in.start, value::min(below, self.min_in.end));
+    }
+
+    /// Decide the width to which the minimum should be determined based on where it lies.
+    ///
+    /// `f` is called with the current estimate whenever the search decides whether to continue,
+    /// and the search continues for as long as the estimate is wider than what `f` returns.
+    ///
+    /// This replaces the `min_width` given to [`until`](BinaryMinSearcher::until).
+    pub fn fidelity_fn(&mut self, f: fn(core::ops::Range<V>) -> V) {
+        self.fidelity_fn = Some(f);
+    }
+
+    fn min_width(&self) -> V {
+        match self.fidelity_fn {
+            Some(f) => f(self.min_in.clone()),
+            None => self.fidelity,
+        }
+    }
+
+    /// Do not probe the starting value, since it is already known to be sufficient.
+    ///
+    /// Normally, the first probe is at the starting value, to confirm that it is sufficient. If
+    /// that is known from elsewhere, this makes the first probe the first one that actually tells
+    /// us something new.
+    ///
+    /// This must be called before the search starts.
+    pub fn skip_known_bounds(&mut self) {
+        self.started = true;
+        self.found = true;
+    }
+
+    /// If the starting value turns out to be insufficient, keep doubling it until a sufficient
+    /// value is found, but never go beyond `cap`.
+    ///
+    /// Without this, a search whose starting value is insufficient ends right away. The cap is
+    /// meant for a known physical maximum, like the number of cores on the machine, so that the
+    /// search does not try settings that cannot be applied. If not even `cap` is sufficient, the
+    /// search ends with [`Status::Unviable`], and the estimate says that the minimum lies above
+    /// the highest value tried.
+    ///
+    /// ```rust
+    /// use cliff::{BinaryMinSearcher, CliffSearch, Status};
+    ///
+    /// let mut threads = BinaryMinSearcher::until(8, 1);
+    /// threads.escalate(24);
+    /// assert_eq!(threads.next(), Some(8));
+    /// threads.overloaded();
+    /// assert_eq!(threads.next(), Some(16));
+    /// threads.overloaded();
+    /// // doubling again would go past the cap
+    /// assert_eq!(threads.next(), Some(24));
+    /// threads.overloaded();
+    /// assert_eq!(threads.next(), None);
+    /// assert_eq!(threads.status(), Status::Unviable);
+    /// assert_eq!(threads.estimate(), 24..usize::MAX);
+    /// ```
+    pub fn escalate(&mut self, cap: V) {
+        self.cap = Some(cap);
+    }
+
+    /// Tell how the search ended, or is ending.
+    ///
+    /// This provides [`CliffSearch::status`] without having to `use` the trait.
+    pub fn status(&self) -> Status {
+        if self.unviable {
+            Status::Unviable
+        } else {
+            Status::Completed
+        }
+    }
+
+    /// Take extra samples between the minimum and the starting value once the minimum is found.
+    ///
+    /// This is the counterpart of [`ExponentialCliffSearcher::fill_left`](crate::ExponentialCliffSearcher::fill_left)
+    /// for plotting how performance varies with the parameter. After the search has determined the
+    /// minimum, it continues to sample values between the upper bound of the estimate and the
+    /// starting value, starting halfway between the two and moving ever closer to the minimum. The
+    /// samples stop once they are within the width given to [`until`](BinaryMinSearcher::until).
+    ///
+    /// The outcomes of these samples do not affect the estimate.
+    ///
+    /// ```rust
+    /// use cliff::BinaryMinSearcher;
+    ///
+    /// let mut limit = BinaryMinSearcher::until(512, 32);
+    /// limit.fill_above();
+    /// let mut tried = Vec::new();
+    /// while let Some(v) = limit.next() {
+    ///     tried.push(v);
+    ///     if v < 170 {
+    ///         limit.overloaded();
+    ///     }
+    /// }
+    /// assert_eq!(limit.estimate(), 160..192);
+    /// assert_eq!(tried, [512, 256, 128, 192, 160, 352, 272, 232, 212]);
+    /// ```
+    pub fn fill_above(&mut self) {
+        self.fill_above = true;
+    }
+
     // NOTE: we provide inherent methods for CliffSearch so that those who do not need LoadIterator
     // do not need to think about the trait at all.
 
@@ -69,55 +269,255 @@ impl BinaryMinSearcher {
         self.overloaded = true;
     }
 
+    /// Indicate that the benchmark run for the previous parameter yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same parameter again, so that it can be re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
     /// Give the current estimate of the minimum parameter load the system-under-test can support.
     ///
     /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
-    pub fn estimate(&self) -> core::ops::Range<usize> {
+    pub fn estimate(&self) -> core::ops::Range<V> {
         self.min_in.clone()
     }
+
+    /// Change the width to which the minimum should be determined, and resume the search if it had
+    /// ended with a wider estimate than that.
+    ///
+    /// A search that ended since nothing up to its [cap](BinaryMinSearcher::escalate) was enough
+    /// stays ended, since there is nothing above the cap it may run.
+    ///
+    /// This replaces any [`fidelity_fn`](BinaryMinSearcher::fidelity_fn).
+    ///
+    /// This provides [`Refine::refine`] without having to `use` the trait.
+    pub fn refine(&mut self, min_width: V) {
+        self.fidelity = min_width;
+        self.fidelity_fn = None;
+        if self.done && !self.unviable && self.min_in.end - self.min_in.start > min_width {
+            self.done = false;
+        }
+    }
 }
 
-impl CliffSearch for BinaryMinSearcher {
+impl<V> CliffSearch<V> for BinaryMinSearcher<V>
+where
+    V: SearchValue,
+{
     fn overloaded(&mut self) {
         BinaryMinSearcher::overloaded(self)
     }
 
-    fn estimate(&self) -> core::ops::Range<usize> {
+    fn inconclusive(&mut self) {
+        BinaryMinSearcher::inconclusive(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<V> {
         BinaryMinSearcher::estimate(self)
     }
+
+    fn status(&self) -> Status {
+        BinaryMinSearcher::status(self)
+    }
+}
+
+impl Refine for BinaryMinSearcher {
+    fn refine(&mut self, min_width: usize) {
+        BinaryMinSearcher::refine(self, min_width)
+    }
+}
+
+/// The state of a suspended [`BinaryMinSearcher`].
+///
+/// Any [`fidelity_fn`](BinaryMinSearcher::fidelity_fn) is not captured, since it cannot be
+/// carried across processes, and must be set again after resuming.
+///
+/// See [`Suspend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuspendedBinaryMin {
+    /// The lower bound of the estimate.
+    pub start: usize,
+    /// The upper bound of the estimate.
+    pub end: usize,
+    /// The probe whose outcome has yet to be taken into account.
+    pub pending: Option<usize>,
+    /// Whether the first probe has been yielded.
+    pub started: bool,
+    /// The width to which the minimum should be determined.
+    pub min_width: usize,
+    /// Whether the pending probe was reported as overloaded.
+    pub overloaded: bool,
+    /// Whether the pending probe was reported as inconclusive.
+    pub inconclusive: bool,
+    /// Whether the search has ended.
+    pub done: bool,
+    /// Whether the search should fill in samples above the minimum.
+    pub fill_above: bool,
+    /// The highest value left to fill below.
+    pub top: usize,
+    /// The value the search may [escalate](BinaryMinSearcher::escalate) up to, if any.
+    pub cap: Option<usize>,
+    /// Whether a sufficient value has been found.
+    pub found: bool,
+    /// Whether not even the cap was sufficient.
+    pub unviable: bool,
+    /// The lowest value the search may probe, as set with [`floor`](BinaryMinSearcher::floor).
+    pub floor: usize,
+}
+
+impl SuspendedBinaryMin {
+    /// Continue the suspended search.
+    pub fn resume(self) -> BinaryMinSearcher {
+        BinaryMinSearcher {
+            min_in: self.start..self.end,
+            last: self.pending,
+            started: self.started,
+            fidelity: self.min_width,
+            fidelity_fn: None,
+            overloaded: self.overloaded,
+            inconclusive: self.inconclusive,
+            done: self.done,
+            fill_above: self.fill_above,
+            top: self.top,
+            cap: self.cap,
+            found: self.found,
+            unviable: self.unviable,
+            floor: self.floor,
+        }
+    }
+}
+
+impl Suspend for BinaryMinSearcher {
+    type Suspended = SuspendedBinaryMin;
+
+    fn suspend(self) -> Self::Suspended {
+        SuspendedBinaryMin {
+            start: self.min_in.start,
+            end: self.min_in.end,
+            pending: self.last,
+            started: self.started,
+            min_width: self.fidelity,
+            overloaded: self.overloaded,
+            inconclusive: self.inconclusive,
+            done: self.done,
+            fill_above: self.fill_above,
+            top: self.top,
+            cap: self.cap,
+            found: self.found,
+            unviable: self.unviable,
+            floor: self.floor,
+        }
+    }
+
+    fn resume(suspended: Self::Suspended) -> Self {
+        suspended.resume()
+    }
 }
 
-impl Iterator for BinaryMinSearcher {
-    type Item = usize;
+impl<V> Iterator for BinaryMinSearcher<V>
+where
+    V: SearchValue,
+{
+    type Item = V;
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
+            if self.fill_above {
+                // sample the values leading down to the minimum from where we started
+                let diff = self.top - self.min_in.end;
+                if diff > self.min_width() {
+                    let next = self.top - diff.scaled(1, 2);
+                    self.top = next;
+                    return Some(next);
+                } else {
+                    self.fill_above = false;
+                }
+            }
             return None;
         }
 
-        if let Some(ref mut last) = self.last {
+        if self.inconclusive && self.last.is_some() {
+            // the last run told us nothing, so run it again
+            self.inconclusive = false;
+            self.overloaded = false;
+            return self.last;
+        }
+        self.inconclusive = false;
+
+        // whether the lower limit was just found to be insufficient, rather than assumed to be
+        let mut lower_probed = false;
+        if let Some(last) = self.last.take() {
+            if self.overloaded && !self.found {
+                if let Some(cap) = self.cap {
+                    // nothing we've tried is enough, so look further up
+                    self.overloaded = false;
+                    if last >= cap {
+                        self.min_in = last..V::UNBOUNDED;
+                        self.unviable = true;
+                        self.done = true;
+                        return None;
+                    }
+                    let doubled = last.doubled().unwrap_or(V::UNBOUNDED);
+                    let next = value::min(value::max(doubled, V::ONE), cap);
+                    self.min_in = last..next;
+                    self.top = next;
+                    self.last = Some(next);
+                    return self.last;
+                }
+            }
             if self.overloaded {
                 // the last thing we tried failed, so it sets a lower limit for min
-                self.min_in.start = *last;
-                self.overloaded = false;
+                self.min_in.start = last;
+                lower_probed = true;
             } else {
                 // the last thing succeeded, so that lowers the upper limit
-                self.min_in.end = *last;
+                self.min_in.end = last;
+                self.found = true;
             }
+        } else if !self.started {
+            self.started = true;
+            self.last = Some(self.min_in.end);
+            return self.last;
+        }
+        // if no probe was outstanding (like after a refine), there is nothing to be overloaded
+        self.overloaded = false;
 
-            // bisect the range
-            let next = self.min_in.start + (self.min_in.end - self.min_in.start) / 2;
+        // bisect the range, but never below the floor
+        let next = value::max(self.min_in.start.bisect(self.min_in.end), self.floor);
 
-            // we only care about the min down to `fidelity`
-            if self.min_in.end - self.min_in.start > self.fidelity {
-                *last = next;
-                Some(next)
-            } else {
-                self.done = true;
-                None
-            }
+        // we only care about the min down to `fidelity`, and with a `fidelity` of 0, bisecting a
+        // range of 1 would probe the lower limit, which is only useful if it hasn't been already.
+        // if the floor is at the upper limit, there is nothing left to probe.
+        if self.min_in.end - self.min_in.start > self.min_width()
+            && !(lower_probed && next == self.min_in.start)
+            && next < self.min_in.end
+        {
+            self.last = Some(next);
+            Some(next)
         } else {
-            self.last = Some(self.min_in.end);
-            return self.last;
+            self.done = true;
+            // normally just None, but may be Some with filling
+            self.next()
+        }
+    }
+}
+
+impl<V> Describe for BinaryMinSearcher<V>
+where
+    V: SearchValue,
+{
+    fn name(&self) -> &'static str {
+        "binary_min"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("min_width", &self.fidelity);
+        if let Some(cap) = self.cap {
+            setting("cap", &cap);
         }
     }
 }
@@ -183,3 +583,249 @@ fn immediate() {
     assert_eq!(scale.next(), None);
     assert_eq!(scale.estimate(), 1024..1024);
 }
+
+#[test]
+fn never_zero() {
+    let mut scale = BinaryMinSearcher::until(4, 0);
+    assert_eq!(scale.next(), Some(4));
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), Some(0));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate_nonzero(), None);
+
+    let mut scale = BinaryMinSearcher::until_nonzero(NonZeroUsize::new(4).unwrap(), 0);
+    assert_eq!(scale.next(), Some(4));
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), None);
+    let one = NonZeroUsize::new(1).unwrap();
+    assert_eq!(scale.estimate_nonzero(), Some(one..one));
+}
+
+#[test]
+fn floor() {
+    let mut scale = BinaryMinSearcher::until(1024, 8);
+    scale.floor(500);
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(761));
+    assert_eq!(scale.next(), Some(630));
+    assert_eq!(scale.next(), Some(564));
+    assert_eq!(scale.next(), Some(531));
+    assert_eq!(scale.next(), Some(515));
+    assert_eq!(scale.next(), Some(507));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 499..507);
+
+    // the floor itself is probed, and may turn out to be enough
+    let mut scale = BinaryMinSearcher::until(1024, 1);
+    scale.floor(500);
+    assert_eq!(scale.by_ref().last(), Some(500));
+    assert_eq!(scale.estimate(), 499..500);
+
+    // or not
+    let mut scale = BinaryMinSearcher::until(1024, 1);
+    scale.floor(500);
+    while let Some(v) = scale.next() {
+        if v <= 500 {
+            scale.overloaded();
+        }
+    }
+    assert_eq!(scale.estimate(), 500..501);
+
+    // but nothing below it is, even with a min_width of 0
+    let mut scale = BinaryMinSearcher::until(1024, 0);
+    scale.floor(500);
+    for v in scale.by_ref() {
+        assert!(v >= 500);
+    }
+    assert_eq!(scale.estimate(), 499..500);
+
+    // with a min_width of 1, a search that must not probe 0 still probes 1
+    let mut scale = BinaryMinSearcher::until_nonzero(NonZeroUsize::new(4).unwrap(), 1);
+    assert_eq!(scale.next(), Some(4));
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), None);
+    let one = NonZeroUsize::new(1).unwrap();
+    assert_eq!(scale.estimate_nonzero(), Some(one..one));
+}
+
+#[test]
+fn inconclusive() {
+    let mut scale = BinaryMinSearcher::until(1024, 256);
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(512));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(512));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(768));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 512..768);
+}
+
+#[test]
+fn skip_known_bounds() {
+    let mut scale = BinaryMinSearcher::until(1024, 256);
+    scale.skip_known_bounds();
+    assert_eq!(scale.next(), Some(512));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(768));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 512..768);
+}
+
+#[test]
+fn fidelity_fn() {
+    let mut scale = BinaryMinSearcher::until(1024, 0);
+    scale.fidelity_fn(|bracket| bracket.end / 8);
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(512));
+    assert_eq!(scale.next(), Some(256));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(384));
+    assert_eq!(scale.next(), Some(320));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(352));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 320..352);
+}
+
+#[test]
+fn suspend() {
+    let mut scale = BinaryMinSearcher::until(1024, 256);
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(512));
+    scale.overloaded();
+    let mut scale = scale.suspend().resume();
+    assert_eq!(scale.next(), Some(768));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 512..768);
+}
+
+#[test]
+fn extremes() {
+    let mut scale = BinaryMinSearcher::until(usize::MAX, usize::MAX / 64);
+    let min = usize::MAX - usize::MAX / 3;
+    while let Some(v) = scale.next() {
+        if v < min {
+            scale.overloaded();
+        }
+    }
+    let estimate = scale.estimate();
+    assert!(estimate.start < min && min <= estimate.end);
+    assert!(estimate.end - estimate.start <= usize::MAX / 64);
+}
+
+#[test]
+fn fill_above() {
+    let mut scale = BinaryMinSearcher::until(1024, 8);
+    scale.fill_above();
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(512));
+    assert_eq!(scale.next(), Some(256));
+    assert_eq!(scale.next(), Some(128));
+    assert_eq!(scale.next(), Some(64));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(96));
+    assert_eq!(scale.next(), Some(80));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(88));
+    assert_eq!(scale.next(), Some(556));
+    // outcomes of fill samples are ignored
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(322));
+    assert_eq!(scale.next(), Some(205));
+    assert_eq!(scale.next(), Some(147));
+    let mut scale = scale.suspend().resume();
+    assert_eq!(scale.next(), Some(118));
+    assert_eq!(scale.next(), Some(103));
+    assert_eq!(scale.next(), Some(96));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 80..88);
+}
+
+#[test]
+fn escalate() {
+    let mut scale = BinaryMinSearcher::until(100, 10);
+    scale.escalate(1000);
+    assert_eq!(scale.next(), Some(100));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(200));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(400));
+    // 400 is enough, so the minimum is between 200 and 400
+    assert_eq!(scale.next(), Some(300));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(350));
+    // and overloads from now on are bisections as usual
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(375));
+    assert_eq!(scale.next(), Some(362));
+    assert_eq!(scale.next(), Some(356));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 350..356);
+    assert_eq!(scale.status(), Status::Completed);
+
+    // with a cap below the start, the start is the last chance
+    let mut scale = BinaryMinSearcher::until(100, 10);
+    scale.escalate(50);
+    assert_eq!(scale.next(), Some(100));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.status(), Status::Unviable);
+    assert_eq!(scale.estimate(), 100..usize::MAX);
+
+    // and refining it does not go past the cap either
+    scale.refine(1);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.status(), Status::Unviable);
+    assert_eq!(scale.estimate(), 100..usize::MAX);
+}
+
+#[test]
+fn tiny_starts() {
+    // whether 0 is enough is found out once, rather than over and over
+    let mut scale = BinaryMinSearcher::until(1, 0);
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), Some(0));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 0..1);
+
+    let mut scale = BinaryMinSearcher::until(2, 1);
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(1));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1..2);
+
+    for need in 0..20 {
+        for &start in &[1, 2] {
+            let mut scale = BinaryMinSearcher::until(start, 0);
+            let mut probes = 0;
+            while let Some(v) = scale.next() {
+                probes += 1;
+                assert!(probes < 20, "start {} did not converge on {}", start, need);
+                if v < need {
+                    scale.overloaded();
+                }
+            }
+            if need <= start {
+                let estimate = scale.estimate();
+                assert_eq!(estimate.end, need, "start {}", start);
+            }
+        }
+    }
+}
+
+#[test]
+fn floats() {
+    let mut scale = BinaryMinSearcher::starting_at(1.0, 0.05);
+    while let Some(load) = scale.next() {
+        if load < 0.3 {
+            scale.overloaded();
+        }
+    }
+    assert_eq!(scale.estimate(), 0.28125..0.3125);
+}
@@ -0,0 +1,71 @@
+//! Shorthands for making boxed searches, for harnesses that pick a search strategy at runtime.
+//!
+//! Every helper returns a [`BoxedSearch`], so the branches of a `match` on the user's choice all
+//! have the same type, without having to spell out trait objects or call
+//! [`CliffSearch::boxed`] on each arm.
+//!
+//! ```rust
+//! use cliff::boxed;
+//! # let strategy = "list";
+//! # let user_list = vec![1000, 2000];
+//!
+//! let mut loads = match strategy {
+//!     "exponential" => boxed::exponential(500),
+//!     "min" => boxed::min(1024, 8),
+//!     _ => boxed::list(user_list),
+//! };
+//! std::thread::spawn(move || {
+//!     while let Some(load) = loads.next() {
+//!         # if load > 1500 { loads.overloaded(); }
+//!         // ...
+//!     }
+//! });
+//! ```
+//!
+//! This module is only available with the `alloc` feature.
+
+use crate::{BinaryMinSearcher, CliffSearch, ExponentialCliffSearcher, LoadIterator};
+use alloc::boxed::Box;
+use core::borrow::Borrow;
+
+/// A search of any kind that can be sent to another thread.
+pub type BoxedSearch = Box<dyn CliffSearch + Send + 'static>;
+
+/// Box up an [`ExponentialCliffSearcher::new`] search starting at `start`.
+pub fn exponential(start: usize) -> BoxedSearch {
+    Box::new(ExponentialCliffSearcher::new(start))
+}
+
+/// Box up a [`BinaryMinSearcher::until`] search starting at `start` that ends once the minimum
+/// is known to within `min_width`.
+pub fn min(start: usize, min_width: usize) -> BoxedSearch {
+    Box::new(BinaryMinSearcher::until(start, min_width))
+}
+
+/// Box up a [`LoadIterator`] over `loads`.
+pub fn list<I, T>(loads: I) -> BoxedSearch
+where
+    I: IntoIterator<Item = T>,
+    I::IntoIter: Send + 'static,
+    T: Borrow<usize>,
+{
+    Box::new(LoadIterator::from(loads))
+}
+
+#[test]
+fn helpers() {
+    let mut searches = [
+        exponential(500),
+        min(1024, 8),
+        list(alloc::vec![1000, 2000, 3000]),
+    ];
+    for search in &mut searches {
+        while let Some(load) = search.next() {
+            if load > 1600 {
+                search.overloaded();
+            }
+        }
+    }
+    assert_eq!(searches[0].estimate(), 1500..1750);
+    assert_eq!(searches[2].estimate(), 1000..2000);
+}
@@ -0,0 +1,233 @@
+//! Related reports bundled into a single artifact.
+//!
+//! A capacity test rarely consists of a single search: the same build is typically searched
+//! under several workloads, on several machines, or with several configurations. A [`Campaign`]
+//! keeps all of those reports together under a label each, along with metadata that applies to
+//! all of them, like the git SHA of the build, the date, or the environment. The whole campaign
+//! is written out as a single JSON document with [`Campaign::to_json`], and read back with
+//! [`Campaign::parse`].
+//!
+//! ```rust
+//! use cliff::campaign::Campaign;
+//! use cliff::{CliffSearch, ExponentialCliffSearcher};
+//!
+//! let mut campaign = Campaign::new();
+//! campaign.set("git_sha", "4f2a9c1");
+//! campaign.set("date", "2024-03-01");
+//! campaign.push(
+//!     "read-heavy",
+//!     ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1600),
+//! );
+//! campaign.push(
+//!     "write-heavy",
+//!     ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 700),
+//! );
+//!
+//! let json = campaign.to_json();
+//! let campaign = Campaign::parse(&json).unwrap();
+//! assert_eq!(campaign.get("git_sha"), Some("4f2a9c1"));
+//! assert_eq!(campaign.report("write-heavy").unwrap().estimate, 500..750);
+//! ```
+//!
+//! This module is only available with the `alloc` feature.
+
+use crate::json;
+use crate::{ParseError, Report};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A report along with the label it was filed under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// What distinguishes this report from the others in the campaign, like the workload.
+    pub label: String,
+    /// The report itself.
+    pub report: Report,
+}
+
+/// A set of labelled reports with shared metadata.
+///
+/// See the [module-level documentation](self) for an example.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Campaign {
+    metadata: Vec<(String, String)>,
+    entries: Vec<Entry>,
+}
+
+impl Campaign {
+    /// The version of the JSON schema written by [`to_json`](Campaign::to_json).
+    ///
+    /// This covers the campaign itself. Every report in it carries its own
+    /// [`schema_version`](Report::SCHEMA_VERSION).
+    pub const SCHEMA_VERSION: u64 = 1;
+
+    /// Start an empty campaign.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the metadata `key` to `value`, replacing any earlier value.
+    pub fn set<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        let (key, value) = (key.into(), value.into());
+        match self.metadata.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.metadata.push((key, value)),
+        }
+    }
+
+    /// Give the value of the metadata `key`, if it is set.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| &**v)
+    }
+
+    /// Give all the metadata, in the order it was first set.
+    pub fn metadata(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.metadata.iter().map(|(k, v)| (&**k, &**v))
+    }
+
+    /// Add a report to the campaign under `label`.
+    pub fn push<S: Into<String>>(&mut self, label: S, report: Report) {
+        self.entries.push(Entry {
+            label: label.into(),
+            report,
+        });
+    }
+
+    /// Give all the reports, in the order they were added.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Give the first report filed under `label`, if any.
+    pub fn report(&self, label: &str) -> Option<&Report> {
+        self.entries
+            .iter()
+            .find(|e| e.label == label)
+            .map(|e| &e.report)
+    }
+
+    /// Write this campaign as a single line of JSON.
+    ///
+    /// The metadata is an object of strings, and every entry is an object with a `label` and a
+    /// `report` as written by [`Report::to_json`]. Numbers are written as described under [output
+    /// formats](crate#output-formats).
+    pub fn to_json(&self) -> String {
+        use core::fmt::Write;
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            r#"{{"campaign_version":{},"metadata":{{"#,
+            Self::SCHEMA_VERSION
+        );
+        for (i, (k, v)) in self.metadata.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            let _ = json::write_string(&mut out, k);
+            out.push(':');
+            let _ = json::write_string(&mut out, v);
+        }
+        out.push_str(r#"},"entries":["#);
+        for (i, e) in self.entries.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            out.push_str(r#"{"label":"#);
+            let _ = json::write_string(&mut out, &e.label);
+            out.push_str(r#","report":"#);
+            e.report.write_json(&mut out);
+            out.push('}');
+        }
+        out.push_str("]}");
+        out
+    }
+
+    /// Read a campaign written by [`to_json`](Campaign::to_json), possibly by an older version of
+    /// this crate.
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let v = json::parse(s).map_err(ParseError::Syntax)?;
+        let version = v
+            .get("campaign_version")
+            .ok_or(ParseError::Missing("campaign_version"))?
+            .as_u64()
+            .ok_or(ParseError::Invalid("campaign_version"))?;
+        if version != 1 {
+            return Err(ParseError::UnsupportedVersion(version));
+        }
+
+        let mut campaign = Self::new();
+        let metadata = v
+            .get("metadata")
+            .ok_or(ParseError::Missing("metadata"))?
+            .as_object()
+            .ok_or(ParseError::Invalid("metadata"))?;
+        for (k, v) in metadata {
+            let v = v.as_str().ok_or(ParseError::Invalid("metadata"))?;
+            campaign.set(&**k, v);
+        }
+        let entries = v
+            .get("entries")
+            .ok_or(ParseError::Missing("entries"))?
+            .as_array()
+            .ok_or(ParseError::Invalid("entries"))?;
+        for e in entries {
+            let label = e
+                .get("label")
+                .ok_or(ParseError::Missing("label"))?
+                .as_str()
+                .ok_or(ParseError::Invalid("label"))?;
+            let report = Report::from_json(e.get("report").ok_or(ParseError::Missing("report"))?)?;
+            campaign.push(label, report);
+        }
+        Ok(campaign)
+    }
+}
+
+#[test]
+fn json() {
+    let mut report = Report::new();
+    report.estimate = 10..20;
+    report.probes = 2;
+    let mut campaign = Campaign::new();
+    campaign.set("env", "staging \"eu\"");
+    campaign.set("machine", "m5.large");
+    campaign.set("env", "prod");
+    campaign.push("a", report.clone());
+    report.estimate = 30..usize::MAX;
+    campaign.push("b", report);
+
+    let json = campaign.to_json();
+    assert!(json.starts_with(
+        r#"{"campaign_version":1,"metadata":{"env":"prod","machine":"m5.large"},"entries":[{"label":"a","report":{"schema_version":7,"#
+    ));
+    let parsed = Campaign::parse(&json).unwrap();
+    assert_eq!(parsed, campaign);
+    assert_eq!(
+        parsed.metadata().collect::<Vec<_>>(),
+        [("env", "prod"), ("machine", "m5.large")]
+    );
+    assert_eq!(
+        parsed.report("b").map(|r| r.estimate.clone()),
+        Some(30..usize::MAX)
+    );
+    assert_eq!(parsed.report("c"), None);
+
+    assert_eq!(
+        Campaign::parse(r#"{"campaign_version":2}"#),
+        Err(ParseError::UnsupportedVersion(2))
+    );
+    assert_eq!(
+        Campaign::parse(r#"{"campaign_version":1,"metadata":{"n":1},"entries":[]}"#),
+        Err(ParseError::Invalid("metadata"))
+    );
+    assert_eq!(
+        Campaign::parse(
+            r#"{"campaign_version":1,"metadata":{},"entries":[{"label":"a","report":{}}]}"#
+        ),
+        Err(ParseError::Missing("schema_version"))
+    );
+}
@@ -0,0 +1,466 @@
+use super::{CliffSearch, Describe, Refine, Status};
+
+/// A change in the outcome of a [`Canary`] run, compared to the first one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drift {
+    /// How many loads had been yielded before the canary run that drifted.
+    pub index: usize,
+    /// The canary load.
+    pub load: usize,
+    /// Whether the first canary run overloaded the system.
+    pub baseline_overloaded: bool,
+    /// Whether the canary run that drifted overloaded the system.
+    pub overloaded: bool,
+    /// The metric of the first canary run, if one was [measured](Canary::measured).
+    pub baseline_metric: Option<f64>,
+    /// The metric of the canary run that drifted, if one was [measured](Canary::measured).
+    pub metric: Option<f64>,
+}
+
+impl core::fmt::Display for Drift {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let outcome = |overloaded| {
+            if overloaded {
+                "overloaded"
+            } else {
+                "sustained"
+            }
+        };
+        write!(f, "canary at {} drifted at run {}: ", self.load, self.index)?;
+        if self.overloaded != self.baseline_overloaded {
+            write!(
+                f,
+                "{}, but was {} at first",
+                outcome(self.overloaded),
+                outcome(self.baseline_overloaded)
+            )
+        } else if let (Some(metric), Some(baseline)) = (self.metric, self.baseline_metric) {
+            write!(f, "metric was {}, but was {} at first", metric, baseline)
+        } else {
+            write!(f, "{}", outcome(self.overloaded))
+        }
+    }
+}
+
+/// A search that runs a fixed canary load every so often, to detect if the environment changes
+/// while the search runs.
+///
+/// A long search, or a campaign of many searches, assumes that the system under test and its
+/// environment stay the same throughout. If they do not, like when a noisy neighbour moves onto
+/// the same host halfway through the night, the later runs are judged against a different system
+/// than the earlier ones, and the estimate quietly becomes meaningless. This wrapper runs the
+/// canary load, like the starting load of the search, before the first probe of the search and
+/// then after every `every` probes. The first canary run is the baseline. A later canary run
+/// whose outcome differs from the baseline, or whose metric (if reported with
+/// [`measured`](Canary::measured)) differs from the baseline metric by more than a
+/// [threshold](Canary::metric_threshold), has [drifted](Canary::drift).
+///
+/// The outcomes of canary runs are not passed on to the wrapped search. Use
+/// [`is_canary`](Canary::is_canary) to tell them apart from the runs of the search.
+///
+/// ```rust
+/// use cliff::{Canary, ExponentialCliffSearcher};
+///
+/// let mut load = Canary::new(ExponentialCliffSearcher::new(500), 500, 2);
+/// let mut runs = 0;
+/// while let Some(l) = load.next() {
+///     runs += 1;
+///     // halfway through, the environment gets a lot slower
+///     let cliff = if runs > 5 { 400 } else { 1600 };
+///     if l > cliff {
+///         load.overloaded();
+///     }
+/// }
+/// let drift = load.drift().unwrap();
+/// assert_eq!((drift.index, drift.overloaded), (6, true));
+/// assert_eq!(
+///     drift.to_string(),
+///     "canary at 500 drifted at run 6: overloaded, but was sustained at first"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Canary<S> {
+    inner: S,
+    load: usize,
+    every: usize,
+    threshold: f64,
+    // how many probes of the search have run since the last canary run
+    since: usize,
+    yielded: usize,
+    canary: bool,
+    baseline: Option<(bool, Option<f64>)>,
+    drift: Option<Drift>,
+    drifts: usize,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+    metric: Option<f64>,
+}
+
+impl<S> Canary<S>
+where
+    S: CliffSearch,
+{
+    /// Run `load` before the first probe of `search`, and then after every `every` of its
+    /// probes.
+    ///
+    /// An `every` of `0` is taken to be `1`.
+    pub fn new(search: S, load: usize, every: usize) -> Self {
+        let every = every.max(1);
+        Canary {
+            inner: search,
+            load,
+            every,
+            threshold: f64::INFINITY,
+            // so that the canary runs first
+            since: every,
+            yielded: 0,
+            canary: false,
+            baseline: None,
+            drift: None,
+            drifts: 0,
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+            metric: None,
+        }
+    }
+
+    /// Also count a canary run as drifted if its metric differs from that of the first canary
+    /// run by more than `threshold`, relative to the first.
+    ///
+    /// With a `threshold` of `0.1`, a canary run whose p99 latency is 10% higher or lower than at
+    /// first has drifted. Without a threshold, only a change in whether the canary load
+    /// overloads the system counts.
+    pub fn metric_threshold(&mut self, threshold: f64) {
+        self.threshold = threshold;
+    }
+
+    /// Returns `true` if the most recently yielded load is a canary run.
+    pub fn is_canary(&self) -> bool {
+        self.canary && self.last.is_some()
+    }
+
+    /// Give the most recent canary run that drifted, if any.
+    pub fn drift(&self) -> Option<Drift> {
+        self.drift
+    }
+
+    /// Give the number of canary runs that drifted.
+    pub fn drifts(&self) -> usize {
+        self.drifts
+    }
+
+    /// Add a warning to `report` if any canary run drifted.
+    ///
+    /// This method is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn warn(&self, report: &mut crate::Report) {
+        if let Some(drift) = self.drift {
+            let mut warning = alloc::string::ToString::to_string(&drift);
+            if self.drifts > 1 {
+                warning.push_str(&alloc::format!(" ({} canary runs drifted)", self.drifts));
+            }
+            report.warnings.push(warning);
+        }
+    }
+
+    /// Report a metric of the previous load factor yielded by [`Iterator::next`], like a latency
+    /// percentile.
+    ///
+    /// Only the metrics of canary runs are used, and runs of the search may be left without one.
+    pub fn measured(&mut self, metric: f64) {
+        self.metric = Some(metric);
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+        if !self.canary {
+            self.inner.overloaded();
+        }
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        if !self.canary {
+            self.inner.overloaded_by(factor);
+        }
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// An inconclusive canary run is not compared to the baseline, and is not run again.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+        if !self.canary {
+            self.inner.inconclusive();
+        }
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        if !self.canary {
+            self.inner.marginal();
+        }
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.inconclusive = true;
+        if !self.canary {
+            self.inner.skip_last();
+        }
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall on a canary run counts as an overload.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+        if !self.canary {
+            self.inner.achieved(actual);
+        }
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    // compare the outcome of a canary run to the baseline
+    fn compare(&mut self, index: usize) {
+        let (overloaded, metric) = (self.overloaded, self.metric);
+        let (baseline_overloaded, baseline_metric) = match self.baseline {
+            Some(baseline) => baseline,
+            None => {
+                self.baseline = Some((overloaded, metric));
+                return;
+            }
+        };
+        let metric_drifted = match (metric, baseline_metric) {
+            (Some(m), Some(b)) => (m - b).abs() > self.threshold * b.abs(),
+            _ => false,
+        };
+        if overloaded != baseline_overloaded || metric_drifted {
+            self.drifts += 1;
+            self.drift = Some(Drift {
+                index,
+                load: self.load,
+                baseline_overloaded,
+                overloaded,
+                baseline_metric,
+                metric,
+            });
+        }
+    }
+}
+
+impl<S> CliffSearch for Canary<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Canary::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Canary::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Canary::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Canary::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Canary::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Canary::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Canary::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for Canary<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for Canary<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.last.take().is_some() && self.canary && !self.inconclusive {
+            self.compare(self.yielded - 1);
+        }
+        self.overloaded = false;
+        self.inconclusive = false;
+        self.metric = None;
+
+        if self.since >= self.every {
+            self.since = 0;
+            self.canary = true;
+            self.last = Some(self.load);
+        } else {
+            self.canary = false;
+            self.last = self.inner.next();
+            // an inconclusive run is run again, and counts only once
+            if self.last.is_some() {
+                self.since += 1;
+            }
+        }
+        if self.last.is_some() {
+            self.yielded += 1;
+        }
+        self.last
+    }
+}
+
+impl<S> Describe for Canary<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "canary"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("load", &self.load);
+        setting("every", &self.every);
+        if self.threshold.is_finite() {
+            setting("metric_threshold", &self.threshold);
+        }
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn steady() {
+    use super::ExponentialCliffSearcher;
+
+    let mut load = Canary::new(ExponentialCliffSearcher::new(500), 250, 2);
+    load.metric_threshold(0.1);
+    let mut ran = [(0, false); 8];
+    let mut runs = 0;
+    while let Some(l) = load.next() {
+        ran[runs] = (l, load.is_canary());
+        runs += 1;
+        if load.is_canary() {
+            // within 10% of the first canary run
+            load.measured(if runs == 1 { 10.0 } else { 10.5 });
+        }
+        if l > 1600 {
+            load.overloaded();
+        }
+    }
+    assert_eq!(
+        ran,
+        [
+            (250, true),
+            (500, false),
+            (1000, false),
+            (250, true),
+            (2000, false),
+            (1500, false),
+            (250, true),
+            (1750, false),
+        ]
+    );
+    assert_eq!(load.estimate(), 1500..1750);
+    assert_eq!(load.drift(), None);
+    assert!(!load.is_canary());
+}
+
+#[test]
+fn metric() {
+    use super::ExponentialCliffSearcher;
+
+    let mut load = Canary::new(ExponentialCliffSearcher::new(500), 250, 1);
+    load.metric_threshold(0.1);
+    assert_eq!(load.next(), Some(250));
+    load.measured(10.0);
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.next(), Some(250));
+    // an inconclusive canary run is not compared
+    load.inconclusive();
+    assert_eq!(load.next(), Some(1000));
+    assert_eq!(load.next(), Some(250));
+    load.measured(12.0);
+    assert_eq!(load.next(), Some(2000));
+    let drift = load.drift().unwrap();
+    assert_eq!(drift.index, 4);
+    assert_eq!(
+        (drift.baseline_metric, drift.metric),
+        (Some(10.0), Some(12.0))
+    );
+    assert!(!drift.overloaded);
+    assert_eq!(load.drifts(), 1);
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut report = crate::Report::new();
+        load.warn(&mut report);
+        assert_eq!(
+            report.warnings,
+            ["canary at 250 drifted at run 4: metric was 12, but was 10 at first"]
+        );
+    }
+}
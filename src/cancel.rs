@@ -0,0 +1,86 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A signal that a long-running search should stop early.
+///
+/// Helpers that drive a search by themselves, like [`CliffSearch::run_cancellable`], the
+/// [`Coordinator`](crate::coordinator::Coordinator), and [`SearchSet`](crate::SearchSet), check
+/// for cancellation between benchmark runs. When cancelled, they take the outcome of the last run
+/// into account and stop, leaving a partial [`Report`](crate::Report) with
+/// [`Status::Cancelled`](crate::Status::Cancelled) rather than throwing away what was learned.
+///
+/// With `std`, an [`AtomicBool`] behind an `Arc` is the usual choice, since it can be set from a
+/// Ctrl-C handler or another thread. Without `std`, any callback can be used through
+/// [`CancelFn`].
+///
+/// ```rust
+/// use cliff::{CliffSearch, ExponentialCliffSearcher, Status};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// let stop = Arc::new(AtomicBool::new(false));
+/// # let ctrlc = Arc::clone(&stop);
+/// let report = ExponentialCliffSearcher::new(500).run_cancellable(&*stop, |load| {
+///     # if load == 1500 { ctrlc.store(true, Ordering::SeqCst); }
+///     load <= 1600
+/// });
+/// assert_eq!(report.status, Status::Cancelled);
+/// assert_eq!(report.estimate, 1500..2000);
+/// ```
+///
+///   [`CliffSearch::run_cancellable`]: crate::CliffSearch::run_cancellable
+pub trait Cancel {
+    /// Returns `true` if the search should stop.
+    fn is_cancelled(&self) -> bool;
+}
+
+impl Cancel for AtomicBool {
+    fn is_cancelled(&self) -> bool {
+        self.load(Ordering::SeqCst)
+    }
+}
+
+impl<T> Cancel for &T
+where
+    T: Cancel + ?Sized,
+{
+    fn is_cancelled(&self) -> bool {
+        (**self).is_cancelled()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Cancel for alloc::sync::Arc<T>
+where
+    T: Cancel + ?Sized,
+{
+    fn is_cancelled(&self) -> bool {
+        (**self).is_cancelled()
+    }
+}
+
+/// A [`Cancel`] signal that asks a callback whether to stop.
+///
+/// ```rust
+/// use cliff::{CancelFn, CliffSearch, ExponentialCliffSearcher, Status};
+/// use std::cell::Cell;
+///
+/// let runs = Cell::new(0);
+/// let budget = CancelFn(|| runs.get() >= 3);
+/// let report = ExponentialCliffSearcher::new(500).run_cancellable(budget, |load| {
+///     runs.set(runs.get() + 1);
+///     load <= 1600
+/// });
+/// assert_eq!(report.status, Status::Cancelled);
+/// assert_eq!(report.probes, 3);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CancelFn<F>(pub F);
+
+impl<F> Cancel for CancelFn<F>
+where
+    F: Fn() -> bool,
+{
+    fn is_cancelled(&self) -> bool {
+        (self.0)()
+    }
+}
@@ -0,0 +1,312 @@
+use super::{CliffSearch, Error, Refine, Status};
+use core::cell::{Cell, RefCell};
+
+/// A search that can be driven with a `for` loop, and that checks that every probe is judged.
+///
+/// Searches learn about the outcome of each run through a side channel: the caller reports
+/// overloads with [`CliffSearch::overloaded`] between calls to [`Iterator::next`]. That means a
+/// search cannot be driven with `for load in &mut search`, since the loop holds on to the mutable
+/// borrow, and that iterator adapters that pull loads ahead of time (like `peekable`, `zip`,
+/// `step_by`, `skip`, or `collect`) make outcomes apply to the wrong load. Adapters that pull one
+/// load at a time and do not drop any, like `map`, `take`, `take_while`, and `enumerate`, are
+/// safe. `filter` is not: a load it drops is never run, but is taken to have been sustained.
+///
+/// `Checked` takes its verdicts through `&self`, so a shared reference to it can be iterated over
+/// while the loop body reports outcomes. In addition, every load must be judged explicitly:
+/// call [`sustained`](Checked::sustained) if the system kept up. Loads that are never judged
+/// before the next one is pulled, like those dropped by an adapter, are counted by
+/// [`unadjudicated`](Checked::unadjudicated), and make [`try_next`](Checked::try_next) fail.
+///
+/// ```rust
+/// use cliff::{Checked, ExponentialCliffSearcher};
+///
+/// let load = Checked::new(ExponentialCliffSearcher::new(500));
+/// for l in load.iter().take_while(|&l| l < 100_000) {
+///     if l > 1600 {
+///         load.overloaded();
+///     } else {
+///         load.sustained();
+///     }
+/// }
+/// assert_eq!(load.estimate(), 1500..1750);
+/// assert_eq!(load.unadjudicated(), 0);
+/// ```
+///
+/// Unlike the other searches in this crate, `Checked` is not `Sync`.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Checked<S> {
+    inner: RefCell<S>,
+    pending: Cell<Option<usize>>,
+    judged: Cell<bool>,
+    unadjudicated: Cell<usize>,
+}
+
+impl<S> Checked<S>
+where
+    S: CliffSearch,
+{
+    /// Wrap `search` so that it can be driven through a shared reference.
+    pub fn new(search: S) -> Self {
+        Checked {
+            inner: RefCell::new(search),
+            pending: Cell::new(None),
+            judged: Cell::new(false),
+            unadjudicated: Cell::new(0),
+        }
+    }
+
+    /// Iterate over the loads to probe through a shared reference.
+    ///
+    /// This is the same as iterating over `&self`.
+    pub fn iter(&self) -> CheckedIter<'_, S> {
+        CheckedIter { search: self }
+    }
+
+    /// The number of loads that were yielded but never judged before the next load was pulled.
+    pub fn unadjudicated(&self) -> usize {
+        self.unadjudicated.get()
+    }
+
+    /// Like [`Iterator::next`], but returns an error if the previous load was never judged.
+    ///
+    /// No load is pulled from the search if an error is returned, so the previous load can still
+    /// be judged.
+    pub fn try_next(&self) -> Result<Option<usize>, Error> {
+        if self.pending.get().is_some() && !self.judged.get() {
+            return Err(Error::Misuse("the previous load was never judged"));
+        }
+        Ok(self.advance())
+    }
+
+    fn advance(&self) -> Option<usize> {
+        if self.pending.get().is_some() && !self.judged.get() {
+            self.unadjudicated.set(self.unadjudicated.get() + 1);
+        }
+        self.judged.set(false);
+        let next = self.inner.borrow_mut().next();
+        self.pending.set(next);
+        next
+    }
+
+    fn judge(&self, verdict: impl FnOnce(&mut S)) {
+        verdict(&mut *self.inner.borrow_mut());
+        self.judged.set(true);
+    }
+
+    /// Indicate that the system kept up with the previous load yielded by [`Iterator::next`].
+    ///
+    /// Plain searches take this to be the case unless told otherwise, but `Checked` requires every
+    /// load to be judged.
+    pub fn sustained(&self) {
+        self.judged.set(true);
+    }
+
+    /// Indicate that the system could not keep up with the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&self) {
+        self.judge(|s| s.overloaded());
+    }
+
+    /// Indicate that the system could not keep up with the previous load yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&self, factor: f64) {
+        self.judge(|s| s.overloaded_by(factor));
+    }
+
+    /// Indicate that the benchmark run for the previous load yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&self) {
+        self.judge(|s| s.inconclusive());
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&self) {
+        self.judge(|s| s.marginal());
+    }
+
+    /// Indicate that the previous load yielded by [`Iterator::next`] could not be run at all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&self) {
+        self.judge(|s| s.skip_last());
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&self, actual: usize) {
+        self.judge(|s| s.achieved(actual));
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.borrow().estimate()
+    }
+
+    /// Recommend how much effort to spend on benchmarking the load just yielded.
+    ///
+    /// This provides [`CliffSearch::effort`] without having to `use` the trait.
+    pub fn effort(&self) -> usize {
+        self.inner.borrow().effort()
+    }
+
+    /// Tell how the search ended, or is ending.
+    ///
+    /// This provides [`CliffSearch::status`] without having to `use` the trait.
+    pub fn status(&self) -> Status {
+        self.inner.borrow().status()
+    }
+
+    /// Unwrap the underlying search.
+    pub fn into_inner(self) -> S {
+        self.inner.into_inner()
+    }
+}
+
+impl<S> CliffSearch for Checked<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Checked::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Checked::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Checked::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Checked::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Checked::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Checked::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Checked::estimate(self)
+    }
+
+    fn effort(&self) -> usize {
+        Checked::effort(self)
+    }
+
+    fn status(&self) -> Status {
+        Checked::status(self)
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.borrow().seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.borrow().search_id()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.borrow().probes_left()
+    }
+}
+
+impl<S> Refine for Checked<S>
+where
+    S: CliffSearch + Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.get_mut().refine(min_width)
+    }
+}
+
+impl<S> Iterator for Checked<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance()
+    }
+}
+
+/// An iterator over the loads of a [`Checked`] search through a shared reference.
+///
+/// See [`Checked::iter`].
+#[derive(Debug)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct CheckedIter<'a, S> {
+    search: &'a Checked<S>,
+}
+
+impl<S> Iterator for CheckedIter<'_, S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.search.advance()
+    }
+}
+
+impl<'a, S> IntoIterator for &'a Checked<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    type IntoIter = CheckedIter<'a, S>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[test]
+fn for_loop() {
+    use crate::ExponentialCliffSearcher;
+
+    let load = Checked::new(ExponentialCliffSearcher::new(500));
+    for l in &load {
+        if l > 1600 {
+            load.overloaded();
+        } else {
+            load.sustained();
+        }
+    }
+    assert_eq!(load.estimate(), 1500..1750);
+    assert_eq!(load.unadjudicated(), 0);
+}
+
+#[test]
+fn unjudged() {
+    use crate::ExponentialCliffSearcher;
+
+    let load = Checked::new(ExponentialCliffSearcher::new(500));
+    // skipping the second load means it's never run, but still taken as sustained
+    let mut loads = load.iter().skip(1);
+    assert_eq!(loads.next(), Some(1000));
+    assert_eq!(load.unadjudicated(), 1);
+    assert!(matches!(load.try_next(), Err(Error::Misuse(_))));
+    load.inconclusive();
+    assert_eq!(load.try_next(), Ok(Some(1000)));
+    load.overloaded();
+    assert_eq!(load.try_next(), Ok(Some(750)));
+    assert_eq!(load.unadjudicated(), 1);
+}
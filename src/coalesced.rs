@@ -0,0 +1,375 @@
+use super::{CliffSearch, Describe, Refine, Status};
+use alloc::vec::Vec;
+
+/// A probe that [`Coalesced`] did not run, since an earlier run was close enough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Substitution {
+    /// The load the search asked to run.
+    pub requested: usize,
+    /// The earlier load whose outcome was used instead.
+    pub reused: usize,
+    /// Whether the system failed to keep up with the reused load.
+    pub overloaded: bool,
+}
+
+impl core::fmt::Display for Substitution {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} was not run, and took the outcome of {} instead: {}",
+            self.requested,
+            self.reused,
+            if self.overloaded {
+                "overloaded"
+            } else {
+                "sustained"
+            }
+        )
+    }
+}
+
+/// A search that does not run probes that are nearly identical to ones it has already run.
+///
+/// Searches that bisect down to a narrow estimate often end up asking for loads that differ by
+/// less than the noise of the benchmark from a load that was run earlier, like `1992` after
+/// `2000`. Running those is mostly a waste of time. When the wrapped search asks for a load that
+/// is within `window` (relative to the requested load) of a load that has already been run to a
+/// conclusion, the outcome of the nearest such run is passed on to the search instead, and the
+/// substitution is recorded in [`substitutions`](Coalesced::substitutions), from where
+/// [`warn`](Coalesced::warn) adds it to the report of the search.
+///
+/// This trades some precision for fewer runs: the search takes the reused outcome to hold for the
+/// load it asked for, so the estimate may be off by up to `window`. If loads can be rounded to a
+/// coarser granularity up front instead, that is usually the better choice.
+///
+/// ```rust
+/// use cliff::{Coalesced, ExponentialCliffSearcher};
+///
+/// let mut load = Coalesced::new(ExponentialCliffSearcher::until(1000, 1), 0.005);
+/// let mut runs = 0;
+/// while let Some(l) = load.next() {
+///     runs += 1;
+///     if l > 1999 {
+///         load.overloaded();
+///     }
+/// }
+/// // 1992 is within 0.5% of 2000, which overloaded the system
+/// let first = load.substitutions()[0];
+/// assert_eq!((first.requested, first.reused, first.overloaded), (1992, 2000, true));
+/// assert_eq!(runs, 8);
+/// ```
+///
+/// This type is only available with the `alloc` feature.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Coalesced<S> {
+    inner: S,
+    window: f64,
+    runs: Vec<(usize, bool)>,
+    substitutions: Vec<Substitution>,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+}
+
+impl<S> Coalesced<S>
+where
+    S: CliffSearch,
+{
+    /// Skip probes of `search` that are within `window` of an earlier run, relative to the
+    /// requested load.
+    ///
+    /// A `window` of `0.005` coalesces loads that differ by at most 0.5%. A `window` of `0` only
+    /// coalesces probes of the exact same load.
+    pub fn new(search: S, window: f64) -> Self {
+        Coalesced {
+            inner: search,
+            window: window.max(0.0),
+            runs: Vec::new(),
+            substitutions: Vec::new(),
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+        }
+    }
+
+    /// Give the probes that were not run, in the order the search asked for them.
+    pub fn substitutions(&self) -> &[Substitution] {
+        &self.substitutions
+    }
+
+    /// Add a warning to `report` for every probe that was not run, in the order the search asked
+    /// for them.
+    ///
+    /// The estimate in the report may be off by up to the window around the reused loads, so the
+    /// report should say which outcomes were borrowed.
+    pub fn warn(&self, report: &mut crate::Report) {
+        report.warnings.extend(
+            self.substitutions
+                .iter()
+                .map(alloc::string::ToString::to_string),
+        );
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// An inconclusive run is not reused for later probes.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        // a run that never happened has no outcome to reuse
+        self.inconclusive = true;
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall is remembered as an overload.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+        self.inner.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// The earlier run nearest to `load` that is within the window, if any.
+    ///
+    /// Ties go to the run that came first.
+    fn nearest(&self, load: usize) -> Option<(usize, bool)> {
+        let within = self.window * load as f64;
+        let distance = |l: usize| if l > load { l - load } else { load - l };
+        self.runs
+            .iter()
+            .filter(|&&(l, _)| distance(l) as f64 <= within)
+            .fold(None, |best: Option<(usize, bool)>, &run| match best {
+                Some(b) if distance(b.0) <= distance(run.0) => Some(b),
+                _ => Some(run),
+            })
+    }
+}
+
+impl<S> CliffSearch for Coalesced<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Coalesced::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Coalesced::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Coalesced::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Coalesced::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Coalesced::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Coalesced::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Coalesced::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for Coalesced<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for Coalesced<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(load) = self.last.take() {
+            if !self.inconclusive {
+                self.runs.push((load, self.overloaded));
+            }
+        }
+        self.overloaded = false;
+        self.inconclusive = false;
+
+        loop {
+            let load = self.inner.next()?;
+            match self.nearest(load) {
+                Some((reused, overloaded)) => {
+                    self.substitutions.push(Substitution {
+                        requested: load,
+                        reused,
+                        overloaded,
+                    });
+                    if overloaded {
+                        self.inner.overloaded();
+                    }
+                }
+                None => {
+                    self.last = Some(load);
+                    return self.last;
+                }
+            }
+        }
+    }
+}
+
+impl<S> Describe for Coalesced<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "coalesced"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("window", &self.window);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn coalesce() {
+    use super::ExponentialCliffSearcher;
+
+    let mut load = Coalesced::new(ExponentialCliffSearcher::until(1000, 1), 0.005);
+    let mut ran = Vec::new();
+    while let Some(l) = load.next() {
+        ran.push(l);
+        if l > 1999 {
+            load.overloaded();
+        }
+    }
+    assert_eq!(
+        ran,
+        alloc::vec![1000, 2000, 1500, 1750, 1875, 1937, 1968, 1984]
+    );
+    let subs: Vec<_> = load
+        .substitutions()
+        .iter()
+        .map(|s| (s.requested, s.reused, s.overloaded))
+        .collect();
+    assert_eq!(
+        subs,
+        alloc::vec![
+            (1992, 2000, true),
+            (1988, 1984, false),
+            (1990, 1984, false),
+            (1991, 1984, false),
+        ]
+    );
+    // the estimate is only as precise as the window
+    assert_eq!(load.estimate(), 1991..1992);
+
+    // and the report says which outcomes were not run
+    let mut report = crate::Report::new();
+    report.warnings.push(alloc::string::String::from("earlier warning"));
+    load.warn(&mut report);
+    assert_eq!(
+        report.warnings,
+        [
+            "earlier warning",
+            "1992 was not run, and took the outcome of 2000 instead: overloaded",
+            "1988 was not run, and took the outcome of 1984 instead: sustained",
+            "1990 was not run, and took the outcome of 1984 instead: sustained",
+            "1991 was not run, and took the outcome of 1984 instead: sustained",
+        ]
+    );
+}
+
+#[test]
+fn exact() {
+    use super::ExponentialCliffSearcher;
+
+    // an inconclusive run is run again, rather than reused
+    let mut load = Coalesced::new(ExponentialCliffSearcher::new(500), 0.0);
+    assert_eq!(load.next(), Some(500));
+    load.inconclusive();
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.next(), Some(1000));
+    load.achieved(900);
+    assert_eq!(load.next(), Some(700));
+    assert!(load.substitutions().is_empty());
+    assert_eq!(load.runs, alloc::vec![(500, false), (1000, true)]);
+    let mut report = crate::Report::new();
+    load.warn(&mut report);
+    assert!(report.warnings.is_empty());
+}
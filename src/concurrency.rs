@@ -0,0 +1,294 @@
+use super::smoothing::Smoother;
+use super::{CliffSearch, Describe, ExponentialCliffSearcher, Refine, Smoothing, Verdict};
+
+/// An iterator that determines the concurrency beyond which a closed-loop benchmark stops scaling.
+///
+/// In a closed-loop benchmark, a fixed number of clients each issue a request, wait for the
+/// response, and then issue the next. There is no offered load to fall behind on, so the cliff
+/// shows up differently: past some number of clients, adding more no longer buys more
+/// throughput, and only makes latency worse. The probed value is the number of clients, and the
+/// throughput of each run is reported with [`measured`](ConcurrencySearcher::measured), which
+/// decides whether the run still scaled.
+///
+/// A run scales if the relative gain in throughput over the highest concurrency known to scale is
+/// at least `min_efficiency` times the relative increase in clients. With a `min_efficiency` of
+/// `0.5`, going from 32 to 64 clients (twice as many) must raise throughput by at least 50%, and
+/// going from 32 to 48 clients (half again as many) must raise it by at least 25%. The first run
+/// scales as long as it has any throughput at all.
+///
+/// ```rust
+/// use cliff::ConcurrencySearcher;
+///
+/// // every client adds 100 req/s, up to 40 clients
+/// let throughput = |clients: usize| clients.min(40) as f64 * 100.0;
+///
+/// let mut clients = ConcurrencySearcher::new(1, 0.75);
+/// while let Some(c) = clients.next() {
+///     clients.measured(throughput(c));
+/// }
+/// assert_eq!(clients.estimate(), 40..41);
+/// ```
+///
+/// See also the [crate-level documentation](..) for details.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about its throughput"]
+pub struct ConcurrencySearcher {
+    inner: ExponentialCliffSearcher,
+    min_efficiency: f64,
+    baseline: Option<(usize, f64)>,
+    last: Option<usize>,
+    smoother: Smoother,
+    // the concurrency to run again for smoothing
+    again: Option<usize>,
+}
+
+impl ConcurrencySearcher {
+    /// Search for the concurrency beyond which throughput stops scaling, starting with `start`
+    /// clients, to within a single client.
+    pub fn new(start: usize, min_efficiency: f64) -> Self {
+        Self::until(start, 1, min_efficiency)
+    }
+
+    /// Search for the concurrency beyond which throughput stops scaling, starting with `start`
+    /// clients, to within `min_width` clients.
+    ///
+    /// A `start` of `0` is taken to be `1`, since a run without clients measures nothing.
+    pub fn until(start: usize, min_width: usize, min_efficiency: f64) -> Self {
+        ConcurrencySearcher {
+            inner: ExponentialCliffSearcher::until(start.max(1), min_width),
+            min_efficiency,
+            baseline: None,
+            last: None,
+            smoother: Smoother::default(),
+            again: None,
+        }
+    }
+
+    /// Run every concurrency several times, and judge it by the moving average of its throughput
+    /// over those runs rather than by a single run.
+    ///
+    /// Until the last of the runs of a concurrency, [`measured`](ConcurrencySearcher::measured)
+    /// gives [`Verdict::Inconclusive`], and the next call to [`Iterator::next`] yields the same
+    /// concurrency again.
+    ///
+    /// ```rust
+    /// use cliff::{ConcurrencySearcher, Smoothing};
+    ///
+    /// let mut clients = ConcurrencySearcher::new(1, 0.75);
+    /// clients.smooth(Smoothing::new(0.3, 3));
+    /// let mut runs = 0;
+    /// while let Some(c) = clients.next() {
+    ///     runs += 1;
+    ///     // every third run is hit by a hiccup that halves its throughput
+    ///     let hiccup = if runs % 3 == 0 { 0.5 } else { 1.0 };
+    ///     clients.measured(c.min(40) as f64 * 100.0 * hiccup);
+    /// }
+    /// assert_eq!(clients.estimate(), 40..41);
+    /// ```
+    pub fn smooth(&mut self, smoothing: Smoothing) {
+        self.smoother.set(smoothing);
+    }
+
+    /// Report the throughput measured when running the previous concurrency yielded by
+    /// [`Iterator::next`], and give the resulting verdict.
+    ///
+    /// The verdict has already been passed on to the search. A throughput that is not a number
+    /// makes the run [inconclusive](Verdict::Inconclusive), so that it is run again.
+    pub fn measured(&mut self, throughput: f64) -> Verdict {
+        let clients = match self.last.take() {
+            Some(clients) => clients,
+            None => return Verdict::Inconclusive,
+        };
+        let throughput = match self.smoother.observe(throughput) {
+            Some(smoothed) => smoothed,
+            None => {
+                self.again = Some(clients);
+                return Verdict::Inconclusive;
+            }
+        };
+        let verdict = self.judge(clients, throughput);
+        if verdict == Verdict::Sustained {
+            self.baseline = Some((clients, throughput));
+        }
+        verdict.apply(&mut self.inner);
+        verdict
+    }
+
+    fn judge(&self, clients: usize, throughput: f64) -> Verdict {
+        if throughput.is_nan() {
+            return Verdict::Inconclusive;
+        }
+        let (c0, t0) = match self.baseline {
+            Some((c0, t0)) if c0 < clients && t0 > 0.0 => (c0, t0),
+            // nothing to compare against, so any throughput at all will do
+            _ if throughput > 0.0 => return Verdict::Sustained,
+            _ => return Verdict::Overloaded,
+        };
+        let more_clients = (clients - c0) as f64 / c0 as f64;
+        let more_throughput = (throughput - t0) / t0;
+        if more_throughput >= self.min_efficiency * more_clients {
+            Verdict::Sustained
+        } else {
+            Verdict::Overloaded
+        }
+    }
+
+    /// Indicate that the benchmark run for the previous concurrency yielded by
+    /// [`Iterator::next`] did not scale, regardless of its throughput.
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.last = None;
+        self.again = None;
+        self.smoother.reset();
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the benchmark run for the previous concurrency yielded by
+    /// [`Iterator::next`] was inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same concurrency again, so that it can be
+    /// re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.last = None;
+        self.inner.inconclusive();
+    }
+
+    /// Give the current estimate of the highest concurrency at which throughput still scales.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Change the number of clients to which the concurrency should be determined, and resume
+    /// the search if it had ended with a wider estimate than that.
+    ///
+    /// This provides [`Refine::refine`] without having to `use` the trait.
+    pub fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl CliffSearch for ConcurrencySearcher {
+    fn overloaded(&mut self) {
+        ConcurrencySearcher::overloaded(self)
+    }
+
+    fn inconclusive(&mut self) {
+        ConcurrencySearcher::inconclusive(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        ConcurrencySearcher::estimate(self)
+    }
+}
+
+impl Refine for ConcurrencySearcher {
+    fn refine(&mut self, min_width: usize) {
+        ConcurrencySearcher::refine(self, min_width)
+    }
+}
+
+impl Iterator for ConcurrencySearcher {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(clients) = self.again.take() {
+            self.last = Some(clients);
+            return self.last;
+        }
+        self.last = self.inner.next();
+        self.last
+    }
+}
+
+impl Describe for ConcurrencySearcher {
+    fn name(&self) -> &'static str {
+        "concurrency"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        self.inner.settings(setting);
+        setting("min_efficiency", &self.min_efficiency);
+        self.smoother.settings(setting);
+    }
+}
+
+#[test]
+fn scaling() {
+    // throughput grows with clients, but less and less so
+    let throughput = |clients: usize| 1000.0 * (1.0 - 0.97f64.powi(clients as i32));
+
+    let mut clients = ConcurrencySearcher::new(4, 0.5);
+    assert_eq!(clients.next(), Some(4));
+    assert_eq!(clients.measured(throughput(4)), Verdict::Sustained);
+    assert_eq!(clients.next(), Some(8));
+    assert_eq!(clients.measured(throughput(8)), Verdict::Sustained);
+    assert_eq!(clients.next(), Some(16));
+    // throughput does not quite double any more, but well enough
+    assert_eq!(clients.measured(throughput(16)), Verdict::Sustained);
+    assert_eq!(clients.next(), Some(32));
+    assert_eq!(clients.measured(throughput(32)), Verdict::Sustained);
+    assert_eq!(clients.next(), Some(64));
+    assert_eq!(clients.measured(throughput(64)), Verdict::Overloaded);
+    while let Some(c) = clients.next() {
+        clients.measured(throughput(c));
+    }
+    assert_eq!(clients.estimate(), 41..42);
+}
+
+#[test]
+fn unmeasured() {
+    let mut clients = ConcurrencySearcher::new(0, 0.5);
+    assert_eq!(clients.next(), Some(1));
+    // a run without a number is repeated
+    assert_eq!(clients.measured(f64::NAN), Verdict::Inconclusive);
+    assert_eq!(clients.next(), Some(1));
+    assert_eq!(clients.measured(0.0), Verdict::Overloaded);
+    assert_eq!(clients.measured(0.0), Verdict::Inconclusive);
+    assert_eq!(clients.next(), None);
+    assert_eq!(clients.estimate(), 1..1);
+
+    // a run that is known to not scale doesn't need a throughput
+    let mut clients = ConcurrencySearcher::new(1, 0.5);
+    assert_eq!(clients.next(), Some(1));
+    clients.measured(100.0);
+    assert_eq!(clients.next(), Some(2));
+    clients.overloaded();
+    assert_eq!(clients.next(), None);
+    assert_eq!(clients.estimate(), 1..2);
+}
+
+#[test]
+fn smoothed() {
+    let mut clients = ConcurrencySearcher::new(16, 0.5);
+    clients.smooth(Smoothing::new(0.5, 2));
+    assert_eq!(clients.next(), Some(16));
+    assert_eq!(clients.measured(1600.0), Verdict::Inconclusive);
+    assert_eq!(clients.next(), Some(16));
+    assert_eq!(clients.measured(1600.0), Verdict::Sustained);
+    assert_eq!(clients.next(), Some(32));
+    assert_eq!(clients.measured(3200.0), Verdict::Inconclusive);
+    // a run without a number doesn't count
+    assert_eq!(clients.next(), Some(32));
+    assert_eq!(clients.measured(f64::NAN), Verdict::Inconclusive);
+    assert_eq!(clients.next(), Some(32));
+    // the average of 3200 and 1800 is still more than 50% over 1600
+    assert_eq!(clients.measured(1800.0), Verdict::Sustained);
+    assert_eq!(clients.next(), Some(64));
+    assert_eq!(clients.measured(6400.0), Verdict::Inconclusive);
+    assert_eq!(clients.next(), Some(64));
+    // a run that is known to not scale ends the runs of its concurrency
+    clients.overloaded();
+    assert_eq!(clients.next(), Some(48));
+
+    let mut smoothing = 0;
+    clients.settings(&mut |name, _| {
+        if name.starts_with("smoothing_") {
+            smoothing += 1;
+        }
+    });
+    assert_eq!(smoothing, 2);
+}
@@ -0,0 +1,187 @@
+use super::{CliffSearch, Comparison, Report};
+
+/// A search that checks whether the cliff still lies where an earlier report found it.
+///
+/// Rather than searching from scratch, this probes just outside the two edges of the earlier
+/// estimate: once `margin` below its lower bound, which should be sustained, and once `margin`
+/// above its upper bound, which should overload the system. That is typically two runs instead of
+/// a full search, which makes it a cheap sign-off check for a new build.
+///
+/// If the lower probe overloads, the upper one is skipped, since the cliff has clearly moved
+/// down. Once the search ends, [`outcome`](Confirm::outcome) says whether the cliff moved.
+///
+/// ```rust
+/// use cliff::{CliffSearch, Comparison, Confirm, ExponentialCliffSearcher};
+///
+/// let release = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1600);
+/// assert_eq!(release.estimate, 1500..1750);
+///
+/// let mut check = Confirm::new(&release, 50);
+/// assert_eq!(check.next(), Some(1450));
+/// assert_eq!(check.next(), Some(1800));
+/// check.overloaded();
+/// assert_eq!(check.next(), None);
+/// assert_eq!(check.outcome(), Some(Comparison::Unchanged));
+/// assert_eq!(check.estimate(), 1450..1800);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Confirm {
+    lower: Option<usize>,
+    upper: Option<usize>,
+    estimate: core::ops::Range<usize>,
+    outcome: Option<Comparison>,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+}
+
+impl Confirm {
+    /// Check that the cliff still lies within the estimate of `report`, give or take `margin`.
+    pub fn new(report: &Report, margin: usize) -> Self {
+        let start = report.estimate.start;
+        let end = report.estimate.end;
+        // there's no point in probing a load of zero, or above an unbounded estimate
+        let lower = if start == 0 {
+            None
+        } else {
+            Some(start.saturating_sub(margin))
+        };
+        let upper = if end == usize::MAX {
+            None
+        } else {
+            Some(end.saturating_add(margin))
+        };
+        Confirm {
+            lower,
+            upper,
+            estimate: lower.unwrap_or(0)..upper.unwrap_or(usize::MAX),
+            outcome: None,
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+        }
+    }
+
+    /// Give how the cliff moved compared to the report, once the search has ended.
+    pub fn outcome(&self) -> Option<Comparison> {
+        self.outcome
+    }
+
+    /// Indicate that the system could not keep up with the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+    }
+
+    /// Indicate that the benchmark run for the previous load yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same load again, so that it can be re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
+    /// Give the range the cliff is now known to lie in.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.estimate.clone()
+    }
+}
+
+impl CliffSearch for Confirm {
+    fn overloaded(&mut self) {
+        Confirm::overloaded(self)
+    }
+
+    fn inconclusive(&mut self) {
+        Confirm::inconclusive(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Confirm::estimate(self)
+    }
+}
+
+impl Iterator for Confirm {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.outcome.is_some() {
+            return None;
+        }
+
+        if self.inconclusive && self.last.is_some() {
+            // the last run told us nothing, so run it again
+            self.inconclusive = false;
+            self.overloaded = false;
+            return self.last;
+        }
+        self.inconclusive = false;
+
+        if let Some(last) = self.last.take() {
+            if Some(last) == self.lower {
+                self.lower = None;
+                if self.overloaded {
+                    self.estimate = 0..last;
+                    self.outcome = Some(Comparison::Regressed);
+                    self.overloaded = false;
+                    return None;
+                }
+            } else if !self.overloaded {
+                self.estimate = last..usize::MAX;
+                self.outcome = Some(Comparison::Improved);
+                return None;
+            } else {
+                self.upper = None;
+            }
+        }
+        self.overloaded = false;
+
+        self.last = self.lower.or(self.upper);
+        if self.last.is_none() {
+            self.outcome = Some(Comparison::Unchanged);
+        }
+        self.last
+    }
+}
+
+#[cfg(test)]
+fn report(estimate: core::ops::Range<usize>) -> Report {
+    let mut r = Report::new();
+    r.estimate = estimate;
+    r
+}
+
+#[test]
+fn moved() {
+    let mut check = Confirm::new(&report(1000..1100), 100);
+    assert_eq!(check.next(), Some(900));
+    check.overloaded();
+    assert_eq!(check.next(), None);
+    assert_eq!(check.outcome(), Some(Comparison::Regressed));
+    assert_eq!(check.estimate(), 0..900);
+
+    let mut check = Confirm::new(&report(1000..1100), 100);
+    assert_eq!(check.next(), Some(900));
+    check.inconclusive();
+    assert_eq!(check.next(), Some(900));
+    assert_eq!(check.next(), Some(1200));
+    assert_eq!(check.next(), None);
+    assert_eq!(check.outcome(), Some(Comparison::Improved));
+    assert_eq!(check.estimate(), 1200..usize::MAX);
+}
+
+#[test]
+fn unbounded() {
+    let mut check = Confirm::new(&report(1000..usize::MAX), 100);
+    assert_eq!(check.outcome(), None);
+    assert_eq!(check.next(), Some(900));
+    assert_eq!(check.next(), None);
+    assert_eq!(check.outcome(), Some(Comparison::Unchanged));
+    assert_eq!(check.estimate(), 900..usize::MAX);
+}
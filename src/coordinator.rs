@@ -0,0 +1,551 @@
+//! Running a search across a fleet of remote load generators.
+//!
+//! A [`Coordinator`] drives a [`CliffSearch`] centrally, and hands each probe to one of a number
+//! of remote workers through a user-supplied [`Transport`]. If a worker fails, whether when the
+//! probe is sent or while waiting for its result, the worker is taken out of rotation and the
+//! probe is re-issued to the next one, so that the search itself never sees the failure.
+//!
+//! ```rust
+//! use cliff::coordinator::{Coordinator, Transport};
+//! use cliff::ExponentialCliffSearcher;
+//!
+//! struct Fleet {
+//!     pending: Vec<Option<usize>>,
+//! }
+//!
+//! impl Transport for Fleet {
+//!     type Error = &'static str;
+//!     fn send_probe(&mut self, worker: usize, load: usize) -> Result<(), Self::Error> {
+//!         if worker == 1 {
+//!             return Err("worker 1 is down");
+//!         }
+//!         self.pending[worker] = Some(load);
+//!         Ok(())
+//!     }
+//!     fn recv_result(&mut self, worker: usize) -> Result<bool, Self::Error> {
+//!         let load = self.pending[worker].take().ok_or("nothing was sent")?;
+//!         Ok(load <= 1600)
+//!     }
+//! }
+//!
+//! let fleet = Fleet { pending: vec![None; 3] };
+//! let mut coordinator = Coordinator::new(ExponentialCliffSearcher::new(500), fleet, 3);
+//! let report = coordinator.run().unwrap();
+//! assert_eq!(report.estimate, 1500..1750);
+//! assert_eq!(coordinator.live_workers().collect::<Vec<_>>(), vec![0, 2]);
+//! ```
+//!
+//! Several coordinators that test the same shared environment, like a staging cluster, can share
+//! an [`OverloadBudget`], which limits how many probes that may overload the environment run at
+//! the same time across all of them.
+//!
+//! This module is only available with the `std` feature.
+
+use crate::{Cancel, CliffSearch, Report, Status};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::sync::atomic::AtomicBool;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// The connection between a [`Coordinator`] and its remote workers.
+///
+/// Workers are identified by their index, from `0` up to the number of workers given to
+/// [`Coordinator::new`].
+pub trait Transport {
+    /// The error returned when a worker fails.
+    type Error;
+
+    /// Ask `worker` to run the benchmark at `load`.
+    fn send_probe(&mut self, worker: usize, load: usize) -> Result<(), Self::Error>;
+
+    /// Wait for `worker` to finish the benchmark it was last sent, and return whether the system
+    /// kept up with the load.
+    fn recv_result(&mut self, worker: usize) -> Result<bool, Self::Error>;
+}
+
+/// The error returned by [`Coordinator::run`] when no workers are left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllWorkersFailed<E> {
+    /// The error from the last worker that failed, if any worker was ever tried.
+    pub last_error: Option<E>,
+    /// What the search had established before the last worker failed.
+    pub report: Report,
+}
+
+impl<E> core::fmt::Display for AllWorkersFailed<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.last_error {
+            Some(e) => write!(f, "all workers failed; the last with: {}", e),
+            None => write!(f, "no workers to run the search on"),
+        }
+    }
+}
+
+impl<E> std::error::Error for AllWorkersFailed<E> where E: core::fmt::Debug + core::fmt::Display {}
+
+/// A limit on how many probes that may overload the system run at the same time, shared by the
+/// [`Coordinator`]s of every search against the same environment.
+///
+/// A probe may overload the system unless it lies below the lower bound of the search's estimate,
+/// which the system is known to sustain. Such a probe only starts once it can take one of the
+/// `limit` places in the budget, and the coordinator waits until then, so that a night of capacity
+/// tests never pushes more than `limit` searches past what the environment takes at once. Probes
+/// that are known to be safe are never held back.
+///
+/// ```rust
+/// use cliff::coordinator::{Coordinator, OverloadBudget, Transport};
+/// use cliff::ExponentialCliffSearcher;
+/// use std::sync::Arc;
+///
+/// struct Staging(usize);
+///
+/// impl Transport for Staging {
+///     type Error = ();
+///     fn send_probe(&mut self, _: usize, load: usize) -> Result<(), Self::Error> {
+///         self.0 = load;
+///         Ok(())
+///     }
+///     fn recv_result(&mut self, _: usize) -> Result<bool, Self::Error> {
+///         Ok(self.0 <= 1600)
+///     }
+/// }
+///
+/// // at most one risky probe against staging at a time, across all searches
+/// let budget = Arc::new(OverloadBudget::new(1));
+/// let searches: Vec<_> = (0..4)
+///     .map(|_| {
+///         let budget = Arc::clone(&budget);
+///         std::thread::spawn(move || {
+///             let search = ExponentialCliffSearcher::new(500);
+///             let mut coordinator = Coordinator::new(search, Staging(0), 1);
+///             coordinator.overload_budget(budget);
+///             coordinator.run().unwrap()
+///         })
+///     })
+///     .collect();
+/// for search in searches {
+///     assert_eq!(search.join().unwrap().estimate, 1500..1750);
+/// }
+/// assert_eq!(budget.in_flight(), 0);
+/// ```
+#[derive(Debug)]
+pub struct OverloadBudget {
+    limit: usize,
+    in_flight: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl OverloadBudget {
+    /// Allow at most `limit` probes that may overload the system to run at the same time.
+    ///
+    /// A `limit` of `0` is taken to be `1`, since no search could make progress otherwise.
+    pub fn new(limit: usize) -> Self {
+        OverloadBudget {
+            limit: limit.max(1),
+            in_flight: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// The number of probes that may overload the system that may run at the same time.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// The number of probes that may overload the system that are running right now.
+    pub fn in_flight(&self) -> usize {
+        *self.in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // take a place in the budget, waiting for one to free up if need be.
+    //
+    // gives `None` if `cancel` fired while waiting, and otherwise whether we had to wait.
+    fn acquire<C: Cancel>(&self, cancel: &C) -> Option<(Permit<'_>, bool)> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        let mut waited = false;
+        while *in_flight >= self.limit {
+            if cancel.is_cancelled() {
+                return None;
+            }
+            waited = true;
+            // wake up every so often to see whether we were cancelled in the meantime
+            in_flight = self
+                .freed
+                .wait_timeout(in_flight, Duration::from_millis(10))
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        *in_flight += 1;
+        Some((Permit(self), waited))
+    }
+}
+
+// a place in an `OverloadBudget`, which is given back when dropped
+struct Permit<'a>(&'a OverloadBudget);
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.0.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        *in_flight -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+/// Drives a search by handing its probes to remote workers.
+///
+/// See the [module-level documentation](self).
+#[derive(Debug)]
+pub struct Coordinator<S, T> {
+    search: S,
+    transport: T,
+    alive: Vec<bool>,
+    next_worker: usize,
+    reissued: usize,
+    budget: Option<Arc<OverloadBudget>>,
+    deferred: usize,
+    // a load the search yielded that was not run because the run was cancelled
+    pending: Option<usize>,
+}
+
+impl<S, T> Coordinator<S, T>
+where
+    S: CliffSearch,
+    T: Transport,
+{
+    /// Run `search` on `workers` remote workers reached through `transport`.
+    pub fn new(search: S, transport: T, workers: usize) -> Self {
+        let mut alive = Vec::new();
+        alive.resize(workers, true);
+        Coordinator {
+            search,
+            transport,
+            alive,
+            next_worker: 0,
+            reissued: 0,
+            budget: None,
+            deferred: 0,
+            pending: None,
+        }
+    }
+
+    /// Hold back probes that may overload the system while `budget` is used up by other
+    /// searches.
+    ///
+    /// See [`OverloadBudget`].
+    pub fn overload_budget(&mut self, budget: Arc<OverloadBudget>) {
+        self.budget = Some(budget);
+    }
+
+    /// Give the number of probes that had to wait for a place in the
+    /// [overload budget](Coordinator::overload_budget).
+    pub fn deferred(&self) -> usize {
+        self.deferred
+    }
+
+    /// Give the transport used to reach the workers.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Give the number of probes that had to be re-issued because a worker failed.
+    pub fn reissued(&self) -> usize {
+        self.reissued
+    }
+
+    /// Give the workers that have not failed.
+    pub fn live_workers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.alive
+            .iter()
+            .enumerate()
+            .filter(|&(_, &alive)| alive)
+            .map(|(w, _)| w)
+    }
+
+    /// Drive the search to completion, handing out probes to the workers in turn.
+    ///
+    /// Returns an error with a partial report if every worker has failed.
+    // the error carries the partial report, which is most of its size, and also most of its use
+    #[allow(clippy::result_large_err)]
+    pub fn run(&mut self) -> Result<Report, AllWorkersFailed<T::Error>> {
+        self.run_cancellable(AtomicBool::new(false))
+    }
+
+    /// Like [`run`](Coordinator::run), but stop handing out probes once `cancel` says so.
+    ///
+    /// Cancellation is checked before each probe is handed out, and while a probe waits for a
+    /// place in the [overload budget](Coordinator::overload_budget). Once cancelled, the report
+    /// has [`Status::Cancelled`], and its estimate takes every probe that completed into account.
+    /// A load that was handed out by the search but not yet run when the cancellation landed is
+    /// kept, and is the first load run by the next call to [`run`](Coordinator::run) or
+    /// `run_cancellable`.
+    #[allow(clippy::result_large_err)]
+    pub fn run_cancellable<C>(&mut self, cancel: C) -> Result<Report, AllWorkersFailed<T::Error>>
+    where
+        C: Cancel,
+    {
+        let mut report = Report::new();
+        let mut last_error = None;
+        while let Some(load) = self.pending.take().or_else(|| self.search.next()) {
+            report.settle(self.search.estimate());
+            if cancel.is_cancelled() {
+                self.pending = Some(load);
+                report.status = Status::Cancelled;
+                break;
+            }
+            // a probe below the known-sustained lower bound can't hurt, so it needs no budget
+            let budget = self.budget.clone();
+            let _permit = match &budget {
+                Some(budget) if load >= self.search.estimate().start => {
+                    match budget.acquire(&cancel) {
+                        Some((permit, waited)) => {
+                            if waited {
+                                self.deferred += 1;
+                            }
+                            Some(permit)
+                        }
+                        None => {
+                            self.pending = Some(load);
+                            report.status = Status::Cancelled;
+                            break;
+                        }
+                    }
+                }
+                _ => None,
+            };
+            let kept_up = loop {
+                let worker = match self.pick() {
+                    Some(worker) => worker,
+                    None => {
+                        return Err(AllWorkersFailed { last_error, report });
+                    }
+                };
+                let result = self
+                    .transport
+                    .send_probe(worker, load)
+                    .and_then(|()| self.transport.recv_result(worker));
+                match result {
+                    Ok(kept_up) => break kept_up,
+                    Err(e) => {
+                        // take the worker out of rotation, and try the probe elsewhere
+                        self.alive[worker] = false;
+                        self.reissued += 1;
+                        last_error = Some(e);
+                    }
+                }
+            };
+
+            if !kept_up {
+                self.search.overloaded();
+            }
+            report.probed(load, !kept_up);
+        }
+        report.settle(self.search.estimate());
+        if report.status != Status::Cancelled {
+            report.status = self.search.status();
+        }
+        report.seed = self.search.seed();
+        report.search_id = self.search.search_id();
+        Ok(report)
+    }
+
+    // the next live worker in round-robin order
+    fn pick(&mut self) -> Option<usize> {
+        let n = self.alive.len();
+        for i in 0..n {
+            let worker = (self.next_worker + i) % n;
+            if self.alive[worker] {
+                self.next_worker = (worker + 1) % n;
+                return Some(worker);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+struct Flaky {
+    // how many probes each worker completes before it fails
+    fails_after: Vec<usize>,
+    completed: Vec<usize>,
+    sent: Vec<(usize, usize)>,
+    load: usize,
+}
+
+#[cfg(test)]
+impl Transport for Flaky {
+    type Error = usize;
+    fn send_probe(&mut self, worker: usize, load: usize) -> Result<(), Self::Error> {
+        self.sent.push((worker, load));
+        if self.completed[worker] >= self.fails_after[worker] {
+            return Err(worker);
+        }
+        self.load = load;
+        Ok(())
+    }
+    fn recv_result(&mut self, worker: usize) -> Result<bool, Self::Error> {
+        self.completed[worker] += 1;
+        Ok(self.load <= 1600)
+    }
+}
+
+#[test]
+fn reissue() {
+    use crate::ExponentialCliffSearcher;
+
+    let flaky = Flaky {
+        fails_after: alloc::vec![usize::MAX, 0, usize::MAX],
+        completed: alloc::vec![0; 3],
+        sent: Vec::new(),
+        load: 0,
+    };
+    let mut c = Coordinator::new(ExponentialCliffSearcher::new(500), flaky, 3);
+    let report = c.run().unwrap();
+    assert_eq!(report.estimate, 1500..1750);
+    assert_eq!(report.probes, 5);
+    assert_eq!(report.overloaded, 2);
+    assert_eq!(c.reissued(), 1);
+    assert_eq!(c.live_workers().collect::<Vec<_>>(), alloc::vec![0, 2]);
+    assert_eq!(
+        c.transport().sent,
+        alloc::vec![
+            (0, 500),
+            (1, 1000),
+            (2, 1000),
+            (0, 2000),
+            (2, 1500),
+            (0, 1750)
+        ]
+    );
+}
+
+#[test]
+fn all_failed() {
+    use crate::ExponentialCliffSearcher;
+
+    let flaky = Flaky {
+        fails_after: alloc::vec![1, 0],
+        completed: alloc::vec![0; 2],
+        sent: Vec::new(),
+        load: 0,
+    };
+    let mut c = Coordinator::new(ExponentialCliffSearcher::new(500), flaky, 2);
+    let err = c.run().unwrap_err();
+    assert_eq!(err.last_error, Some(0));
+    assert_eq!(err.report.probes, 1);
+    assert_eq!(err.report.estimate, 500..usize::MAX);
+    assert_eq!(c.live_workers().count(), 0);
+}
+
+#[test]
+fn cancel() {
+    use crate::ExponentialCliffSearcher;
+
+    let flaky = Flaky {
+        fails_after: alloc::vec![usize::MAX],
+        completed: alloc::vec![0],
+        sent: Vec::new(),
+        load: 0,
+    };
+    let mut c = Coordinator::new(ExponentialCliffSearcher::new(500), flaky, 1);
+    let checks = core::cell::Cell::new(0);
+    let report = c
+        .run_cancellable(crate::CancelFn(|| {
+            checks.set(checks.get() + 1);
+            checks.get() > 3
+        }))
+        .unwrap();
+    assert_eq!(report.status, Status::Cancelled);
+    assert_eq!(report.probes, 3);
+    assert_eq!(report.estimate, 1000..2000);
+}
+
+#[test]
+fn resume_after_cancel() {
+    use crate::ExponentialCliffSearcher;
+
+    let flaky = Flaky {
+        fails_after: alloc::vec![usize::MAX],
+        completed: alloc::vec![0],
+        sent: Vec::new(),
+        load: 0,
+    };
+    let mut c = Coordinator::new(ExponentialCliffSearcher::new(500), flaky, 1);
+
+    // the search has handed out 500 by the time the cancellation is noticed
+    let report = c.run_cancellable(AtomicBool::new(true)).unwrap();
+    assert_eq!(report.status, Status::Cancelled);
+    assert_eq!(report.probes, 0);
+    assert!(c.transport().sent.is_empty());
+
+    // so it must be the first load run once the search resumes
+    let report = c.run().unwrap();
+    assert_eq!(report.status, Status::Completed);
+    assert_eq!(report.estimate, 1500..1750);
+    assert_eq!(report.probes, 5);
+    assert_eq!(
+        c.transport().sent,
+        alloc::vec![(0, 500), (0, 1000), (0, 2000), (0, 1500), (0, 1750)]
+    );
+}
+
+#[test]
+fn budget() {
+    use crate::ExponentialCliffSearcher;
+
+    let budget = Arc::new(OverloadBudget::new(1));
+    let flaky = Flaky {
+        fails_after: alloc::vec![usize::MAX],
+        completed: alloc::vec![0],
+        sent: Vec::new(),
+        load: 0,
+    };
+    let mut c = Coordinator::new(ExponentialCliffSearcher::new(500), flaky, 1);
+    c.overload_budget(Arc::clone(&budget));
+
+    // while someone else holds the only place, nothing risky runs
+    let held = budget.acquire(&AtomicBool::new(false)).unwrap().0;
+    let checks = core::cell::Cell::new(0);
+    let report = c
+        .run_cancellable(crate::CancelFn(|| {
+            checks.set(checks.get() + 1);
+            checks.get() > 3
+        }))
+        .unwrap();
+    assert_eq!(report.status, Status::Cancelled);
+    assert_eq!(report.probes, 0);
+    assert!(c.transport().sent.is_empty());
+
+    // once the place is given back, the search runs as usual, and gives back its places as it
+    // goes
+    let (waiting, is_waiting) = std::sync::mpsc::channel();
+    let search = std::thread::spawn(move || {
+        // cancellation is checked once before the first probe is handed out, and then again
+        // only while that probe waits for a place in the budget
+        let checks = core::cell::Cell::new(0);
+        let report = c
+            .run_cancellable(crate::CancelFn(|| {
+                checks.set(checks.get() + 1);
+                if checks.get() == 2 {
+                    waiting.send(()).unwrap();
+                }
+                false
+            }))
+            .unwrap();
+        (report, c.deferred(), c.transport().sent.clone())
+    });
+    is_waiting.recv().unwrap();
+    drop(held);
+    let (report, deferred, sent) = search.join().unwrap();
+    assert_eq!(report.estimate, 1500..1750);
+    // the probe that was held back when the first run was cancelled is run first
+    assert_eq!(report.probes, 5);
+    assert_eq!(sent[0], (0, 500));
+    assert_eq!(deferred, 1);
+    assert_eq!(budget.in_flight(), 0);
+
+    assert_eq!(OverloadBudget::new(0).limit(), 1);
+}
@@ -0,0 +1,107 @@
+use core::fmt;
+
+/// A search or wrapper that can describe its own configuration.
+///
+/// A search is often several layers deep, like an [`ExponentialCliffSearcher`] that is
+/// [`Repeated`] and then kept within a [`Domain`]. Each layer has settings of its own, and the
+/// effective configuration of the search is all of them together. [`describe`](Describe::describe)
+/// walks the stack of layers from the outermost inwards, and each layer gives its
+/// [`name`](Describe::name) and [`settings`](Describe::settings). With the `alloc` feature,
+/// [`Report::record_config`] records all of them in a report.
+///
+/// The names of layers and of their settings are kept stable across releases, so that recorded
+/// configurations can be compared.
+///
+/// ```rust
+/// use cliff::{CliffSearch, Describe, ExponentialCliffSearcher, Repeated};
+///
+/// let search = Repeated::new(ExponentialCliffSearcher::until(500, 100), 3).with_domain(1..10_000);
+/// let names: Vec<_> = search.describe().map(|layer| layer.name()).collect();
+/// assert_eq!(names, ["domain", "repeated", "exponential"]);
+///
+/// let mut runs = None;
+/// search.describe().nth(1).unwrap().settings(&mut |key, value| {
+///     if key == "runs" {
+///         runs = Some(value.to_string());
+///     }
+/// });
+/// assert_eq!(runs.as_deref(), Some("3"));
+/// ```
+///
+/// [`ExponentialCliffSearcher`]: crate::ExponentialCliffSearcher
+/// [`Repeated`]: crate::Repeated
+/// [`Domain`]: crate::Domain
+/// [`Report::record_config`]: crate::Report::record_config
+pub trait Describe {
+    /// A short, lowercase name for this kind of layer, like `"exponential"` or `"paced"`.
+    fn name(&self) -> &'static str;
+
+    /// Call `setting` with the name and value of every setting of this layer.
+    ///
+    /// Settings of the layers this one wraps are not included. Settings that are not set, like
+    /// an optional jitter, are left out.
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn fmt::Display)) {
+        let _ = setting;
+    }
+
+    /// Give the layer this one wraps, if any.
+    fn inner(&self) -> Option<&dyn Describe> {
+        None
+    }
+
+    /// Walk the stack of layers, starting with this one.
+    fn describe(&self) -> Layers<'_>
+    where
+        Self: Sized,
+    {
+        Layers { next: Some(self) }
+    }
+}
+
+/// An iterator over the layers of a search, from the outermost inwards.
+///
+/// See [`Describe::describe`].
+#[derive(Clone)]
+pub struct Layers<'a> {
+    next: Option<&'a dyn Describe>,
+}
+
+impl fmt::Debug for Layers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layers")
+            .field("next", &self.next.map(|l| l.name()))
+            .finish()
+    }
+}
+
+impl<'a> Iterator for Layers<'a> {
+    type Item = &'a dyn Describe;
+    fn next(&mut self) -> Option<Self::Item> {
+        let layer = self.next?;
+        self.next = layer.inner();
+        Some(layer)
+    }
+}
+
+#[test]
+fn layers() {
+    use crate::{ExponentialCliffSearcher, Paced, Tracked};
+    use core::time::Duration;
+
+    let search = Tracked::new(Paced::new(
+        ExponentialCliffSearcher::new(500),
+        Duration::from_secs(2),
+    ));
+    let mut layers = search.describe();
+    assert_eq!(layers.next().map(|l| l.name()), Some("tracked"));
+    let paced = layers.next().unwrap();
+    assert_eq!(paced.name(), "paced");
+    let mut count = 0;
+    paced.settings(&mut |key, _| {
+        assert_ne!(key, "jitter");
+        count += 1;
+    });
+    assert_eq!(count, 2);
+    assert_eq!(layers.next().map(|l| l.name()), Some("exponential"));
+    assert!(layers.next().is_none());
+}
@@ -0,0 +1,410 @@
+use super::{CliffSearch, Describe, Refine, Status};
+use core::ops::Range;
+
+/// A search that stays within a domain of valid parameter values.
+///
+/// Many parameters only make sense within some range, like a thread count between 1 and 1024.
+/// A search left to itself may still ramp past the end of such a range, and hints, warm starts,
+/// or shifted bounds can leave its estimate partly outside it. This wrapper, made with
+/// [`CliffSearch::with_domain`], makes sure that no load outside the domain is ever yielded, and
+/// that the estimate is always clipped to it.
+///
+/// When the search asks for a load above the domain, the highest value in the domain is run in
+/// its place. If the system keeps up with it, the whole domain is supported, the search ends,
+/// and it has [`Status::NeverOverloaded`]. Likewise, a load below the domain is replaced by the
+/// lowest value in the domain, and if the system does not keep up with that, the search ends
+/// with an empty estimate at the start of the domain. Each end of the domain is run at most once,
+/// and its outcome is reused whenever the search asks for it again.
+///
+/// ```rust
+/// use cliff::{CliffSearch, ExponentialCliffSearcher, Status};
+///
+/// // threads can go from 1 to 1024
+/// let mut threads = ExponentialCliffSearcher::new(64).with_domain(1..1025);
+/// let mut ran = Vec::new();
+/// while let Some(t) = threads.next() {
+///     ran.push(t);
+/// }
+/// // the search would have gone on to 2048 threads, but 1024 is as far as it goes
+/// assert_eq!(ran, [64, 128, 256, 512, 1024]);
+/// assert_eq!(threads.estimate(), 1024..1025);
+/// assert_eq!(threads.status(), Status::NeverOverloaded);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Domain<S> {
+    inner: S,
+    domain: Range<usize>,
+    last: Option<usize>,
+    // the load the inner search asked for when `last` was run in its place
+    requested: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+    // the outcomes at the lowest and highest values of the domain, once known
+    bottom: Option<bool>,
+    top: Option<bool>,
+    done: bool,
+}
+
+impl<S> Domain<S> {
+    /// Keep `search` within `domain`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `domain` is empty.
+    pub fn new(search: S, domain: Range<usize>) -> Self {
+        assert!(
+            domain.start < domain.end,
+            "the domain of a search must not be empty"
+        );
+        Domain {
+            inner: search,
+            domain,
+            last: None,
+            requested: None,
+            overloaded: false,
+            inconclusive: false,
+            bottom: None,
+            top: None,
+            done: false,
+        }
+    }
+}
+
+impl<S> Domain<S>
+where
+    S: CliffSearch,
+{
+    /// Give the domain the search is kept within.
+    pub fn domain(&self) -> Range<usize> {
+        self.domain.clone()
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.inconclusive = true;
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// If the load was run in place of one outside the domain, and the system achieved all of it,
+    /// the wrapped search is told that it achieved the load it asked for, since it never asked
+    /// for the one that was run.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        let last = match self.last {
+            Some(last) => last,
+            None => return self.inner.achieved(actual),
+        };
+        if actual < last {
+            self.overloaded = true;
+        }
+        match self.requested {
+            Some(requested) if actual >= last => self.inner.achieved(requested),
+            _ => self.inner.achieved(actual),
+        }
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support, clipped
+    /// to the domain.
+    ///
+    /// An estimate of `max..domain.end`, where `max` is the highest value in the domain, means
+    /// that the whole domain is supported.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> Range<usize> {
+        let max = self.domain.end - 1;
+        if self.top == Some(false) && self.done {
+            return max..self.domain.end;
+        }
+        if self.bottom == Some(true) && self.done {
+            return self.domain.start..self.domain.start;
+        }
+        let estimate = self.inner.estimate();
+        let start = estimate.start.max(self.domain.start).min(max);
+        let end = if self.top == Some(true) {
+            estimate.end.min(max)
+        } else {
+            estimate.end.min(self.domain.end)
+        };
+        start..end.max(start)
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> CliffSearch for Domain<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Domain::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Domain::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Domain::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Domain::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Domain::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Domain::skip_last(self)
+    }
+
+    fn estimate(&self) -> Range<usize> {
+        Domain::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        if self.done && self.top == Some(false) {
+            Status::NeverOverloaded
+        } else {
+            self.inner.status()
+        }
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        if self.done {
+            Some(0)
+        } else {
+            self.inner.probes_left()
+        }
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for Domain<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for Domain<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        let max = self.domain.end - 1;
+        if let Some(load) = self.last.take() {
+            if !self.inconclusive {
+                // the outcome is recorded as whether the system was overloaded
+                if load == self.domain.start {
+                    self.bottom = Some(self.overloaded);
+                }
+                if load == max {
+                    self.top = Some(self.overloaded);
+                }
+            }
+        }
+        self.requested = None;
+        self.overloaded = false;
+        self.inconclusive = false;
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let requested = self.inner.next()?;
+            let load = if requested >= max {
+                match self.top {
+                    // the whole domain is supported, so there is nothing left to find
+                    Some(false) => {
+                        self.done = true;
+                        return None;
+                    }
+                    // if the highest value overloads the system, so does anything above it
+                    Some(true) => {
+                        self.inner.overloaded();
+                        continue;
+                    }
+                    None => max,
+                }
+            } else if requested <= self.domain.start {
+                match self.bottom {
+                    // nothing in the domain is supported
+                    Some(true) => {
+                        self.done = true;
+                        return None;
+                    }
+                    // if the lowest value is supported, so is anything below it
+                    Some(false) => continue,
+                    None => self.domain.start,
+                }
+            } else {
+                requested
+            };
+            if load != requested {
+                self.requested = Some(requested);
+            }
+            self.last = Some(load);
+            return self.last;
+        }
+    }
+}
+
+impl<S> Describe for Domain<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "domain"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("start", &self.domain.start);
+        setting("end", &self.domain.end);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn top() {
+    use super::ExponentialCliffSearcher;
+
+    let mut threads = ExponentialCliffSearcher::new(64).with_domain(1..1000);
+    assert_eq!(threads.next(), Some(64));
+    assert_eq!(threads.next(), Some(128));
+    assert_eq!(threads.next(), Some(256));
+    assert_eq!(threads.next(), Some(512));
+    // 1024 is outside the domain
+    assert_eq!(threads.next(), Some(999));
+    threads.overloaded();
+    // which also overloads the search's 1024, and so it bisects below it
+    assert_eq!(threads.next(), Some(768));
+    threads.overloaded();
+    assert_eq!(threads.next(), Some(640));
+    assert_eq!(threads.next(), Some(704));
+    assert_eq!(threads.next(), Some(736));
+    assert_eq!(threads.next(), None);
+    assert_eq!(threads.estimate(), 736..768);
+    assert_eq!(threads.status(), Status::Completed);
+
+    // a search that is told its upper bound lies above the domain
+    let mut threads = ExponentialCliffSearcher::new(64).with_domain(1..100);
+    assert_eq!(threads.next(), Some(64));
+    assert_eq!(threads.next(), Some(99));
+    threads.overloaded();
+    assert_eq!(threads.next(), Some(96));
+    threads.overloaded();
+    assert_eq!(threads.next(), None);
+    assert_eq!(threads.estimate(), 64..96);
+}
+
+#[test]
+fn top_achieved() {
+    use super::ExponentialCliffSearcher;
+
+    // the system achieves every load it is asked to run, including 999 in place of 1024
+    let mut threads = ExponentialCliffSearcher::new(64).with_domain(1..1000);
+    let mut ran = [0; 6];
+    let mut n = 0;
+    while let Some(t) = threads.next() {
+        ran[n] = t;
+        n += 1;
+        threads.achieved(t);
+    }
+    assert_eq!(ran[..n], [64, 128, 256, 512, 999]);
+    assert_eq!(threads.estimate(), 999..1000);
+    assert_eq!(threads.status(), Status::NeverOverloaded);
+
+    // but falling short of a substituted load is still a shortfall
+    let mut threads = ExponentialCliffSearcher::new(64).with_domain(1..1000);
+    while let Some(t) = threads.next() {
+        threads.achieved(t.min(900));
+    }
+    assert_eq!(threads.status(), Status::Completed);
+    assert!(threads.estimate().end <= 900);
+}
+
+#[test]
+fn bottom() {
+    use super::ExponentialCliffSearcher;
+
+    let mut replicas = ExponentialCliffSearcher::within(0..8, 1).with_domain(3..10);
+    assert_eq!(replicas.next(), Some(4));
+    replicas.overloaded();
+    // the search wants 2, which is below the domain
+    assert_eq!(replicas.next(), Some(3));
+    replicas.overloaded();
+    assert_eq!(replicas.next(), None);
+    assert_eq!(replicas.estimate(), 3..3);
+
+    let mut replicas = ExponentialCliffSearcher::within(0..8, 1).with_domain(3..10);
+    assert_eq!(replicas.next(), Some(4));
+    replicas.overloaded();
+    assert_eq!(replicas.next(), Some(3));
+    assert_eq!(replicas.next(), None);
+    assert_eq!(replicas.estimate(), 3..4);
+
+    // a search that starts out entirely outside the domain still has its estimate clipped
+    let search = ExponentialCliffSearcher::within(0..2, 1).with_domain(3..10);
+    assert_eq!(search.estimate(), 3..3);
+}
@@ -0,0 +1,51 @@
+/// An error returned by the `try_` methods of the searchers in this crate, and by
+/// [`Report::validate`](crate::Report::validate).
+///
+/// The plain methods silently ignore requests that make no sense, or cut the search short when it
+/// cannot go on. The `try_` variants report such cases instead, so that the code driving a search
+/// can recover (or at least log what went wrong) rather than panic or carry on with a bogus
+/// estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The search was configured in a way that cannot work.
+    InvalidConfig(&'static str),
+    /// A method was called at a point in the search where it makes no sense.
+    Misuse(&'static str),
+    /// The search would need to go to a load that does not fit in the type of value it searches
+    /// over, like `usize`.
+    Overflow,
+    /// A report does not add up, usually because the harness that produced it did not report
+    /// every outcome.
+    Inconsistent(&'static str),
+    /// The system was found to support `supported`, but not `overloaded`, even though `supported`
+    /// is no lower than `overloaded`.
+    Contradiction {
+        /// The load that the system supported.
+        supported: usize,
+        /// The load that the system did not support.
+        overloaded: usize,
+    },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::InvalidConfig(why) => write!(f, "invalid search configuration: {}", why),
+            Error::Misuse(why) => write!(f, "search used incorrectly: {}", why),
+            Error::Overflow => write!(f, "load does not fit in the search's value type"),
+            Error::Inconsistent(why) => write!(f, "report is inconsistent: {}", why),
+            Error::Contradiction {
+                supported,
+                overloaded,
+            } => write!(
+                f,
+                "system supported {} but not the lower load {}",
+                supported, overloaded
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,115 @@
+use super::CliffSearch;
+use core::time::Duration;
+
+/// How long each benchmark run should last, depending on how far along the search is.
+///
+/// Early probes, taken while the estimate is still wide, only need to tell whether the system
+/// falls over entirely, so they can be short. Probes close to the cliff are where noise matters
+/// most, so they deserve long, careful runs. An `Escalation` encodes that trade-off in one place,
+/// so that every harness that uses it behaves the same way near the cliff.
+///
+/// Runs last for the default duration given to [`new`](Escalation::new), except:
+///
+///  - while the estimate has no upper bound, or its upper bound is more than a given multiple of
+///    its lower bound, runs last for the [`coarse`](Escalation::coarse) duration; and
+///  - once the search expects to need no more than a given number of runs (see
+///    [`CliffSearch::probes_left`]), runs last for the [`finest`](Escalation::finest) duration.
+///
+/// ```rust
+/// use cliff::{Escalation, ExponentialCliffSearcher};
+/// use std::time::Duration;
+///
+/// let mut escalation = Escalation::new(Duration::from_secs(60));
+/// escalation
+///     .coarse(Duration::from_secs(30), 4.0)
+///     .finest(Duration::from_secs(300), 2);
+///
+/// let mut load = ExponentialCliffSearcher::until(500, 100);
+/// let mut durations = Vec::new();
+/// while let Some(l) = load.next() {
+///     durations.push((l, escalation.duration(&load).as_secs()));
+///     if l > 1600 {
+///         load.overloaded();
+///     }
+/// }
+/// assert_eq!(
+///     durations,
+///     [(500, 30), (1000, 30), (2000, 30), (1500, 60), (1750, 60), (1625, 300), (1562, 300)]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Escalation {
+    default: Duration,
+    coarse: Option<(Duration, f64)>,
+    finest: Option<(Duration, usize)>,
+}
+
+impl Escalation {
+    /// Make every run last for `default`, until told otherwise.
+    pub fn new(default: Duration) -> Self {
+        Escalation {
+            default,
+            coarse: None,
+            finest: None,
+        }
+    }
+
+    /// Make runs last for `duration` while the estimate has no upper bound, or while its upper
+    /// bound is more than `ratio` times its lower bound.
+    pub fn coarse(&mut self, duration: Duration, ratio: f64) -> &mut Self {
+        self.coarse = Some((duration, ratio));
+        self
+    }
+
+    /// Make runs last for `duration` once the search expects to need at most `probes` more runs,
+    /// counting the one about to happen.
+    ///
+    /// This takes precedence over [`coarse`](Escalation::coarse).
+    pub fn finest(&mut self, duration: Duration, probes: usize) -> &mut Self {
+        self.finest = Some((duration, probes));
+        self
+    }
+
+    /// Give how long to run the benchmark at the load `search` last yielded.
+    pub fn duration<S>(&self, search: &S) -> Duration
+    where
+        S: CliffSearch + ?Sized,
+    {
+        if let Some((duration, probes)) = self.finest {
+            if search.probes_left().map_or(false, |left| left <= probes) {
+                return duration;
+            }
+        }
+        if let Some((duration, ratio)) = self.coarse {
+            let estimate = search.estimate();
+            if estimate.end == usize::MAX || estimate.end as f64 > estimate.start as f64 * ratio {
+                return duration;
+            }
+        }
+        self.default
+    }
+}
+
+#[test]
+fn escalation() {
+    use super::{ExponentialCliffSearcher, LoadIterator};
+
+    let mut escalation = Escalation::new(Duration::from_secs(60));
+    escalation.coarse(Duration::from_secs(30), 4.0);
+    let mut scale = ExponentialCliffSearcher::new(100);
+    assert_eq!(scale.next(), Some(100));
+    assert_eq!(escalation.duration(&scale), Duration::from_secs(30));
+    assert_eq!(scale.next(), Some(200));
+    assert_eq!(scale.next(), Some(400));
+    assert_eq!(scale.next(), Some(800));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(600));
+    // 400..800 is only twice as wide
+    assert_eq!(escalation.duration(&scale), Duration::from_secs(60));
+
+    // searches that can't tell how many runs are left never get the finest duration
+    escalation.finest(Duration::from_secs(300), 2);
+    let mut scale = LoadIterator::from(&[100, 200]);
+    assert_eq!(scale.next(), Some(100));
+    assert_eq!(escalation.duration(&scale), Duration::from_secs(30));
+}
@@ -0,0 +1,542 @@
+use core::ops::{Range, RangeInclusive};
+
+/// An estimate of where the performance cliff of a system lies.
+///
+/// `start` is the highest load known to be supported, and `end` is the lowest load known _not_ to
+/// be supported (or `usize::MAX` if no such load has been found). This is the same interpretation
+/// as the half-open [`Range`] returned by [`CliffSearch::estimate`](crate::CliffSearch::estimate),
+/// and the two convert freely into each other. This type adds helpers for computations that are
+/// otherwise easy to get subtly wrong, like forgetting that `end` is exclusive.
+///
+/// ```rust
+/// use cliff::{Estimate, ExponentialCliffSearcher};
+///
+/// let mut load = ExponentialCliffSearcher::new(500);
+/// # while let Some(l) = load.next() { if l > 1600 { load.overloaded(); } }
+/// let estimate = Estimate::from(load.estimate());
+/// assert_eq!(estimate, 1500..1750);
+/// assert_eq!(estimate.width(), 250);
+/// assert!(estimate.contains(1500));
+/// assert!(!estimate.contains(1750));
+/// assert_eq!(estimate.to_inclusive(), 1500..=1749);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Estimate {
+    /// The highest load known to be supported.
+    pub start: usize,
+    /// The lowest load known not to be supported.
+    pub end: usize,
+}
+
+impl Estimate {
+    /// The number of loads that may still be the highest supported load.
+    pub fn width(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// The width of the estimate relative to its lower bound.
+    ///
+    /// A `relative_width` of `0.1` means that the estimate is within 10% of its lower bound. This
+    /// is infinite if the lower bound is `0` or if no upper bound has been found.
+    pub fn relative_width(&self) -> f64 {
+        if self.start == 0 || self.end == usize::MAX {
+            return f64::INFINITY;
+        }
+        self.width() as f64 / self.start as f64
+    }
+
+    /// The highest load known to be supported.
+    ///
+    /// This is the same as `start`, but makes it explicit which interpretation of the estimate the
+    /// caller relies on.
+    pub fn highest_supported(&self) -> usize {
+        self.start
+    }
+
+    /// The lowest load known not to be supported, or `None` if no such load has been found.
+    ///
+    /// Unlike `end`, this does not use `usize::MAX` to stand for "no upper bound yet".
+    pub fn lowest_unsupported(&self) -> Option<usize> {
+        if self.end == usize::MAX {
+            None
+        } else {
+            Some(self.end)
+        }
+    }
+
+    /// The loads that may still be the highest supported load, as an inclusive range.
+    ///
+    /// That is, from the highest load known to be supported up to and including one less than the
+    /// lowest load known not to be supported. The range is empty if the estimate is. See also
+    /// [`CliffSearch::estimate_inclusive`](crate::CliffSearch::estimate_inclusive).
+    pub fn to_inclusive(&self) -> RangeInclusive<usize> {
+        if self.end == 0 {
+            // there's no way to write an empty range that ends at -1
+            return RangeInclusive::new(1, 0);
+        }
+        self.start..=(self.end - 1)
+    }
+
+    /// Boil the estimate down to a single load according to `policy`, for example to publish as
+    /// the capacity of the system.
+    ///
+    /// The result is always one of the loads that may still be the highest supported load, so
+    /// [`UpperBound`](SummaryPolicy::UpperBound) gives one less than `end`. If the estimate has no
+    /// upper bound, only [`LowerBound`](SummaryPolicy::LowerBound) gives a load, and the other
+    /// policies give `None`.
+    ///
+    /// ```rust
+    /// use cliff::{Estimate, SummaryPolicy};
+    ///
+    /// let estimate = Estimate::from(1500..1750);
+    /// assert_eq!(estimate.summarize(SummaryPolicy::LowerBound), Some(1500));
+    /// assert_eq!(estimate.summarize(SummaryPolicy::Midpoint), Some(1625));
+    /// assert_eq!(estimate.summarize(SummaryPolicy::UpperBound), Some(1749));
+    /// assert_eq!(estimate.summarize(SummaryPolicy::FractionBetween(0.1)), Some(1525));
+    /// ```
+    pub fn summarize(&self, policy: SummaryPolicy) -> Option<usize> {
+        if self.end == usize::MAX {
+            return match policy {
+                SummaryPolicy::LowerBound => Some(self.start),
+                _ => None,
+            };
+        }
+        let fraction = match policy {
+            SummaryPolicy::LowerBound => return Some(self.start),
+            SummaryPolicy::Midpoint => 0.5,
+            SummaryPolicy::UpperBound => return Some(self.end.saturating_sub(1).max(self.start)),
+            SummaryPolicy::FractionBetween(f) => f64::from(f).clamp(0.0, 1.0),
+        };
+        // an f64 may round a wide estimate's width up past the width itself
+        let offset = ((self.width() as f64 * fraction) as usize).min(self.width());
+        // never land on `end` itself, which is known not to be supported
+        Some(
+            self.start
+                .saturating_add(offset)
+                .min(self.end.saturating_sub(1))
+                .max(self.start),
+        )
+    }
+
+    /// Give the load at which to run a confirmation run according to `policy`, like an extended
+    /// soak run to check that the estimate holds up.
+    ///
+    /// A search only runs each load briefly, so the single most useful follow-up is usually a
+    /// longer run at a load the search found to be supported. This gives `None` if there is no
+    /// such load, that is, if the lower bound is `0`, and also if `policy` is
+    /// [`Within`](ConfirmationPolicy::Within) and [`summarize`](Estimate::summarize) gives `None`.
+    ///
+    /// ```rust
+    /// use cliff::{ConfirmationPolicy, Estimate, SummaryPolicy};
+    ///
+    /// let estimate = Estimate::from(1500..1750);
+    /// assert_eq!(estimate.confirmation_probe(ConfirmationPolicy::default()), Some(1500));
+    /// assert_eq!(estimate.confirmation_probe(ConfirmationPolicy::Headroom(0.2)), Some(1200));
+    /// assert_eq!(
+    ///     estimate.confirmation_probe(ConfirmationPolicy::Within(SummaryPolicy::Midpoint)),
+    ///     Some(1625)
+    /// );
+    /// ```
+    pub fn confirmation_probe(&self, policy: ConfirmationPolicy) -> Option<usize> {
+        if self.start == 0 {
+            return None;
+        }
+        match policy {
+            ConfirmationPolicy::LowerBound => Some(self.start),
+            ConfirmationPolicy::Headroom(f) => {
+                let below = (self.start as f64 * f64::from(f).clamp(0.0, 1.0)) as usize;
+                Some(self.start - below).filter(|&load| load != 0)
+            }
+            ConfirmationPolicy::Within(policy) => self.summarize(policy),
+        }
+    }
+
+    /// Widen the estimate to multiples of `quantum`, for example to publish round numbers.
+    ///
+    /// The lower bound is rounded down and the upper bound is rounded up, so anything the
+    /// original estimate contains, the rounded one does too. Rounding both bounds to the nearest
+    /// multiple instead can exclude the cliff. An upper bound that cannot be rounded up without
+    /// overflowing becomes `usize::MAX`, as does no upper bound at all. A `quantum` of `0` leaves
+    /// the estimate as it is.
+    ///
+    /// ```rust
+    /// use cliff::Estimate;
+    ///
+    /// let estimate = Estimate::from(1_234_567..1_290_001);
+    /// assert_eq!(estimate.round_outward(10_000), 1_230_000..1_300_000);
+    /// ```
+    pub fn round_outward(&self, quantum: usize) -> Estimate {
+        if quantum == 0 {
+            return *self;
+        }
+        let start = self.start - self.start % quantum;
+        let end = if self.end == usize::MAX || self.end % quantum == 0 {
+            self.end
+        } else {
+            (self.end - self.end % quantum).saturating_add(quantum)
+        };
+        Estimate { start, end }
+    }
+
+    /// Combine the estimates of independent shards into one for all of them together, like for a
+    /// cluster made up of those shards.
+    ///
+    /// The lower bound is the sum of the lower bounds. The upper bound is one more than the sum of
+    /// the highest loads each shard may still support, rather than the sum of the upper bounds,
+    /// since those are exclusive: two shards that each fall over at `1750` together support at
+    /// most `3498`, not `3499`. If any shard has no upper bound, neither does the sum, and an upper
+    /// bound that overflows becomes `usize::MAX`, meaning the same. The sum of no estimates is
+    /// `0..1`.
+    ///
+    /// This assumes the shards do not affect each other. If they share a network or a database,
+    /// the whole may be less than the sum of its parts; see [`discount`](Estimate::discount).
+    ///
+    /// ```rust
+    /// use cliff::Estimate;
+    ///
+    /// let shards = [1500..1750, 1200..1300, 1400..1500];
+    /// let cluster = Estimate::sum(shards.iter().cloned());
+    /// assert_eq!(cluster, 4100..4548);
+    /// assert_eq!(Estimate::sum(vec![1500..1750, 900..usize::MAX]), 2400..usize::MAX);
+    /// ```
+    pub fn sum<I>(estimates: I) -> Estimate
+    where
+        I: IntoIterator,
+        I::Item: Into<Estimate>,
+    {
+        let mut start = 0usize;
+        let mut top = Some(0usize);
+        for e in estimates {
+            let e = e.into();
+            start = start.saturating_add(e.start);
+            top = top.and_then(|top| Some(top.saturating_add(e.upper()?)));
+        }
+        Estimate {
+            start,
+            end: top.map_or(usize::MAX, |top| top.saturating_add(1)),
+        }
+    }
+
+    /// The estimate for `n` independent shards that each have this estimate.
+    ///
+    /// This is the same as the [`sum`](Estimate::sum) of `n` copies of the estimate.
+    ///
+    /// ```rust
+    /// use cliff::Estimate;
+    ///
+    /// assert_eq!(Estimate::from(1500..1750).scale(4), 6000..6997);
+    /// assert_eq!(Estimate::from(1500..usize::MAX).scale(4), 6000..usize::MAX);
+    /// ```
+    pub fn scale(&self, n: usize) -> Estimate {
+        Estimate {
+            start: self.start.saturating_mul(n),
+            end: self
+                .upper()
+                .map_or(usize::MAX, |top| top.saturating_mul(n).saturating_add(1)),
+        }
+    }
+
+    /// Lower the estimate by the given fraction, for example to account for shards that are
+    /// correlated after taking their [`sum`](Estimate::sum).
+    ///
+    /// Both the lower bound and the highest load that may still be supported are scaled down by
+    /// `fraction` and rounded down, so a `discount(0.1)` gives an estimate 10% lower. An estimate
+    /// without an upper bound keeps not having one. The fraction is clamped to between `0.0` and
+    /// `1.0`.
+    ///
+    /// ```rust
+    /// use cliff::Estimate;
+    ///
+    /// let cluster = Estimate::from(1500..1750).scale(4);
+    /// assert_eq!(cluster.discount(0.1), 5400..6297);
+    /// ```
+    pub fn discount(&self, fraction: f64) -> Estimate {
+        let keep = 1.0 - fraction.clamp(0.0, 1.0);
+        // an f64 may round loads near the top of the range up, so never let a discount raise a
+        // bound, or turn a bounded end into `usize::MAX`, which means there is no upper bound
+        Estimate {
+            start: ((self.start as f64 * keep) as usize).min(self.start),
+            end: self.upper().map_or(usize::MAX, |top| {
+                ((top as f64 * keep) as usize).min(top).min(usize::MAX - 2) + 1
+            }),
+        }
+    }
+
+    // the highest load that may still be supported, or `None` if there is no upper bound
+    fn upper(&self) -> Option<usize> {
+        self.lowest_unsupported()
+            .map(|end| end.saturating_sub(1).max(self.start))
+    }
+
+    /// Returns `true` if `load` may be the highest supported load.
+    ///
+    /// That is, `load` is at least `start`, and strictly less than `end`.
+    pub fn contains(&self, load: usize) -> bool {
+        self.start <= load && load < self.end
+    }
+}
+
+/// How to boil an [`Estimate`] down to a single load.
+///
+/// Teams publishing a single capacity number disagree on whether to use the lower bound of the
+/// estimate, which is conservative, or some point within it. Naming the choice makes it explicit,
+/// and keeps it consistent across reports.
+///
+/// See [`Estimate::summarize`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum SummaryPolicy {
+    /// The highest load known to be supported.
+    LowerBound,
+    /// The load halfway between the two bounds, rounded down.
+    Midpoint,
+    /// The highest load that may still be supported, which is one less than the lowest load known
+    /// not to be supported.
+    UpperBound,
+    /// The load the given fraction of the way from the lower bound to the upper bound, rounded
+    /// down. The fraction is clamped to between `0.0` and `1.0`.
+    FractionBetween(f32),
+}
+
+/// Where to run the confirmation run for an [`Estimate`].
+///
+/// See [`Estimate::confirmation_probe`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum ConfirmationPolicy {
+    /// The highest load known to be supported.
+    ///
+    /// This is the default, and checks that the lower bound of the estimate holds up under a
+    /// longer run.
+    LowerBound,
+    /// The given fraction below the highest load known to be supported, rounded up.
+    ///
+    /// A `Headroom(0.1)` runs at 90% of the lower bound, which checks that the system holds up
+    /// with some margin, like it would have to in production. The fraction is clamped to between
+    /// `0.0` and `1.0`.
+    Headroom(f32),
+    /// The load given by [`Estimate::summarize`] with the given policy, for checking whether a
+    /// load within the estimate is supported.
+    Within(SummaryPolicy),
+}
+
+impl Default for ConfirmationPolicy {
+    fn default() -> Self {
+        ConfirmationPolicy::LowerBound
+    }
+}
+
+impl From<Range<usize>> for Estimate {
+    fn from(r: Range<usize>) -> Self {
+        Estimate {
+            start: r.start,
+            end: r.end,
+        }
+    }
+}
+
+impl From<Estimate> for Range<usize> {
+    fn from(e: Estimate) -> Self {
+        e.start..e.end
+    }
+}
+
+impl PartialEq<Range<usize>> for Estimate {
+    fn eq(&self, other: &Range<usize>) -> bool {
+        self.start == other.start && self.end == other.end
+    }
+}
+
+#[test]
+fn helpers() {
+    let e = Estimate::from(1000..1100);
+    assert_eq!(e.width(), 100);
+    assert_eq!(e.relative_width(), 0.1);
+    assert_eq!(e.to_inclusive(), 1000..=1099);
+    assert!(!e.contains(999));
+    assert!(e.contains(1000));
+    assert!(e.contains(1099));
+    assert!(!e.contains(1100));
+    assert_eq!(Range::from(e), 1000..1100);
+}
+
+#[test]
+fn degenerate() {
+    let e = Estimate::from(500..500);
+    assert_eq!(e.width(), 0);
+    assert_eq!(e.relative_width(), 0.0);
+    assert!(e.to_inclusive().is_empty());
+    assert!(!e.contains(500));
+
+    let e = Estimate::from(0..0);
+    assert!(e.to_inclusive().is_empty());
+
+    let e = Estimate::from(500..usize::MAX);
+    assert_eq!(e.relative_width(), f64::INFINITY);
+    assert!(e.contains(usize::MAX - 1));
+}
+
+#[test]
+fn interpretations() {
+    let e = Estimate::from(1000..1100);
+    assert_eq!(e.highest_supported(), 1000);
+    assert_eq!(e.lowest_unsupported(), Some(1100));
+
+    let e = Estimate::from(1000..usize::MAX);
+    assert_eq!(e.highest_supported(), 1000);
+    assert_eq!(e.lowest_unsupported(), None);
+}
+
+#[test]
+fn summarize() {
+    let e = Estimate::from(1000..1100);
+    assert_eq!(e.summarize(SummaryPolicy::LowerBound), Some(1000));
+    assert_eq!(e.summarize(SummaryPolicy::Midpoint), Some(1050));
+    assert_eq!(e.summarize(SummaryPolicy::UpperBound), Some(1099));
+    assert_eq!(e.summarize(SummaryPolicy::FractionBetween(0.0)), Some(1000));
+    assert_eq!(
+        e.summarize(SummaryPolicy::FractionBetween(0.25)),
+        Some(1025)
+    );
+    assert_eq!(e.summarize(SummaryPolicy::FractionBetween(1.0)), Some(1099));
+    assert_eq!(e.summarize(SummaryPolicy::FractionBetween(7.0)), Some(1099));
+    assert_eq!(
+        e.summarize(SummaryPolicy::FractionBetween(-1.0)),
+        Some(1000)
+    );
+
+    let e = Estimate::from(1000..usize::MAX);
+    assert_eq!(e.summarize(SummaryPolicy::LowerBound), Some(1000));
+    assert_eq!(e.summarize(SummaryPolicy::Midpoint), None);
+    assert_eq!(e.summarize(SummaryPolicy::UpperBound), None);
+
+    let e = Estimate::from(500..500);
+    assert_eq!(e.summarize(SummaryPolicy::Midpoint), Some(500));
+    assert_eq!(e.summarize(SummaryPolicy::UpperBound), Some(500));
+
+    // widths an f64 cannot represent exactly do not overflow
+    let e = Estimate::from(1..usize::MAX - 1);
+    let top = usize::MAX - 2;
+    assert_eq!(e.summarize(SummaryPolicy::FractionBetween(1.0)), Some(top));
+    assert_eq!(e.summarize(SummaryPolicy::UpperBound), Some(top));
+    assert!(e.summarize(SummaryPolicy::Midpoint).unwrap() < top);
+    let e = Estimate::from(usize::MAX - 3..usize::MAX - 1);
+    assert_eq!(e.summarize(SummaryPolicy::FractionBetween(1.0)), Some(top));
+}
+
+#[test]
+fn round_outward() {
+    let e = Estimate::from(1050..1150);
+    assert_eq!(e.round_outward(100), 1000..1200);
+    assert_eq!(e.round_outward(1), 1050..1150);
+    assert_eq!(e.round_outward(0), 1050..1150);
+    // bounds that are already round stay put
+    assert_eq!(Estimate::from(1000..1200).round_outward(100), 1000..1200);
+    assert_eq!(Estimate::from(1000..1001).round_outward(100), 1000..1100);
+
+    assert_eq!(
+        Estimate::from(1050..usize::MAX).round_outward(100),
+        1000..usize::MAX
+    );
+    assert_eq!(
+        Estimate::from(1 << 21..usize::MAX - 1).round_outward(1 << 20),
+        1 << 21..usize::MAX
+    );
+}
+
+#[test]
+fn confirmation_probe() {
+    let e = Estimate::from(1000..1100);
+    assert_eq!(
+        e.confirmation_probe(ConfirmationPolicy::LowerBound),
+        Some(1000)
+    );
+    assert_eq!(
+        e.confirmation_probe(ConfirmationPolicy::Headroom(0.15)),
+        Some(850)
+    );
+    assert_eq!(
+        e.confirmation_probe(ConfirmationPolicy::Headroom(-1.0)),
+        Some(1000)
+    );
+    assert_eq!(
+        e.confirmation_probe(ConfirmationPolicy::Headroom(1.0)),
+        None
+    );
+    assert_eq!(
+        e.confirmation_probe(ConfirmationPolicy::Within(SummaryPolicy::UpperBound)),
+        Some(1099)
+    );
+
+    let e = Estimate::from(1000..usize::MAX);
+    assert_eq!(
+        e.confirmation_probe(ConfirmationPolicy::default()),
+        Some(1000)
+    );
+    assert_eq!(
+        e.confirmation_probe(ConfirmationPolicy::Within(SummaryPolicy::Midpoint)),
+        None
+    );
+
+    // nothing was sustained, so there is nothing to confirm
+    let e = Estimate::from(0..100);
+    assert_eq!(e.confirmation_probe(ConfirmationPolicy::default()), None);
+}
+
+#[test]
+fn sum() {
+    let shards = [
+        Estimate::from(1000..1100),
+        Estimate::from(500..501),
+        Estimate::from(0..10),
+    ];
+    assert_eq!(Estimate::sum(shards.iter().copied()), 1500..1609);
+    assert_eq!(Estimate::sum(core::iter::once(1000..1100)), 1000..1100);
+    assert_eq!(Estimate::sum(core::iter::empty::<Estimate>()), 0..1);
+    // an empty estimate counts as supporting its lower bound
+    assert_eq!(
+        Estimate::sum([500..500, 100..200].iter().cloned()),
+        600..700
+    );
+
+    // one unbounded shard makes the sum unbounded
+    let shards = [1000..1100, 1000..usize::MAX];
+    assert_eq!(Estimate::sum(shards.iter().cloned()), 2000..usize::MAX);
+    // and so does overflowing
+    let shards = [usize::MAX - 10..usize::MAX - 1, 100..200];
+    assert_eq!(
+        Estimate::sum(shards.iter().cloned()),
+        usize::MAX..usize::MAX
+    );
+}
+
+#[test]
+fn scale() {
+    let e = Estimate::from(1000..1100);
+    assert_eq!(e.scale(1), 1000..1100);
+    assert_eq!(e.scale(3), Estimate::sum([e, e, e].iter().copied()));
+    assert_eq!(e.scale(0), 0..1);
+    assert_eq!(Estimate::from(1000..usize::MAX).scale(2), 2000..usize::MAX);
+    assert_eq!(
+        Estimate::from(usize::MAX / 2..usize::MAX - 1).scale(3),
+        usize::MAX..usize::MAX
+    );
+
+    assert_eq!(e.discount(0.0), 1000..1100);
+    assert_eq!(e.discount(0.5), 500..550);
+    assert_eq!(e.discount(-1.0), 1000..1100);
+    assert_eq!(e.discount(2.0), 0..1);
+    assert_eq!(
+        Estimate::from(1000..usize::MAX).discount(0.5),
+        500..usize::MAX
+    );
+
+    // a bounded estimate near the top of the range stays bounded
+    let e = Estimate::from(usize::MAX - 10..usize::MAX - 1);
+    assert_eq!(e.discount(0.0), e);
+    let d = e.discount(1e-18);
+    assert!(d.lowest_unsupported().is_some());
+    assert!(d.start <= e.start && d.end <= e.end);
+    let e = Estimate::from(usize::MAX - 1..usize::MAX - 1);
+    assert_eq!(e.discount(0.0), e);
+}
@@ -1,44 +1,1021 @@
-use super::CliffSearch;
+use super::{
+    value, CliffSearch, Describe, Error, Refine, SearchValue, Status, Suspend, WarmStart,
+};
+use core::num::NonZeroUsize;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Why an [`ExponentialCliffSearcher`] yielded a load.
+///
+/// Downstream analysis will often want to treat these differently. For example, fill samples
+/// do not narrow the estimate, so they should not count towards convergence statistics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ProbeKind {
+    /// The very first probe, at the starting load.
+    Warmup,
+    /// The initial ramp, which doubles the load (or multiplies it by the
+    /// [growth factor](ExponentialCliffSearcher::with_growth_factor)) until the system is
+    /// overloaded.
+    Ramp,
+    /// The binary search between the highest supported and lowest unsupported load.
+    Bisect,
+    /// The extra samples leading up to the cliff added by
+    /// [`fill_left`](ExponentialCliffSearcher::fill_left).
+    Fill,
+    /// A repeat of the previous probe, since its run was
+    /// [inconclusive](ExponentialCliffSearcher::inconclusive).
+    Verify,
+}
+
+/// The rule an [`ExponentialCliffSearcher`] applied to pick its next probe, or to end.
+///
+/// See [`ExponentialCliffSearcher::explain_next`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum ProbeRule {
+    /// Run the starting load, to confirm that the system keeps up with it.
+    Start,
+    /// Double the highest sustained load (or multiply it by the
+    /// [growth factor](ProbeRationale::growth_factor)), since nothing has overloaded the system
+    /// yet.
+    Double,
+    /// Split the estimate down the middle.
+    Midpoint,
+    /// Split the estimate closer to its lower bound, since its upper bound overloaded the system
+    /// by the given factor, as reported with
+    /// [`overloaded_by`](ExponentialCliffSearcher::overloaded_by).
+    Skewed {
+        /// How badly the upper bound overloaded the system.
+        severity: f64,
+    },
+    /// Run the load the requested width above the highest sustained load, since the system only
+    /// just kept up with that, as reported with
+    /// [`marginal`](ExponentialCliffSearcher::marginal).
+    Marginal,
+    /// Run the previous load again, since its run was inconclusive.
+    Repeat,
+    /// Run a load near the given one instead, since that one could not be run.
+    Alternative {
+        /// The load that was meant to be run.
+        origin: usize,
+    },
+    /// Run the upper bound given to [`hint_upper`](ExponentialCliffSearcher::hint_upper), since
+    /// nothing below it has overloaded the system.
+    CheckHint,
+    /// Fill in samples below the cliff, as asked for with
+    /// [`fill_left`](ExponentialCliffSearcher::fill_left).
+    Fill,
+    /// End, since the estimate is as narrow as was asked for.
+    Converged,
+    /// End, since the load would have gone past the
+    /// [sanity cap](ExponentialCliffSearcher::sanity_cap).
+    Capped,
+    /// End, since the system kept up with the most the search may run, as set with
+    /// [`with_max`](ExponentialCliffSearcher::with_max).
+    AtMax,
+    /// End, since the system does not take in any more load than it already did.
+    Clamped,
+    /// End before the estimate is as narrow as was asked for, since the search ran out of runs or
+    /// time, or could not run anything near its last probe.
+    Stopped,
+}
+
+/// Why an [`ExponentialCliffSearcher`] will yield the load it yields next.
+///
+/// See [`ExponentialCliffSearcher::explain_next`]. The [`Display`](core::fmt::Display)
+/// implementation gives a sentence that is meant for operators, and that may change between
+/// releases.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ProbeRationale {
+    /// The load that will be yielded next, or `None` if the search will end instead.
+    pub load: Option<usize>,
+    /// The phase of the search the probe belongs to.
+    pub kind: Option<ProbeKind>,
+    /// The estimate the probe was picked from.
+    ///
+    /// This takes the outcome of the previous probe into account, so it may be narrower than the
+    /// current [`estimate`](ExponentialCliffSearcher::estimate).
+    pub bounds: core::ops::Range<usize>,
+    /// The width to which the search narrows down the estimate.
+    pub min_width: usize,
+    /// The factor by which the ramp raises the load, as set with
+    /// [`with_growth_factor`](ExponentialCliffSearcher::with_growth_factor).
+    pub growth_factor: f64,
+    /// The rule that picked the load.
+    pub rule: ProbeRule,
+}
+
+impl core::fmt::Display for ProbeRationale {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (start, end) = (self.bounds.start, self.bounds.end);
+        let load = match self.load {
+            Some(load) => load,
+            None => {
+                return match self.rule {
+                    ProbeRule::Capped => write!(
+                        f,
+                        "stop, since ramping past {} would exceed the sanity cap",
+                        start
+                    ),
+                    ProbeRule::Clamped => {
+                        write!(f, "stop, since the system takes in no more than {}", start)
+                    }
+                    ProbeRule::AtMax => {
+                        write!(f, "stop, since {} is the most the search may run", start)
+                    }
+                    ProbeRule::Stopped => write!(
+                        f,
+                        "stop early, since there are no runs left, or nothing could be run"
+                    ),
+                    _ => write!(
+                        f,
+                        "stop, since {}..{} is no wider than {}",
+                        start, end, self.min_width
+                    ),
+                };
+            }
+        };
+        match self.rule {
+            ProbeRule::Start => write!(f, "run {} to confirm the starting load", load),
+            ProbeRule::Double if self.growth_factor == 2.0 => write!(
+                f,
+                "run {}, twice the highest sustained load, since nothing has overloaded the system yet",
+                load
+            ),
+            ProbeRule::Double => write!(
+                f,
+                "run {}, {} times the highest sustained load, since nothing has overloaded the \
+                 system yet",
+                load, self.growth_factor
+            ),
+            ProbeRule::Midpoint => write!(
+                f,
+                "run {}, halfway between {} (sustained) and {} (overloaded)",
+                load, start, end
+            ),
+            ProbeRule::Skewed { severity } => write!(
+                f,
+                "run {}, between {} (sustained) and {} (overloaded) but closer to {}, since {} \
+                 overloaded the system {:.1}x",
+                load, start, end, start, end, severity
+            ),
+            ProbeRule::Marginal => write!(
+                f,
+                "run {}, just above {}, since the system only just kept up with it",
+                load, start
+            ),
+            ProbeRule::Repeat => write!(f, "run {} again, since its last run was inconclusive", load),
+            ProbeRule::Alternative { origin } => {
+                write!(f, "run {} instead of {}, which could not be run", load, origin)
+            }
+            ProbeRule::CheckHint => write!(
+                f,
+                "run {}, the hinted upper bound, since nothing below it overloaded the system",
+                load
+            ),
+            _ => write!(f, "run {} to fill in samples below the cliff", load),
+        }
+    }
+}
+
+/// The width to which an [`ExponentialCliffSearcher`] should determine the maximum load.
+///
+/// See [`ExponentialCliffSearcher::with_fidelity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Fidelity {
+    /// Determine the maximum load to within a range of the given width.
+    Width(usize),
+    /// Determine the maximum load to within 5% of the first load that overloaded the system.
+    ///
+    /// Picking a width up front requires knowing roughly where the cliff lies, which is what the
+    /// search is for in the first place. Instead, this lets the ramp find the scale of the cliff,
+    /// and fixes the width once the first upper bound is established.
+    Auto,
+    /// Spend exactly the given number of benchmark runs, and narrow the estimate as far as they
+    /// allow.
+    ///
+    /// This is for when the budget is a number of runs rather than a width. The search ramps up
+    /// until the first overload, and then bisects evenly with the runs that remain, as with
+    /// [`finalize_by`](ExponentialCliffSearcher::finalize_by). The estimate is whatever bracket
+    /// the runs bought, and may still have no upper bound if the ramp used them all up.
+    Probes(usize),
+}
+
+/// What an [`ExponentialCliffSearcher`] does when the first bracket it finds around the cliff is
+/// already no wider than the width it was asked to narrow the estimate down to.
+///
+/// This usually means that the width was picked for a different scale of load than the system
+/// turned out to have, like a `min_width` of `1000` for a system that falls over at `800`. The
+/// search then ends as soon as it finds an upper bound, with an estimate that may span a factor of
+/// two, and without bisecting at all.
+///
+/// See [`ExponentialCliffSearcher::on_coarse_fidelity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum CoarseFidelity {
+    /// End the search with the bracket as it is.
+    ///
+    /// This is the default.
+    Accept,
+    /// Narrow the width to 5% of the upper bound of the bracket, as with [`Fidelity::Auto`], and
+    /// bisect down to that.
+    Shrink,
+    /// End the search with the bracket as it is, and have
+    /// [`try_next`](ExponentialCliffSearcher::try_next) return an error.
+    Error,
+}
+
+impl Default for CoarseFidelity {
+    fn default() -> Self {
+        CoarseFidelity::Accept
+    }
+}
+
+impl From<usize> for Fidelity {
+    fn from(min_width: usize) -> Self {
+        Fidelity::Width(min_width)
+    }
+}
 
 /// An iterator that determines the maximum supported load for a system by exponential search.
 ///
 /// See the [crate-level documentation](..) for details.
-#[derive(Debug, Clone)]
-pub struct ExponentialCliffSearcher {
-    max_in: core::ops::Range<usize>,
-    start: usize,
-    prev_min: usize,
-    last: Option<usize>,
-    fidelity: usize,
+///
+/// # Formatting
+///
+/// Both [`Display`](core::fmt::Display) and [`Debug`](core::fmt::Debug) give a compact summary
+/// of the search that is suitable for logs: the estimate (with `∞` for no upper bound), the
+/// width the search is narrowing it down to, and what the search is currently doing (`warmup`,
+/// `ramp`, `bisect`, `fill`, or `done`). This format is kept stable across releases, so it can be
+/// used in golden-file tests. Debug with the alternate flag (`{:#?}`) instead gives every field
+/// of the search, which is useful when debugging the search itself, but which changes between
+/// releases.
+///
+/// ```rust
+/// use cliff::ExponentialCliffSearcher;
+///
+/// let mut load = ExponentialCliffSearcher::new(500);
+/// assert_eq!(format!("{:?}", load), "Exp{500..∞, fid=250, phase=warmup}");
+/// load.next();
+/// load.next();
+/// load.next();
+/// load.overloaded();
+/// load.next();
+/// assert_eq!(load.to_string(), "Exp{1000..2000, fid=250, phase=bisect}");
+/// ```
+#[derive(Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct ExponentialCliffSearcher<V = usize> {
+    max_in: core::ops::Range<V>,
+    prev_min: V,
+    last: Option<V>,
+    started: bool,
+    fidelity: V,
+    fidelity_fn: Option<fn(core::ops::Range<V>) -> V>,
+    auto_fidelity: bool,
     overloaded: bool,
+    inconclusive: bool,
+    severity: f64,
+    achieved: Option<V>,
     done: bool,
     fill_left: bool,
+    kind: Option<ProbeKind>,
+    deadline: Option<usize>,
+    #[cfg(feature = "std")]
+    time_limit: Option<TimeLimit>,
+    overflowed: bool,
+    cap: V,
+    capped: bool,
+    skip: bool,
+    skips: usize,
+    skip_origin: Option<V>,
+    clamp_tolerance: Option<f64>,
+    clamped_at: Option<V>,
+    hint: Option<V>,
+    on_coarse: CoarseFidelity,
+    bracketed: bool,
+    coarse: Option<(V, V)>,
+    growth: f64,
+    max: Option<V>,
+    marginal: bool,
+}
+
+// see `ExponentialCliffSearcher::DEFAULT_CAP_FACTOR`
+const CAP_FACTOR: usize = 1 << 20;
+
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+struct TimeLimit {
+    deadline: Instant,
+    since: Instant,
+    runs: u32,
 }
 
 impl ExponentialCliffSearcher {
     /// Perform a load search starting at `start`, and ending when the maximum load has been
     /// determined to within a range of `start / 2`.
+    ///
+    /// For a `start` of `1`, that is a width of `0`, which is taken to be `1` as described for
+    /// [`until`](ExponentialCliffSearcher::until). Small integer parameters, like a number of
+    /// replicas, are thus searched exactly. A `start` of `0` is taken to be `1`.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut replicas = ExponentialCliffSearcher::new(1);
+    /// while let Some(r) = replicas.next() {
+    ///     if r > 5 {
+    ///         replicas.overloaded();
+    ///     }
+    /// }
+    /// assert_eq!(replicas.estimate(), 5..6);
+    /// ```
     pub fn new(start: usize) -> Self {
         Self::until(start, start / 2)
     }
 
+    /// Perform a load search starting at the non-zero `start`, and ending when the maximum load
+    /// has been determined to within a range of `start / 2`.
+    ///
+    /// Since every probe is at least `start`, this guarantees that
+    /// [`estimate_nonzero`](ExponentialCliffSearcher::estimate_nonzero) returns `Some`.
+    pub fn new_nonzero(start: NonZeroUsize) -> Self {
+        Self::new(start.get())
+    }
+
+    /// Perform a load search starting at the non-zero `start`, and ending when the maximum load
+    /// has been determined to within a range of `min_width`.
+    pub fn until_nonzero(start: NonZeroUsize, min_width: usize) -> Self {
+        Self::until(start.get(), min_width)
+    }
+
     /// Perform a load search starting at `start`, and ending when the maximum load has been
     /// determined to within a range of `min_width`.
+    ///
+    /// Loads are whole numbers, so the estimate can be no narrower than `1`. A `min_width` of `0`
+    /// (or a [`fidelity_fn`](ExponentialCliffSearcher::fidelity_fn) that returns `0`) is taken to
+    /// be `1`, and so asks for the exact maximum. A `start` of `0` would never ramp up, since
+    /// doubling it gives `0` again, and is taken to be `1`.
     pub fn until(start: usize, min_width: usize) -> Self {
+        Self::starting_at(start, min_width)
+    }
+
+    /// Perform a load search starting at `start` that multiplies the load by `factor`, rather
+    /// than doubling it, until the system is overloaded.
+    ///
+    /// A smaller factor overshoots the cliff by less, which matters when an overloaded run is
+    /// much more expensive than one the system keeps up with, like when it takes minutes to time
+    /// out. A larger factor gets to a far-off cliff in fewer runs. Loads are rounded down, but
+    /// every step of the ramp raises the load by at least `1`. A `factor` that is not above `1`
+    /// would never find an upper bound, and is taken to be `2`.
+    ///
+    /// As with [`new`](ExponentialCliffSearcher::new), the search ends when the maximum load has
+    /// been determined to within a range of `start / 2`.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::with_growth_factor(1000, 1.5);
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(1500));
+    /// assert_eq!(load.next(), Some(2250));
+    /// assert_eq!(load.next(), Some(3375));
+    /// load.overloaded();
+    /// # while let Some(l) = load.next() { if l > 3000 { load.overloaded(); } }
+    /// assert_eq!(load.estimate(), 2812..3093);
+    /// ```
+    pub fn with_growth_factor(start: usize, factor: f64) -> Self {
+        let mut search = Self::new(start);
+        if factor > 1.0 {
+            search.growth = factor;
+        }
+        search
+    }
+
+    /// Perform a load search starting at `start` that never yields a load above `max`, like the
+    /// most a load generator can physically produce.
+    ///
+    /// When the ramp would go past `max`, `max` itself is run in its place, and if the system
+    /// keeps up with that, the search ends with [`Status::NeverOverloaded`]. Since no load above
+    /// `max` was ever run, the estimate then has no upper bound, and its lower bound is `max`. A
+    /// `start` above `max` is taken to be `max`.
+    ///
+    /// The [sanity cap](ExponentialCliffSearcher::sanity_cap) still applies, so a `max` more than
+    /// [`DEFAULT_CAP_FACTOR`](Self::DEFAULT_CAP_FACTOR) times `start` is only reached if the cap
+    /// is raised.
+    ///
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, Status};
+    ///
+    /// let mut load = ExponentialCliffSearcher::with_max(1_000_000, 10_000_000);
+    /// let mut ran = Vec::new();
+    /// while let Some(l) = load.next() {
+    ///     ran.push(l);
+    /// }
+    /// // no 16M, which the load generator could not have produced anyway
+    /// assert_eq!(ran, [1_000_000, 2_000_000, 4_000_000, 8_000_000, 10_000_000]);
+    /// assert_eq!(load.estimate(), 10_000_000..usize::MAX);
+    /// assert_eq!(load.status(), Status::NeverOverloaded);
+    /// ```
+    pub fn with_max(start: usize, max: usize) -> Self {
+        let mut search = Self::new(start.min(max));
+        search.max = Some(max);
+        search
+    }
+
+    /// Perform a load search starting at `start`, and ending when the maximum load has been
+    /// determined to within `fidelity`.
+    ///
+    /// With [`Fidelity::Auto`], the width is picked once the first upper bound is known:
+    ///
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, Fidelity};
+    ///
+    /// let mut load = ExponentialCliffSearcher::with_fidelity(500, Fidelity::Auto);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(1500));
+    /// // 5% of 2000
+    /// assert_eq!(load.to_string(), "Exp{1000..2000, fid=100, phase=bisect}");
+    /// # while let Some(l) = load.next() { if l > 1600 { load.overloaded(); } }
+    /// # assert_eq!(load.estimate(), 1562..1625);
+    /// ```
+    pub fn with_fidelity(start: usize, fidelity: Fidelity) -> Self {
+        match fidelity {
+            Fidelity::Width(min_width) => Self::until(start, min_width),
+            Fidelity::Auto => {
+                let mut search = Self::until(start, 0);
+                search.auto_fidelity = true;
+                search
+            }
+            Fidelity::Probes(probes) => {
+                let mut search = Self::until(start, 1);
+                search.finalize_by(probes);
+                search
+            }
+        }
+    }
+
+    /// Continue a search within `bracket`, as established by some other search, and end when the
+    /// maximum load has been determined to within a range of `min_width`.
+    ///
+    /// The lower bound of `bracket` is taken to be supported, and is not probed again. If
+    /// `bracket` has an upper bound, this is a plain binary search within it. Otherwise, the
+    /// search ramps up from the lower bound, which must then not be `0`.
+    ///
+    /// Any search's [estimate](CliffSearch::estimate) can be used as the bracket. For example, to
+    /// narrow down the estimate of a [`LoadIterator`](crate::LoadIterator) once it has stopped:
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher, LoadIterator};
+    ///
+    /// let mut list = LoadIterator::from(&[1000, 2000, 4000]);
+    /// while let Some(l) = list.next() {
+    ///     if l > 2500 {
+    ///         list.overloaded();
+    ///     }
+    /// }
+    /// assert_eq!(list.estimate(), 2000..4000);
+    ///
+    /// let mut load = ExponentialCliffSearcher::within(list.estimate(), 500);
+    /// assert_eq!(load.next(), Some(3000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(2500));
+    /// assert_eq!(load.next(), None);
+    /// assert_eq!(load.estimate(), 2500..3000);
+    /// ```
+    pub fn within(bracket: core::ops::Range<usize>, min_width: usize) -> Self {
+        let mut search = Self::until(bracket.start, min_width);
+        // unlike a starting load, a lower bound of 0 is fine for a bracket with an upper bound
+        search.prev_min = bracket.start;
+        search.cap = bracket.start.saturating_mul(Self::DEFAULT_CAP_FACTOR);
+        search.max_in = bracket;
+        search.started = true;
+        search
+    }
+
+    /// The multiple of the starting load at which the search gives up on ever overloading the
+    /// system by default.
+    ///
+    /// See [`sanity_cap`](ExponentialCliffSearcher::sanity_cap).
+    pub const DEFAULT_CAP_FACTOR: usize = CAP_FACTOR;
+
+    /// Give the number of loads a search starting at `start` tries before it has ramped above
+    /// `cap`, including the first load above `cap`.
+    ///
+    /// This is the worst case for a system that never supports more than `cap`. If doubling the
+    /// load would overflow before it gets above `cap`, the ramp ends there instead. A `start` of
+    /// `0` is taken to be `1`, as it is by [`until`](ExponentialCliffSearcher::until). The ramp
+    /// doubles the load, as it does by default, so this does not hold for a search made with
+    /// [`with_growth_factor`](ExponentialCliffSearcher::with_growth_factor).
+    ///
+    /// This is a `const fn`, so it can be used to size a table of the ramp's loads at compile
+    /// time. See [`ramp_schedule`](ExponentialCliffSearcher::ramp_schedule).
+    pub const fn max_ramp_probes(start: usize, cap: usize) -> usize {
+        let mut load = if start == 0 { 1 } else { start };
+        let mut probes = 1;
+        while load <= cap {
+            match load.checked_mul(2) {
+                Some(next) => load = next,
+                None => return probes,
+            }
+            probes += 1;
+        }
+        probes
+    }
+
+    /// Give the first `N` loads a search starting at `start` tries as long as the system keeps up.
+    ///
+    /// Entries for loads that do not fit in a `usize` are left as `0`. A `start` of `0` is taken to
+    /// be `1`, as it is by [`until`](ExponentialCliffSearcher::until). Like
+    /// [`max_ramp_probes`](ExponentialCliffSearcher::max_ramp_probes), this is the schedule of the
+    /// default ramp, which doubles the load, and not that of a search made with
+    /// [`with_growth_factor`](ExponentialCliffSearcher::with_growth_factor).
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// const N: usize = ExponentialCliffSearcher::max_ramp_probes(500, 5_000);
+    /// const RAMP: [usize; N] = ExponentialCliffSearcher::ramp_schedule(500);
+    /// assert_eq!(RAMP, [500, 1000, 2000, 4000, 8000]);
+    /// ```
+    pub const fn ramp_schedule<const N: usize>(start: usize) -> [usize; N] {
+        let mut loads = [0; N];
+        let mut load = if start == 0 { 1 } else { start };
+        let mut i = 0;
+        while i < N {
+            loads[i] = load;
+            match load.checked_mul(2) {
+                Some(next) => load = next,
+                None => break,
+            }
+            i += 1;
+        }
+        loads
+    }
+
+    /// Give the largest number of loads a search made with
+    /// [`until(start, min_width)`](ExponentialCliffSearcher::until) tries for a system that never
+    /// supports more than `cap`.
+    ///
+    /// This assumes that every overload is reported with
+    /// [`overloaded`](ExponentialCliffSearcher::overloaded) rather than
+    /// [`overloaded_by`](ExponentialCliffSearcher::overloaded_by), and does not count re-runs of
+    /// inconclusive loads or samples added by [`fill_left`](ExponentialCliffSearcher::fill_left).
+    /// A `min_width` of `0` is treated as `1`, and so is a `start` of `0`.
+    ///
+    /// This is a `const fn`, so it can be used to size buffers without allocating, such as the
+    /// one kept by [`Recorded`](crate::Recorded):
+    ///
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, Recorded};
+    ///
+    /// const RUNS: usize = ExponentialCliffSearcher::max_probes(500, 250, 10_000);
+    /// let mut load = Recorded::<_, RUNS>::new(ExponentialCliffSearcher::new(500));
+    /// while let Some(l) = load.next() {
+    ///     if l > 1600 {
+    ///         load.overloaded();
+    ///     }
+    /// }
+    /// assert!(!load.is_truncated());
+    /// ```
+    pub const fn max_probes(start: usize, min_width: usize, cap: usize) -> usize {
+        let start = if start == 0 { 1 } else { start };
+        if start > cap {
+            // the very first load overloads
+            return 1;
+        }
+        let mut probes = 1;
+        let mut supported = start;
+        let overloaded = loop {
+            match supported.checked_mul(2) {
+                Some(next) => {
+                    probes += 1;
+                    if next > cap {
+                        break next;
+                    }
+                    supported = next;
+                }
+                // the ramp ends without an upper bound, so there is nothing to bisect
+                None => return probes,
+            }
+        };
+
+        // in the worst case, every bisection keeps the larger half
+        let min_width = if min_width == 0 { 1 } else { min_width };
+        let mut width = overloaded - supported;
+        while width > min_width {
+            width -= width / 2;
+            probes += 1;
+        }
+        probes
+    }
+
+    // NOTE: we provide inherent methods for CliffSearch so that those who do not need LoadIterator
+    // do not need to think about the trait at all.
+
+    /// Adopt whichever bounds of `other`'s estimate are tighter than this search's own.
+    ///
+    /// The lower bound becomes the higher of the two lower bounds, and the upper bound the lower
+    /// of the two upper bounds. Both searches are assumed to be of the same system, so `other`
+    /// should only have been told about outcomes that actually happened. A pending probe that
+    /// falls outside the merged estimate is dropped, since its outcome can no longer tighten it.
+    ///
+    /// If the merged bounds contradict each other, this returns [`Error::Contradiction`] and
+    /// leaves the search as it was.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    ///
+    /// // 1000 was actually run on a branch, and overloaded the system
+    /// let mut branch = load.branch();
+    /// branch.overloaded();
+    /// assert_eq!(branch.next(), Some(750));
+    ///
+    /// load.merge_tighter(&branch).unwrap();
+    /// assert_eq!(load.estimate(), 500..1000);
+    /// assert_eq!(load.next(), Some(750));
+    /// ```
+    pub fn merge_tighter(&mut self, other: &Self) -> Result<(), Error> {
+        let start = self.max_in.start.max(other.max_in.start);
+        let end = self.max_in.end.min(other.max_in.end);
+        if start > end {
+            return Err(Error::Contradiction {
+                supported: start,
+                overloaded: end,
+            });
+        }
+        if start != self.max_in.start {
+            self.prev_min = self.max_in.start;
+            // the adopted lower bound has been run, so there's no need to confirm it
+            self.started = true;
+        }
+        self.max_in = start..end;
+        if self.last.map_or(false, |last| last <= start || last >= end) {
+            self.last = None;
+            self.overloaded = false;
+            self.inconclusive = false;
+            self.severity = 1.0;
+            self.marginal = false;
+            self.achieved = None;
+        }
+        if self.max_in.end != usize::MAX {
+            // the ramp has nothing left to find
+            self.capped = false;
+        }
+        Ok(())
+    }
+
+    /// Give the current estimate as a range of non-zero loads.
+    ///
+    /// Returns `None` if the lower bound of the estimate is `0`, like for a search
+    /// [`within`](ExponentialCliffSearcher::within) a bracket that starts at `0`.
+    pub fn estimate_nonzero(&self) -> Option<core::ops::Range<NonZeroUsize>> {
+        Some(NonZeroUsize::new(self.max_in.start)?..NonZeroUsize::new(self.max_in.end)?)
+    }
+
+    /// Explain what the next call to [`Iterator::next`] will yield, and why.
+    ///
+    /// This takes the outcome reported for the previous probe into account, just as
+    /// [`Iterator::next`] would, but does not change the search.
+    ///
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, ProbeKind, ProbeRule};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// load.overloaded();
+    ///
+    /// let why = load.explain_next();
+    /// assert_eq!(why.load, Some(750));
+    /// assert_eq!(why.kind, Some(ProbeKind::Bisect));
+    /// assert_eq!(why.rule, ProbeRule::Midpoint);
+    /// assert_eq!(why.bounds, 500..1000);
+    /// assert_eq!(
+    ///     why.to_string(),
+    ///     "run 750, halfway between 500 (sustained) and 1000 (overloaded)"
+    /// );
+    /// assert_eq!(load.next(), Some(750));
+    /// ```
+    pub fn explain_next(&self) -> ProbeRationale {
+        let mut ahead = self.clone();
+        let load = ahead.next();
+        let bounds = ahead.max_in.clone();
+        let rule = match (load, ahead.kind) {
+            (None, _) => {
+                if ahead.clamped_at.is_some() {
+                    ProbeRule::Clamped
+                } else if ahead.capped && ahead.max.map_or(false, |max| bounds.start >= max) {
+                    ProbeRule::AtMax
+                } else if ahead.capped {
+                    ProbeRule::Capped
+                } else if bounds.end - bounds.start <= ahead.min_width() {
+                    ProbeRule::Converged
+                } else {
+                    ProbeRule::Stopped
+                }
+            }
+            (Some(_), _) if self.skip && ahead.skip_origin.is_some() => ProbeRule::Alternative {
+                origin: ahead.skip_origin.unwrap_or_default(),
+            },
+            (Some(_), Some(ProbeKind::Warmup)) => ProbeRule::Start,
+            (Some(load), _)
+                if self.marginal && load == bounds.start.saturating_add(ahead.min_width()) =>
+            {
+                ProbeRule::Marginal
+            }
+            (Some(_), Some(ProbeKind::Ramp)) => ProbeRule::Double,
+            (Some(_), Some(ProbeKind::Verify)) => ProbeRule::Repeat,
+            (Some(_), Some(ProbeKind::Fill)) => ProbeRule::Fill,
+            (Some(load), _) if load == bounds.end => ProbeRule::CheckHint,
+            (Some(load), _) if load == bounds.start + (bounds.end - bounds.start) / 2 => {
+                ProbeRule::Midpoint
+            }
+            (Some(_), _) => ProbeRule::Skewed {
+                severity: self.severity,
+            },
+        };
+        ProbeRationale {
+            load,
+            kind: ahead.kind,
+            min_width: ahead.min_width(),
+            growth_factor: self.growth,
+            bounds,
+            rule,
+        }
+    }
+
+    /// Perform a load search starting at `start`, and ending when the maximum load has been
+    /// determined to within a range of `min_width`.
+    ///
+    /// Unlike [`until`](ExponentialCliffSearcher::until), which takes a `start` of `0` to be `1`,
+    /// this returns an error if `start` is `0`, since that is usually a sign of a starting load
+    /// that was never configured.
+    pub fn try_until(start: usize, min_width: usize) -> Result<Self, Error> {
+        if start == 0 {
+            return Err(Error::InvalidConfig("the starting load must be non-zero"));
+        }
+        Ok(Self::until(start, min_width))
+    }
+
+    /// Like [`warm_start`](ExponentialCliffSearcher::warm_start), but returns an error rather than
+    /// ignoring results that contradict each other, or if the search has already started.
+    ///
+    /// The search is left unchanged if an error is returned.
+    ///
+    /// ```rust
+    /// use cliff::{Error, ExponentialCliffSearcher};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// assert_eq!(
+    ///     load.try_warm_start(&[(1000, false), (3000, true), (3500, false)]),
+    ///     Err(Error::Contradiction { supported: 3500, overloaded: 3000 })
+    /// );
+    /// ```
+    pub fn try_warm_start(&mut self, history: &[(usize, bool)]) -> Result<WarmStart, Error> {
+        if self.started {
+            return Err(Error::Misuse("the search has already started"));
+        }
+        let supported = history.iter().filter(|h| !h.1).map(|h| h.0).max();
+        let overloaded = history.iter().filter(|h| h.1).map(|h| h.0).min();
+        if let (Some(supported), Some(overloaded)) = (supported, overloaded) {
+            if supported >= overloaded {
+                return Err(Error::Contradiction {
+                    supported,
+                    overloaded,
+                });
+            }
+        }
+        Ok(self.warm_start(history))
+    }
+}
+
+impl<V> ExponentialCliffSearcher<V>
+where
+    V: SearchValue,
+{
+    /// Perform a load search over loads of any [`SearchValue`] type, starting at `start`, and
+    /// ending when the maximum load has been determined to within a range of `min_width`.
+    ///
+    /// This is [`until`](ExponentialCliffSearcher::until) for loads that are not `usize`, like an
+    /// `f64` rate of requests per second. As for `usize` loads, a `min_width` below `1` is taken to
+    /// be `1`, and a `start` below `1` is taken to be `1`.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::starting_at(500.0, 100.0);
+    /// while let Some(l) = load.next() {
+    ///     if l > 1600.0 {
+    ///         load.overloaded();
+    ///     }
+    /// }
+    /// assert_eq!(load.estimate(), 1562.5..1625.0);
+    /// ```
+    pub fn starting_at(start: V, min_width: V) -> Self {
+        let start = value::max(start, V::ONE);
         Self {
-            max_in: start..usize::max_value(),
-            start,
+            max_in: start..V::UNBOUNDED,
             prev_min: start,
             fidelity: min_width,
+            fidelity_fn: None,
+            auto_fidelity: false,
             last: None,
+            started: false,
             overloaded: false,
+            inconclusive: false,
+            severity: 1.0,
+            achieved: None,
             done: false,
             fill_left: false,
+            kind: None,
+            deadline: None,
+            #[cfg(feature = "std")]
+            time_limit: None,
+            overflowed: false,
+            cap: start.scaled(CAP_FACTOR, 1),
+            capped: false,
+            skip: false,
+            skips: 0,
+            skip_origin: None,
+            clamp_tolerance: None,
+            clamped_at: None,
+            hint: None,
+            on_coarse: CoarseFidelity::Accept,
+            bracketed: false,
+            coarse: None,
+            growth: 2.0,
+            max: None,
+            marginal: false,
         }
     }
 
-    // NOTE: we provide inherent methods for CliffSearch so that those who do not need LoadIterator
-    // do not need to think about the trait at all.
+    // the next load of the ramp after `load`, or `None` if it does not fit
+    fn grow(&self, load: V) -> Option<V> {
+        if let Some(max) = self.max {
+            if load >= max {
+                return None;
+            }
+            return Some(self.grow_unbounded(load).map_or(max, |next| value::min(next, max)));
+        }
+        self.grow_unbounded(load)
+    }
+
+    fn grow_unbounded(&self, load: V) -> Option<V> {
+        if self.growth == 2.0 {
+            // exact, even for loads too large for an f64 to represent
+            return load.doubled();
+        }
+        let next = load.to_f64() * self.growth;
+        if next >= V::UNBOUNDED.to_f64() {
+            return None;
+        }
+        // a small factor must not get stuck on a small load, but a load of 0 stays at 0 just
+        // like it does when doubling
+        if load == V::ZERO {
+            Some(V::ZERO)
+        } else {
+            Some(value::max(V::from_f64(next), load + V::ONE))
+        }
+    }
+
+    // keep a probe from going above the most the search may run
+    fn clamp_to_max(&self, load: V) -> V {
+        self.max.map_or(load, |max| value::min(load, max))
+    }
+
+    /// Take `upper` to be roughly where the cliff lies, like from an earlier search, and bisect
+    /// towards it rather than ramping up to find an upper bound.
+    ///
+    /// The ramp of a search without an upper bound runs the highest loads of the whole search,
+    /// and the last of them overloads the system by up to a factor of two, so with a good hint,
+    /// skipping it saves the most expensive runs. The hint is not trusted blindly, though: if
+    /// every probe below it is sustained, the search runs `upper` itself before it ends, and if
+    /// the system keeps up with that too, the search ramps up from there as usual.
+    ///
+    /// A hint at or below the lower bound of the estimate, or at or above its upper bound, is
+    /// ignored.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::until(500, 100);
+    /// load.hint_upper(2000);
+    /// assert_eq!(load.next(), Some(500));
+    /// // no ramp to 1000 and 2000, and straight to bisecting
+    /// assert_eq!(load.next(), Some(1250));
+    /// assert_eq!(load.next(), Some(1625));
+    /// load.overloaded();
+    /// # while let Some(l) = load.next() { if l > 1600 { load.overloaded(); } }
+    /// assert_eq!(load.estimate(), 1531..1625);
+    ///
+    /// // with a hint that is too low, the search finds out, and carries on past it
+    /// let mut load = ExponentialCliffSearcher::until(500, 100);
+    /// load.hint_upper(1000);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(750));
+    /// assert_eq!(load.next(), Some(875));
+    /// assert_eq!(load.next(), Some(937));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// ```
+    pub fn hint_upper(&mut self, upper: V) {
+        if upper > self.max_in.start && upper < self.max_in.end {
+            self.max_in.end = upper;
+            self.hint = Some(upper);
+        }
+    }
+
+    /// Give up ramping once the load would exceed `cap`.
+    ///
+    /// A benchmark that is misconfigured so that it never actually loads the system would
+    /// otherwise have the search double the load until it no longer fits in a `usize`, which
+    /// takes a long time and ends with an absurd estimate. Instead, the search ends when the ramp
+    /// would go above `cap`, and its [`status`](ExponentialCliffSearcher::status) is
+    /// [`Status::NeverOverloaded`]. The estimate then has no upper bound, and its lower bound is
+    /// the highest load that was tried.
+    ///
+    /// The cap defaults to [`DEFAULT_CAP_FACTOR`](Self::DEFAULT_CAP_FACTOR) times the starting
+    /// load. Raising the cap resumes a search that had ended because of it.
+    ///
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, Status};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// load.sanity_cap(4000);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// assert_eq!(load.next(), Some(4000));
+    /// assert_eq!(load.next(), None);
+    /// assert_eq!(load.estimate(), 4000..usize::MAX);
+    /// assert_eq!(load.status(), Status::NeverOverloaded);
+    /// ```
+    pub fn sanity_cap(&mut self, cap: V) {
+        self.cap = cap;
+        let regrows = self.grow(self.max_in.start).map_or(false, |next| next <= cap);
+        if self.capped && self.clamped_at.is_none() && regrows {
+            self.capped = false;
+            self.done = false;
+        }
+    }
+
+    /// Tell how the search ended, or is ending.
+    ///
+    /// This is [`Status::NeverOverloaded`] if the search gave up on finding an overload because it
+    /// hit its [sanity cap](ExponentialCliffSearcher::sanity_cap), because the next load would
+    /// not fit in a `usize`, or because the system kept up with the
+    /// [`with_max`](ExponentialCliffSearcher::with_max) load.
+    ///
+    /// This provides [`CliffSearch::status`] without having to `use` the trait.
+    pub fn status(&self) -> Status {
+        if self.capped {
+            Status::NeverOverloaded
+        } else {
+            Status::Completed
+        }
+    }
+
+    /// Estimate how many more benchmark runs the search will perform, counting the one for the
+    /// load just yielded.
+    ///
+    /// This is `None` while the search is still ramping up, and while it is
+    /// [filling](ExponentialCliffSearcher::fill_left) samples below the cliff. Otherwise, it is
+    /// the number of bisections it takes to narrow the estimate down to the requested width if
+    /// every split goes the worse way, or the number of runs left before a
+    /// [deadline](ExponentialCliffSearcher::finalize_by) if that is fewer.
+    ///
+    /// This provides [`CliffSearch::probes_left`] without having to `use` the trait.
+    pub fn probes_left(&self) -> Option<usize> {
+        let pending = self.last.is_some() as usize;
+        let budget = self.deadline.map(|left| left + pending);
+        if self.done {
+            return if self.fill_left { None } else { Some(0) };
+        }
+        if self.max_in.end == V::UNBOUNDED {
+            return budget;
+        }
+        let min_width = self.min_width();
+        let mut width = self.max_in.end - self.max_in.start;
+        let mut left = 0;
+        while width > min_width {
+            width = width - width.scaled(1, 2);
+            left += 1;
+        }
+        Some(budget.map_or(left, |budget| budget.min(left)))
+    }
 
     /// Indicate that the system could not keep up with the previous load factor yielded by
     /// [`Iterator::next`].
@@ -50,201 +1027,2571 @@ impl ExponentialCliffSearcher {
         self.overloaded = true;
     }
 
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// If the system missed its target by more than a factor of two, the next probe will be that
+    /// many times closer to the highest supported load than a plain bisection would be. For
+    /// example, if the system supported 4000, but missed its target by 8x at 8000, the next load
+    /// tried is 4500 rather than 6000.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        self.severity = factor;
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same load again, so that it can be re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// The load counts as sustained, but since the cliff is probably right above it, the next
+    /// load tried is the requested width above it, rather than halfway to the upper bound or
+    /// twice as high. If the system keeps up with that too, the search carries on as usual.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::until(500, 100);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(1500));
+    /// // the latency objective was only just met
+    /// load.marginal();
+    /// assert_eq!(load.next(), Some(1600));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), None);
+    /// assert_eq!(load.estimate(), 1500..1600);
+    /// ```
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.marginal = true;
+    }
+
+    /// Indicate that the previous load yielded by [`Iterator::next`] could not be run at all, for
+    /// example because the load generator cannot be configured for that exact load.
+    ///
+    /// Unlike [`inconclusive`](ExponentialCliffSearcher::inconclusive), which repeats the load,
+    /// this makes the next call to [`Iterator::next`] yield a different load close to the one that
+    /// was skipped and that tells the search nearly as much. Up to six nearby loads are tried in
+    /// turn, alternating below and above the skipped one. If none of them can be run either, the
+    /// search ends with the estimate it has.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(1500));
+    /// // the load generator can't do 1500
+    /// load.skip_last();
+    /// assert_eq!(load.next(), Some(1375));
+    /// assert_eq!(load.next(), Some(1687));
+    /// ```
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        if self.last.is_some() {
+            self.skip = true;
+            self.overloaded = false;
+            self.inconclusive = false;
+            self.severity = 1.0;
+            self.marginal = false;
+            self.achieved = None;
+        }
+    }
+
+    // a load near the skipped `origin` that is still worth probing, if any are left
+    fn next_alternative(&mut self, origin: V) -> Option<V> {
+        const ALTERNATIVES: usize = 6;
+        while self.skips < ALTERNATIVES {
+            self.skips += 1;
+            let alternative = if self.kind == Some(ProbeKind::Warmup) {
+                // anything above the starting load will do, since nothing has been learned yet
+                origin.offset(origin.scaled(1, 8).scaled(self.skips, 1))
+            } else {
+                // stay well inside the range the probe was meant to split
+                let lo = self.max_in.start;
+                let hi = self.max_in.end;
+                let step = value::min(origin - lo, hi - origin).scaled(1, 4);
+                let offset = step.scaled((self.skips + 1) / 2, 1);
+                if self.skips % 2 == 1 {
+                    Some(origin - offset)
+                } else {
+                    origin.offset(offset).filter(|&a| a < hi)
+                }
+            };
+            match alternative {
+                Some(a) if a != origin && a > self.max_in.start => return Some(a),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// If `actual` is below the requested load, the probe is treated as overloaded, and `actual`
+    /// (rather than the requested load) becomes the upper limit for the maximum load, as long as
+    /// it is above the highest load known to be supported.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: V) {
+        self.achieved = Some(actual);
+    }
+
     /// Give the current estimate of the maximum load the system-under-test can support.
     ///
     /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
-    pub fn estimate(&self) -> core::ops::Range<usize> {
+    pub fn estimate(&self) -> core::ops::Range<V> {
         self.max_in.clone()
     }
 
-    /// Ensure that samples are taken just before the cliff.
+    /// Tell apart systems that clamp the load they let in from systems that fall over.
     ///
-    /// If the system under test supports, say, eight million operations per second, and searches
-    /// starting at 1M. The searcher will first sample 1M, 2M, 4M, 8M, 16M. When it detects that
-    /// 16M is overloaded, it'll perform a binary search from the "right", running 12M, 10M, and 9M
-    /// before ultimately deciding that 8M is the lower bound for the cliff.
+    /// Some systems limit their intake internally, like with a token bucket, and keep up just
+    /// fine with whatever they let in. Requesting more load than that does not tell the search
+    /// anything, and the benchmark may even report such runs as sustained, which gives a false
+    /// lower bound. With this, a run whose [achieved](ExponentialCliffSearcher::achieved) load
+    /// falls more than `tolerance` (a fraction of the requested load) below the requested load,
+    /// and which was not also reported as overloaded, is taken to mean that the system clamps its
+    /// intake at the achieved load. The achieved load then becomes the lower bound of the
+    /// estimate, [`clamped_at`](ExponentialCliffSearcher::clamped_at) gives it, and the search
+    /// ends, since no higher load can be offered. If no load has overloaded the system by then,
+    /// the [`status`](ExponentialCliffSearcher::status) is [`Status::NeverOverloaded`].
     ///
-    /// This evaluation is correct, but may yield strange-looking results when plotting as the
-    /// system is likely highly loaded at 8M. In particular, a straight line drawn from the 4M
-    /// sample to the 8M sample may yield a jarring visual image, and will make it hard to see what
-    /// happens in the system leading up to its capacity.
+    /// Smaller shortfalls are treated as overloads, as they are without this.
     ///
-    /// This method makes the searcher "fill in" extra samples to the left of the lower bound when
-    /// necessary. In the case above, after finding that 8M is the lower bound, this filling would
-    /// also sample 6M and 7M.
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, Status};
     ///
-    /// Filling also respects the minimum search range width if specified with [`until`].
-    pub fn fill_left(&mut self) {
-        self.fill_left = true;
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// load.detect_clamping(0.2);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// // the system's rate limiter only lets 1200 through
+    /// load.achieved(1200);
+    /// assert_eq!(load.next(), None);
+    /// assert_eq!(load.clamped_at(), Some(1200));
+    /// assert_eq!(load.estimate(), 1200..usize::MAX);
+    /// assert_eq!(load.status(), Status::NeverOverloaded);
+    /// ```
+    pub fn detect_clamping(&mut self, tolerance: f64) {
+        self.clamp_tolerance = Some(tolerance);
+    }
+
+    /// Give the load at which the system was found to clamp its intake, if it was.
+    ///
+    /// See [`detect_clamping`](ExponentialCliffSearcher::detect_clamping).
+    pub fn clamped_at(&self) -> Option<V> {
+        self.clamped_at
+    }
+
+    /// Decide what to do if the first bracket the search finds around the cliff is already no
+    /// wider than the width it was asked to narrow the estimate down to.
+    ///
+    /// Whichever `policy` is picked, [`coarse_fidelity`](ExponentialCliffSearcher::coarse_fidelity)
+    /// tells whether that happened, and [`warn`](ExponentialCliffSearcher::warn) adds it to a
+    /// [`Report`](crate::Report).
+    ///
+    /// ```rust
+    /// use cliff::{CoarseFidelity, ExponentialCliffSearcher};
+    ///
+    /// let mut load = ExponentialCliffSearcher::until(500, 1000);
+    /// load.on_coarse_fidelity(CoarseFidelity::Shrink);
+    /// while let Some(l) = load.next() {
+    ///     if l > 800 {
+    ///         load.overloaded();
+    ///     }
+    /// }
+    /// // rather than ending with 500..1000
+    /// assert_eq!(load.estimate(), 781..812);
+    /// assert_eq!(load.coarse_fidelity(), Some((1000, 500)));
+    /// ```
+    pub fn on_coarse_fidelity(&mut self, policy: CoarseFidelity) {
+        self.on_coarse = policy;
     }
+
+    /// Give the width the search was asked for and the width of the first bracket it found
+    /// around the cliff, if the latter was no wider than the former.
+    ///
+    /// See [`on_coarse_fidelity`](ExponentialCliffSearcher::on_coarse_fidelity).
+    pub fn coarse_fidelity(&self) -> Option<(V, V)> {
+        self.coarse
+    }
+
+    /// Add a warning to `report` if the first bracket the search found around the cliff was no
+    /// wider than the width it was asked for.
+    ///
+    /// This method is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn warn(&self, report: &mut crate::Report) {
+        if let Some((min_width, bracket)) = self.coarse {
+            let mut warning = alloc::format!(
+                "the first bracket around the cliff was {} wide, which is no wider than the \
+                 requested width of {}",
+                bracket,
+                min_width
+            );
+            if self.on_coarse == CoarseFidelity::Shrink {
+                warning.push_str(&alloc::format!(
+                    ", so the width was shrunk to {}",
+                    self.fidelity
+                ));
+            }
+            report.warnings.push(warning);
+        }
+    }
+
+    /// Returns `true` if the search has found a load the system-under-test could not keep up with.
+    ///
+    /// While this is `false`, the search is still ramping up, and the cliff could be anywhere above
+    /// the lower bound of the estimate.
+    ///
+    /// A search whose ramp runs into its [sanity cap](ExponentialCliffSearcher::sanity_cap), or
+    /// into a load that would not fit in a `usize`, ends without ever finding the cliff. It does
+    /// not wrap around or panic, but ends with an estimate of `last..usize::MAX`, where `last` is
+    /// the highest load that was tried, and this stays `false`.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let top = usize::MAX / 2 + 1;
+    /// let mut load = ExponentialCliffSearcher::new(top);
+    /// assert_eq!(load.next(), Some(top));
+    /// // twice `top` does not fit in a `usize`
+    /// assert_eq!(load.next(), None);
+    /// assert!(!load.has_upper_bound());
+    /// assert_eq!(load.estimate(), top..usize::MAX);
+    /// ```
+    ///
+    /// This provides [`CliffSearch::has_upper_bound`] without having to `use` the trait.
+    pub fn has_upper_bound(&self) -> bool {
+        self.max_in.end != V::UNBOUNDED
+    }
+
+    /// Change the width to which the maximum load should be determined, and resume the search if it had
+    /// ended with a wider estimate than that.
+    ///
+    /// This replaces any [`fidelity_fn`](ExponentialCliffSearcher::fidelity_fn).
+    ///
+    /// This provides [`Refine::refine`] without having to `use` the trait.
+    pub fn refine(&mut self, min_width: V) {
+        self.fidelity = min_width;
+        self.fidelity_fn = None;
+        self.auto_fidelity = false;
+        if self.done && self.max_in.end - self.max_in.start > min_width {
+            self.done = false;
+        }
+    }
+
+    /// Forget everything learned about loads above `load`, for example because the system under
+    /// test changed mid-search.
+    ///
+    /// If the upper bound of the estimate lies above `load`, it is dropped, and the search will
+    /// ramp up again from the lower bound. If the lower bound lies above `load`, it is lowered to
+    /// `load`, so `load` should be a load the system is still believed to support. What was
+    /// learned about loads at or below `load` is kept, and the search resumes if it had ended.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(1500));
+    /// // a deploy just happened, so the failure at 2000 may no longer hold
+    /// load.invalidate_all_above(1000);
+    /// assert_eq!(load.estimate(), 1000..usize::MAX);
+    /// assert_eq!(load.next(), Some(2000));
+    /// ```
+    pub fn invalidate_all_above(&mut self, load: V) {
+        if self.max_in.end > load {
+            self.max_in.end = V::UNBOUNDED;
+        }
+        if self.max_in.start > load {
+            self.max_in.start = load;
+        }
+        self.prev_min = value::min(self.prev_min, self.max_in.start);
+        // the outcome of a pending probe above `load` is no longer meaningful either
+        if self.last.map_or(false, |last| last > load) {
+            self.last = None;
+            self.overloaded = false;
+            self.inconclusive = false;
+            self.severity = 1.0;
+            self.marginal = false;
+            self.achieved = None;
+        }
+        self.capped = false;
+        self.clamped_at = None;
+        self.done = false;
+    }
+
+    /// Make an independent copy of the search, for exploring what it would do under hypothetical
+    /// outcomes.
+    ///
+    /// This is the same as [`Clone::clone`], which is cheap since the search holds no buffers,
+    /// but makes the intent explicit. Telling the branch about an outcome, real or not, leaves the
+    /// original search untouched. What the branch learned from real runs can be brought back with
+    /// [`merge_tighter`](ExponentialCliffSearcher::merge_tighter).
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    ///
+    /// // what if 1000 turns out to be too much?
+    /// let mut what_if = load.branch();
+    /// what_if.overloaded();
+    /// assert_eq!(what_if.next(), Some(750));
+    ///
+    /// // the original search is none the wiser
+    /// assert_eq!(load.next(), Some(2000));
+    /// ```
+    pub fn branch(&self) -> Self {
+        self.clone()
+    }
+
+    /// Iterate over the loads this search would yield next if the pending probe, and every probe
+    /// after it, came out as `overloaded` says.
+    ///
+    /// This is a lookahead, for example to prepare the datasets for the likely next loads while
+    /// the current run is still going. The search itself is left untouched, and any outcome
+    /// already reported for the pending probe is disregarded in favor of `overloaded`. If no probe
+    /// is pending, the first load yielded is the one the search would yield next, and the outcome
+    /// applies to it and those after it. Planning with `false` follows the ramp all the way up to
+    /// the [sanity cap](ExponentialCliffSearcher::sanity_cap), so it is usually best to only take
+    /// the first few loads.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::until(500, 100);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    ///
+    /// let ok: Vec<_> = load.plan_if(false).take(3).collect();
+    /// assert_eq!(ok, [2000, 4000, 8000]);
+    /// let overloaded: Vec<_> = load.plan_if(true).collect();
+    /// assert_eq!(overloaded, [750, 625, 562]);
+    /// assert_eq!(load.next(), Some(2000));
+    /// ```
+    pub fn plan_if(&self, overloaded: bool) -> impl Iterator<Item = V> {
+        let mut search = self.branch();
+        search.overloaded = false;
+        search.inconclusive = false;
+        search.severity = 1.0;
+        search.achieved = None;
+        search.skip = false;
+        let mut pending = search.last.is_some();
+        core::iter::from_fn(move || {
+            if pending && overloaded {
+                search.overloaded();
+            }
+            let next = search.next();
+            pending = next.is_some();
+            next
+        })
+    }
+
+    /// Scale what has been learned so far by `factor`, for example because the system under test
+    /// was scaled out mid-search.
+    ///
+    /// Both bounds of the estimate are multiplied by `factor`, and the search resumes from there.
+    /// Since the new lower bound has not been tried, the next probe confirms it, just like the
+    /// first probe of a new search does.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(1500));
+    /// // capacity just doubled
+    /// load.shift_bounds(2.0);
+    /// assert_eq!(load.estimate(), 2000..4000);
+    /// assert_eq!(load.next(), Some(2000));
+    /// assert_eq!(load.next(), Some(3000));
+    /// ```
+    pub fn shift_bounds(&mut self, factor: f64) {
+        let scale = |v: V| {
+            if v == V::UNBOUNDED {
+                v
+            } else {
+                // conversions saturate, but a known bound must not turn into the "no bound" marker
+                let scaled = V::from_f64(v.to_f64() * factor);
+                if scaled == V::UNBOUNDED {
+                    V::UNBOUNDED - V::ONE
+                } else {
+                    scaled
+                }
+            }
+        };
+        self.max_in = scale(self.max_in.start)..scale(self.max_in.end);
+        self.prev_min = value::min(scale(self.prev_min), self.max_in.start);
+        // any pending probe was for the old system, so start over from the new lower bound
+        self.last = None;
+        self.overloaded = false;
+        self.inconclusive = false;
+        self.severity = 1.0;
+        self.marginal = false;
+        self.achieved = None;
+        self.started = false;
+        self.capped = false;
+        self.clamped_at = None;
+        self.done = false;
+    }
+
+    /// Seed the search with the results of earlier benchmark runs, like those of a previous
+    /// release or from another machine.
+    ///
+    /// Each entry in `history` is a load and whether the system was overloaded at that load. The
+    /// results that agree with a single cliff location are used to tighten the range the search
+    /// starts out with, so that fewer runs are needed to confirm the cliff. Results that
+    /// contradict the rest are ignored, and counted in the returned [`WarmStart`]. Results outside
+    /// the range the search would cover anyway do not change it.
+    ///
+    /// This should be called before the search starts.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// let seeded = load.warm_start(&[(1000, false), (3000, true), (3500, false)]);
+    /// assert_eq!(seeded.inconsistent, 1);
+    /// assert_eq!(load.estimate(), 1000..3000);
+    /// // the search picks up where the history left off
+    /// assert_eq!(load.next(), Some(2000));
+    /// ```
+    pub fn warm_start(&mut self, history: &[(V, bool)]) -> WarmStart {
+        let cliff = best_cliff(history);
+
+        let mut seeded = WarmStart::default();
+        let mut supported = None;
+        let mut unsupported = None;
+        for &(load, overloaded) in history {
+            if overloaded != (load >= cliff) {
+                seeded.inconsistent += 1;
+                continue;
+            }
+            seeded.consistent += 1;
+            if overloaded {
+                unsupported = Some(unsupported.map_or(load, |u: V| value::min(u, load)));
+            } else {
+                supported = Some(supported.map_or(load, |s: V| value::max(s, load)));
+            }
+        }
+
+        if let Some(end) = unsupported {
+            if end > self.max_in.start && end < self.max_in.end {
+                self.max_in.end = end;
+            }
+        }
+        if let Some(start) = supported {
+            if start >= self.max_in.start && start < self.max_in.end {
+                // no need to confirm the starting point again
+                self.prev_min = self.max_in.start;
+                self.max_in.start = start;
+                self.started = true;
+            }
+        }
+        seeded
+    }
+
+    /// Decide the width to which the maximum load should be determined based on where it lies.
+    ///
+    /// `f` is called with the current estimate whenever the search decides whether to continue,
+    /// and the search continues for as long as the estimate is wider than what `f` returns. This
+    /// makes it possible to, say, demand 1% precision for a cliff that lies below 1M, but accept 5%
+    /// for one that lies above 50M. The estimate's `end` is `usize::MAX` until the first overload.
+    ///
+    /// This replaces the `min_width` given to [`until`](ExponentialCliffSearcher::until).
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(1000);
+    /// load.fidelity_fn(|bracket| bracket.start / 4);
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// assert_eq!(load.next(), Some(4000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(3000));
+    /// assert_eq!(load.next(), Some(3500));
+    /// load.overloaded();
+    /// // 3000..3500 is narrower than 3000 / 4
+    /// assert_eq!(load.next(), None);
+    /// ```
+    pub fn fidelity_fn(&mut self, f: fn(core::ops::Range<V>) -> V) {
+        self.fidelity_fn = Some(f);
+    }
+
+    fn min_width(&self) -> V {
+        let min_width = match self.fidelity_fn {
+            Some(f) => f(self.max_in.clone()),
+            None => self.fidelity,
+        };
+        // an estimate can't be narrower than a single value, and asking for that would have the
+        // search probe one of its bounds over and over
+        value::max(min_width, V::ONE)
+    }
+
+    /// Do not probe the starting load, since it is already known to be supported.
+    ///
+    /// Normally, the first probe is at the starting load, to confirm that the system supports it.
+    /// If that is known from elsewhere, like from earlier runs or from the bounds given to
+    /// [`warm_start`](ExponentialCliffSearcher::warm_start), this makes the first probe the first
+    /// one that actually tells us something new.
+    ///
+    /// This must be called before the search starts.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// load.skip_known_bounds();
+    /// assert_eq!(load.next(), Some(1000));
+    /// ```
+    pub fn skip_known_bounds(&mut self) {
+        self.started = true;
+    }
+
+    /// Give the reason the most recent load was yielded.
+    ///
+    /// This is useful for telling apart the samples that came from the initial ramp from those
+    /// that came from the binary search or from [filling](ExponentialCliffSearcher::fill_left),
+    /// for example when plotting. Returns `None` if no load has been yielded yet, or if the search
+    /// has ended. See also [`probes`](ExponentialCliffSearcher::probes).
+    ///
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, ProbeKind};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.kind(), Some(ProbeKind::Warmup));
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.kind(), Some(ProbeKind::Ramp));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(750));
+    /// assert_eq!(load.kind(), Some(ProbeKind::Bisect));
+    /// ```
+    pub fn kind(&self) -> Option<ProbeKind> {
+        self.kind
+    }
+
+    /// Iterate over the loads to probe along with why each one is probed.
+    ///
+    /// The returned iterator yields the same loads as this search does, but each is paired with
+    /// its [`ProbeKind`]. The outcome of each run is reported through the iterator itself.
+    ///
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, ProbeKind};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// let mut probes = load.probes();
+    /// let mut ramp = 0;
+    /// while let Some((l, kind)) = probes.next() {
+    ///     if kind == ProbeKind::Ramp {
+    ///         ramp += 1;
+    ///     }
+    ///     if l > 1600 {
+    ///         probes.overloaded();
+    ///     }
+    /// }
+    /// assert_eq!(ramp, 2);
+    /// assert_eq!(load.estimate(), 1500..1750);
+    /// ```
+    pub fn probes(&mut self) -> Probes<'_, V> {
+        Probes { search: self }
+    }
+
+    /// Give the highest load that was known to be supported before the current lower bound of the
+    /// estimate was established.
+    ///
+    /// During the ramp, this is the load probed just before the most recent successful one. Once
+    /// the search has ended, [filling](ExponentialCliffSearcher::fill_left) samples the range
+    /// between this load and the lower bound of the estimate.
+    pub fn prev_min(&self) -> V {
+        self.prev_min
+    }
+
+    /// Ensure that samples are taken just before the cliff.
+    ///
+    /// If the system under test supports, say, eight million operations per second, and searches
+    /// starting at 1M. The searcher will first sample 1M, 2M, 4M, 8M, 16M. When it detects that
+    /// 16M is overloaded, it'll perform a binary search from the "right", running 12M, 10M, and 9M
+    /// before ultimately deciding that 8M is the lower bound for the cliff.
+    ///
+    /// This evaluation is correct, but may yield strange-looking results when plotting as the
+    /// system is likely highly loaded at 8M. In particular, a straight line drawn from the 4M
+    /// sample to the 8M sample may yield a jarring visual image, and will make it hard to see what
+    /// happens in the system leading up to its capacity.
+    ///
+    /// This method makes the searcher "fill in" extra samples to the left of the lower bound when
+    /// necessary. In the case above, after finding that 8M is the lower bound, this filling would
+    /// also sample 6M and 7M.
+    ///
+    /// Filling also respects the minimum search range width if specified with
+    /// [`until`](ExponentialCliffSearcher::until).
+    pub fn fill_left(&mut self) {
+        self.fill_left = true;
+    }
+
+    /// End the search after at most `probes` more benchmark runs, and make the most of the runs
+    /// that remain.
+    ///
+    /// Rather than being cut off at an arbitrary point, the search plans for the deadline: it no
+    /// longer [fills](ExponentialCliffSearcher::fill_left) samples below the cliff, since those do
+    /// not narrow the estimate, and it bisects the estimate evenly even after an
+    /// [`overloaded_by`](ExponentialCliffSearcher::overloaded_by), since that narrows the estimate
+    /// the most in the worst case. Re-runs of [inconclusive](ExponentialCliffSearcher::inconclusive)
+    /// probes count towards `probes`. If the deadline is reached, the search ends with whatever
+    /// estimate it has, even if that is wider than the requested width.
+    ///
+    /// Calling this again replaces the previous deadline, and resumes the search if it ended
+    /// because of it.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    ///
+    /// let mut load = ExponentialCliffSearcher::until(500, 10);
+    /// load.fill_left();
+    /// assert_eq!(load.next(), Some(500));
+    /// load.finalize_by(3);
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// load.overloaded_by(8.0);
+    /// assert_eq!(load.next(), Some(1500));
+    /// assert_eq!(load.next(), None);
+    /// assert_eq!(load.estimate(), 1500..2000);
+    /// ```
+    pub fn finalize_by(&mut self, probes: usize) {
+        self.deadline = Some(probes);
+        self.fill_left = false;
+        if self.done && self.max_in.end - self.max_in.start > self.min_width() {
+            self.done = false;
+        }
+    }
+
+    /// End the search before `deadline`, and make the most of the time that remains.
+    ///
+    /// This works like [`finalize_by`](ExponentialCliffSearcher::finalize_by), except that the
+    /// number of runs that fit before the deadline is worked out from how long the runs since
+    /// this call have taken on average. A new run is not started if it would not finish in time.
+    ///
+    /// The deadline is not captured when the search is [suspended](Suspend), since an [`Instant`]
+    /// is meaningless in another process.
+    ///
+    /// This method is only available with the `std` feature.
+    ///
+    /// ```rust
+    /// use cliff::ExponentialCliffSearcher;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// load.finalize_by_time(Instant::now() + Duration::from_secs(3600));
+    /// assert_eq!(load.next(), Some(500));
+    /// load.finalize_by_time(Instant::now());
+    /// assert_eq!(load.next(), None);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn finalize_by_time(&mut self, deadline: Instant) {
+        self.time_limit = Some(TimeLimit {
+            deadline,
+            since: Instant::now(),
+            runs: 0,
+        });
+        self.fill_left = false;
+        if self.done && self.max_in.end - self.max_in.start > self.min_width() {
+            self.done = false;
+        }
+    }
+
+    fn finalizing(&self) -> bool {
+        #[cfg(feature = "std")]
+        {
+            if self.time_limit.is_some() {
+                return true;
+            }
+        }
+        self.deadline.is_some()
+    }
+
+    /// Take one run out of what is left before the deadline, if any, or return `false` if there
+    /// is no time for another run.
+    fn spend(&mut self) -> bool {
+        if self.deadline == Some(0) {
+            return false;
+        }
+        #[cfg(feature = "std")]
+        {
+            if let Some(limit) = &mut self.time_limit {
+                let now = Instant::now();
+                if now >= limit.deadline {
+                    return false;
+                }
+                if limit.runs > 0 {
+                    let per_run = (now - limit.since) / limit.runs;
+                    if now + per_run > limit.deadline {
+                        return false;
+                    }
+                }
+                limit.runs += 1;
+            }
+        }
+        if let Some(left) = &mut self.deadline {
+            *left -= 1;
+        }
+        true
+    }
+
+    fn out_of_time(&mut self) -> Option<V> {
+        self.done = true;
+        self.last = None;
+        self.kind = None;
+        None
+    }
+
+    // NOTE: the try_ variants below report the cases where the plain methods have to guess at
+    // what was meant, or give up, as an Error instead.
+
+    fn awaiting_outcome(&self) -> Result<(), Error> {
+        if self.kind.is_none() {
+            return Err(Error::Misuse("no load is awaiting an outcome"));
+        }
+        Ok(())
+    }
+
+    /// Like [`overloaded`](ExponentialCliffSearcher::overloaded), but returns an error if no load
+    /// has been yielded for the outcome to apply to.
+    pub fn try_overloaded(&mut self) -> Result<(), Error> {
+        self.awaiting_outcome()?;
+        self.overloaded();
+        Ok(())
+    }
+
+    /// Like [`overloaded_by`](ExponentialCliffSearcher::overloaded_by), but returns an error if no
+    /// load has been yielded for the outcome to apply to, or if `factor` is not a number.
+    pub fn try_overloaded_by(&mut self, factor: f64) -> Result<(), Error> {
+        self.awaiting_outcome()?;
+        if factor.is_nan() {
+            return Err(Error::InvalidConfig("the overload factor must be a number"));
+        }
+        self.overloaded_by(factor);
+        Ok(())
+    }
+
+    /// Like [`inconclusive`](ExponentialCliffSearcher::inconclusive), but returns an error if no
+    /// load has been yielded for the outcome to apply to.
+    pub fn try_inconclusive(&mut self) -> Result<(), Error> {
+        self.awaiting_outcome()?;
+        self.inconclusive();
+        Ok(())
+    }
+
+    /// Like [`achieved`](ExponentialCliffSearcher::achieved), but returns an error if no load has
+    /// been yielded for the outcome to apply to.
+    pub fn try_achieved(&mut self, actual: V) -> Result<(), Error> {
+        self.awaiting_outcome()?;
+        self.achieved(actual);
+        Ok(())
+    }
+
+    /// Like [`shift_bounds`](ExponentialCliffSearcher::shift_bounds), but returns an error rather
+    /// than saturating if `factor` is not a positive number, or if the scaled bounds do not fit in
+    /// a `usize`.
+    ///
+    /// The search is left unchanged if an error is returned.
+    pub fn try_shift_bounds(&mut self, factor: f64) -> Result<(), Error> {
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(Error::InvalidConfig(
+                "the scaling factor must be a positive number",
+            ));
+        }
+        let fits = |v: V| v == V::UNBOUNDED || v.to_f64() * factor < V::UNBOUNDED.to_f64();
+        if !fits(self.max_in.start) || !fits(self.max_in.end) {
+            return Err(Error::Overflow);
+        }
+        self.shift_bounds(factor);
+        Ok(())
+    }
+
+    /// Like [`Iterator::next`], but returns an error rather than ending the search if the next
+    /// load would not fit in a `usize`, or if the first bracket around the cliff is too narrow for
+    /// the requested width and [`CoarseFidelity::Error`] was
+    /// [picked](ExponentialCliffSearcher::on_coarse_fidelity) for that.
+    ///
+    /// ```rust
+    /// use cliff::{Error, ExponentialCliffSearcher};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(usize::MAX / 2 + 1);
+    /// assert_eq!(load.try_next(), Ok(Some(usize::MAX / 2 + 1)));
+    /// assert_eq!(load.try_next(), Err(Error::Overflow));
+    /// ```
+    pub fn try_next(&mut self) -> Result<Option<V>, Error> {
+        let coarse = self.coarse.is_some();
+        let next = self.next();
+        if core::mem::replace(&mut self.overflowed, false) {
+            return Err(Error::Overflow);
+        }
+        if !coarse && self.coarse.is_some() && self.on_coarse == CoarseFidelity::Error {
+            return Err(Error::InvalidConfig(
+                "the requested width is no narrower than the first bracket around the cliff",
+            ));
+        }
+        Ok(next)
+    }
+}
+
+impl<V> CliffSearch<V> for ExponentialCliffSearcher<V>
+where
+    V: SearchValue,
+{
+    fn overloaded(&mut self) {
+        ExponentialCliffSearcher::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        ExponentialCliffSearcher::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        ExponentialCliffSearcher::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        ExponentialCliffSearcher::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: V) {
+        ExponentialCliffSearcher::achieved(self, actual)
+    }
+
+    fn estimate(&self) -> core::ops::Range<V> {
+        ExponentialCliffSearcher::estimate(self)
+    }
+
+    fn has_upper_bound(&self) -> bool {
+        ExponentialCliffSearcher::has_upper_bound(self)
+    }
+
+    fn skip_last(&mut self) {
+        ExponentialCliffSearcher::skip_last(self)
+    }
+
+    fn status(&self) -> Status {
+        ExponentialCliffSearcher::status(self)
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        ExponentialCliffSearcher::probes_left(self)
+    }
+}
+
+impl<V> ExponentialCliffSearcher<V>
+where
+    V: SearchValue,
+{
+    fn phase(&self) -> &'static str {
+        if self.done {
+            match self.kind {
+                Some(ProbeKind::Fill) => "fill",
+                _ => "done",
+            }
+        } else if !self.started {
+            "warmup"
+        } else if self.max_in.end == V::UNBOUNDED {
+            "ramp"
+        } else {
+            "bisect"
+        }
+    }
+}
+
+impl<V> core::fmt::Display for ExponentialCliffSearcher<V>
+where
+    V: SearchValue,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Exp{{{}..", self.max_in.start)?;
+        if self.max_in.end == V::UNBOUNDED {
+            write!(f, "∞")?;
+        } else {
+            write!(f, "{}", self.max_in.end)?;
+        }
+        if self.auto_fidelity && self.fidelity_fn.is_none() {
+            write!(f, ", fid=auto")?;
+        } else {
+            write!(f, ", fid={}", self.min_width())?;
+        }
+        write!(f, ", phase={}}}", self.phase())
+    }
+}
+
+impl<V> core::fmt::Debug for ExponentialCliffSearcher<V>
+where
+    V: SearchValue,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if !f.alternate() {
+            return core::fmt::Display::fmt(self, f);
+        }
+
+        let mut d = f.debug_struct("ExponentialCliffSearcher");
+        d.field("max_in", &self.max_in)
+            .field("prev_min", &self.prev_min)
+            .field("last", &self.last)
+            .field("started", &self.started)
+            .field("fidelity", &self.fidelity)
+            .field("fidelity_fn", &self.fidelity_fn)
+            .field("auto_fidelity", &self.auto_fidelity)
+            .field("overloaded", &self.overloaded)
+            .field("inconclusive", &self.inconclusive)
+            .field("severity", &self.severity)
+            .field("achieved", &self.achieved)
+            .field("done", &self.done)
+            .field("fill_left", &self.fill_left)
+            .field("kind", &self.kind)
+            .field("deadline", &self.deadline);
+        #[cfg(feature = "std")]
+        d.field("time_limit", &self.time_limit);
+        d.field("overflowed", &self.overflowed)
+            .field("cap", &self.cap)
+            .field("capped", &self.capped)
+            .field("clamp_tolerance", &self.clamp_tolerance)
+            .field("clamped_at", &self.clamped_at)
+            .field("hint", &self.hint)
+            .field("on_coarse", &self.on_coarse)
+            .field("bracketed", &self.bracketed)
+            .field("coarse", &self.coarse)
+            .field("growth", &self.growth)
+            .field("max", &self.max)
+            .field("marginal", &self.marginal)
+            .finish()
+    }
+}
+
+impl Refine for ExponentialCliffSearcher {
+    fn refine(&mut self, min_width: usize) {
+        ExponentialCliffSearcher::refine(self, min_width)
+    }
+}
+
+/// The state of a suspended [`ExponentialCliffSearcher`].
+///
+/// Any [`fidelity_fn`](ExponentialCliffSearcher::fidelity_fn) is not captured, since it cannot be
+/// carried across processes, and must be set again after resuming. The same goes for a deadline
+/// given to `finalize_by_time`.
+///
+/// See [`Suspend`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuspendedExponential {
+    /// The lower bound of the estimate.
+    pub start: usize,
+    /// The upper bound of the estimate, or `usize::MAX` if there is none yet.
+    pub end: usize,
+    /// The previous lower bound of the estimate.
+    pub prev_min: usize,
+    /// The probe whose outcome has yet to be taken into account.
+    pub pending: Option<usize>,
+    /// Whether the first probe has been yielded.
+    pub started: bool,
+    /// The width to which the maximum load should be determined.
+    pub min_width: usize,
+    /// Whether `min_width` is yet to be picked from the first upper bound, as for
+    /// [`Fidelity::Auto`].
+    pub auto_fidelity: bool,
+    /// Whether the pending probe was reported as overloaded.
+    pub overloaded: bool,
+    /// Whether the pending probe was reported as inconclusive.
+    pub inconclusive: bool,
+    /// How badly the pending probe was overloaded.
+    pub severity: f64,
+    /// The load actually achieved by the pending probe, if reported.
+    pub achieved: Option<usize>,
+    /// Whether the search has ended.
+    pub done: bool,
+    /// Whether the search should fill in samples below the cliff.
+    pub fill_left: bool,
+    /// The kind of the most recently yielded probe.
+    pub kind: Option<ProbeKind>,
+    /// The number of runs left before the search ends, if
+    /// [finalizing](ExponentialCliffSearcher::finalize_by).
+    pub deadline: Option<usize>,
+    /// The load above which the search gives up on ever overloading the system.
+    pub cap: usize,
+    /// Whether the search ended because it hit its cap.
+    pub capped: bool,
+    /// Whether the pending probe was skipped.
+    pub skipped: bool,
+    /// How far below the requested load an achieved load must fall to count as clamped, if
+    /// [detecting clamping](ExponentialCliffSearcher::detect_clamping).
+    pub clamp_tolerance: Option<f64>,
+    /// The load at which the system was found to clamp its intake, if any.
+    pub clamped_at: Option<usize>,
+    /// The upper bound given to [`hint_upper`](ExponentialCliffSearcher::hint_upper), if it has
+    /// yet to be confirmed.
+    pub hint: Option<usize>,
+    /// What to do if the first bracket around the cliff is too narrow for `min_width`.
+    pub on_coarse_fidelity: CoarseFidelity,
+    /// Whether the search has found its first bracket around the cliff.
+    pub bracketed: bool,
+    /// The requested width and the width of the first bracket around the cliff, if the bracket
+    /// was no wider.
+    pub coarse_fidelity: Option<(usize, usize)>,
+    /// The factor by which the load is multiplied while ramping up.
+    pub growth_factor: f64,
+    /// The most the search may run, as set with
+    /// [`with_max`](ExponentialCliffSearcher::with_max).
+    pub max: Option<usize>,
+    /// Whether the pending probe was reported as marginal.
+    pub marginal: bool,
+}
+
+impl SuspendedExponential {
+    /// Continue the suspended search.
+    pub fn resume(self) -> ExponentialCliffSearcher {
+        ExponentialCliffSearcher {
+            max_in: self.start..self.end,
+            prev_min: self.prev_min,
+            last: self.pending,
+            started: self.started,
+            fidelity: self.min_width,
+            fidelity_fn: None,
+            auto_fidelity: self.auto_fidelity,
+            overloaded: self.overloaded,
+            inconclusive: self.inconclusive,
+            severity: self.severity,
+            achieved: self.achieved,
+            done: self.done,
+            fill_left: self.fill_left,
+            kind: self.kind,
+            deadline: self.deadline,
+            #[cfg(feature = "std")]
+            time_limit: None,
+            overflowed: false,
+            cap: self.cap,
+            capped: self.capped,
+            skip: self.skipped,
+            skips: 0,
+            skip_origin: None,
+            clamp_tolerance: self.clamp_tolerance,
+            clamped_at: self.clamped_at,
+            hint: self.hint,
+            on_coarse: self.on_coarse_fidelity,
+            bracketed: self.bracketed,
+            coarse: self.coarse_fidelity,
+            growth: self.growth_factor,
+            max: self.max,
+            marginal: self.marginal,
+        }
+    }
+}
+
+impl Suspend for ExponentialCliffSearcher {
+    type Suspended = SuspendedExponential;
+
+    fn suspend(self) -> Self::Suspended {
+        SuspendedExponential {
+            start: self.max_in.start,
+            end: self.max_in.end,
+            prev_min: self.prev_min,
+            pending: self.last,
+            started: self.started,
+            min_width: self.fidelity,
+            auto_fidelity: self.auto_fidelity,
+            overloaded: self.overloaded,
+            inconclusive: self.inconclusive,
+            severity: self.severity,
+            achieved: self.achieved,
+            done: self.done,
+            fill_left: self.fill_left,
+            kind: self.kind,
+            deadline: self.deadline,
+            cap: self.cap,
+            capped: self.capped,
+            skipped: self.skip,
+            clamp_tolerance: self.clamp_tolerance,
+            clamped_at: self.clamped_at,
+            hint: self.hint,
+            on_coarse_fidelity: self.on_coarse,
+            bracketed: self.bracketed,
+            coarse_fidelity: self.coarse,
+            growth_factor: self.growth,
+            max: self.max,
+            marginal: self.marginal,
+        }
+    }
+
+    fn resume(suspended: Self::Suspended) -> Self {
+        suspended.resume()
+    }
+}
+
+impl<V> Iterator for ExponentialCliffSearcher<V>
+where
+    V: SearchValue,
+{
+    type Item = V;
+    fn next(&mut self) -> Option<Self::Item> {
+        // a skipped fill sample needs no replacement
+        let skip = core::mem::replace(&mut self.skip, false);
+        if self.done {
+            if self.fill_left {
+                // we've found the range in which the cliff lies: self.max_in
+                // but the user has requested that we also "fill the curve" up to the min
+                // by sampling some data points leading up to the cliff as well
+                let diff = self.max_in.start - self.prev_min;
+                if diff > self.min_width() {
+                    // now just binary search between prev_min and max_in.start
+                    let next = self.prev_min + diff.scaled(1, 2);
+                    self.prev_min = next;
+                    self.kind = Some(ProbeKind::Fill);
+                    return Some(next);
+                } else {
+                    self.fill_left = false;
+                }
+            }
+            self.kind = None;
+            return None;
+        }
+
+        if skip {
+            if let Some(skipped) = self.last.take() {
+                let origin = *self.skip_origin.get_or_insert(skipped);
+                if let Some(alternative) = self.next_alternative(origin) {
+                    self.last = Some(alternative);
+                    return self.last;
+                }
+                // nothing near the probe can be run, so there's nothing more to learn
+                self.done = true;
+                return self.next();
+            }
+        }
+        self.skips = 0;
+        self.skip_origin = None;
+
+        if self.inconclusive && self.last.is_some() {
+            // the last run told us nothing, so run it again
+            self.inconclusive = false;
+            self.overloaded = false;
+            self.severity = 1.0;
+            self.marginal = false;
+            self.achieved = None;
+            if !self.spend() {
+                return self.out_of_time();
+            }
+            self.kind = Some(ProbeKind::Verify);
+            return self.last;
+        }
+        self.inconclusive = false;
+
+        let achieved = self.achieved.take();
+        let mut near = false;
+        if let Some(last) = self.last.take() {
+            let achieved = achieved.filter(|&a| a < last);
+            let clamped = match (achieved, self.clamp_tolerance) {
+                (Some(a), Some(tolerance)) if !self.overloaded => {
+                    a.to_f64() < last.to_f64() * (1.0 - tolerance)
+                }
+                _ => false,
+            };
+            if let (true, Some(achieved)) = (clamped, achieved) {
+                // the system kept up with what it let in, but won't let in any more than that, so
+                // higher loads can't tell us anything
+                if achieved > self.max_in.start {
+                    self.prev_min = self.max_in.start;
+                    self.max_in.start = achieved;
+                }
+                self.clamped_at = Some(achieved);
+                self.capped = self.max_in.end == V::UNBOUNDED;
+                self.done = true;
+                return self.next();
+            } else if let Some(achieved) = achieved.filter(|&a| a > self.max_in.start) {
+                // we couldn't even generate the load we tried, so the cliff is below what we got
+                self.max_in.end = achieved;
+            } else if self.overloaded || achieved.is_some() {
+                // the last thing we tried failed, so it sets an upper limit for max load
+                self.max_in.end = last;
+            } else {
+                // the last thing succeeded, so that increases the lower limit
+                near = self.marginal;
+                self.prev_min = self.max_in.start;
+                self.max_in.start = last;
+                if self.max_in.end == last {
+                    // only a hinted upper bound can be sustained, and so it was wrong
+                    self.max_in.end = V::UNBOUNDED;
+                }
+            }
+        } else if !self.started {
+            if !self.spend() {
+                return self.out_of_time();
+            }
+            self.started = true;
+            self.last = Some(self.max_in.start);
+            self.kind = Some(ProbeKind::Warmup);
+            return self.last;
+        }
+        if let Some(max) = self.max.filter(|&max| self.max_in.start >= max) {
+            // the system kept up with the most the search may run, so there's nothing more to
+            // learn, and all we know is that the cliff lies somewhere above it
+            let limit = max.offset(V::ONE).unwrap_or(V::UNBOUNDED);
+            self.capped = self.max_in.end == V::UNBOUNDED || self.max_in.end > limit;
+            self.done = true;
+            return self.next();
+        }
+        if self.auto_fidelity && self.max_in.end != V::UNBOUNDED {
+            // the first upper bound tells us the scale of the cliff
+            self.fidelity = value::max(self.max_in.end.scaled(1, 20), V::ONE);
+            self.auto_fidelity = false;
+        }
+        if !self.bracketed && self.max_in.end != V::UNBOUNDED {
+            self.bracketed = true;
+            let min_width = self.min_width();
+            let width = self.max_in.end - self.max_in.start;
+            if width <= min_width {
+                self.coarse = Some((min_width, width));
+                if self.on_coarse == CoarseFidelity::Shrink {
+                    self.fidelity = value::max(self.max_in.end.scaled(1, 20), V::ONE);
+                    self.fidelity_fn = None;
+                }
+            }
+        }
+        // if no probe was outstanding (like after a refine), there is nothing to be overloaded
+        self.overloaded = false;
+        self.marginal = false;
+        let mut severity = core::mem::replace(&mut self.severity, 1.0);
+        if self.finalizing() {
+            // with few runs left, an even split narrows the estimate the most in the worst case
+            severity = 1.0;
+        }
+
+        self.kind = Some(ProbeKind::Bisect);
+        let next = if near && !self.finalizing() {
+            // the system only just kept up, so the cliff is probably right above the lower limit
+            let above = self.max_in.start.offset(self.min_width()).unwrap_or(V::UNBOUNDED);
+            if self.max_in.end == V::UNBOUNDED {
+                self.kind = Some(ProbeKind::Ramp);
+                let next = self.grow(self.max_in.start).map_or(above, |next| value::min(next, above));
+                if next > self.cap {
+                    self.capped = true;
+                    self.done = true;
+                    return self.next();
+                }
+                next
+            } else {
+                value::min(above, self.max_in.start.bisect(self.max_in.end))
+            }
+        } else if self.max_in.end == V::UNBOUNDED {
+            // no upper limit, so exponential search
+            self.kind = Some(ProbeKind::Ramp);
+            match self.grow(self.max_in.start) {
+                Some(next) if next <= self.cap => next,
+                next => {
+                    // there's nowhere left to ramp to, so the benchmark is probably broken
+                    self.overflowed = next.is_none();
+                    self.capped = true;
+                    self.done = true;
+                    return self.next();
+                }
+            }
+        } else if severity > 2.0 {
+            // we overshot by a lot, so the cliff is probably much closer to the lower limit
+            let width = self.max_in.end - self.max_in.start;
+            self.max_in.start + value::max(V::from_f64(width.to_f64() / severity), V::ONE)
+        } else {
+            // bisect the range
+            self.max_in.start.bisect(self.max_in.end)
+        };
+
+        // we only care about the max down to `fidelity`, and floating-point bounds that are too
+        // close together to split cannot be narrowed down any further
+        let splits = self.max_in.end == V::UNBOUNDED
+            || (next > self.max_in.start && next < self.max_in.end);
+        if self.max_in.end - self.max_in.start > self.min_width() && splits {
+            if !self.spend() {
+                return self.out_of_time();
+            }
+            self.last = Some(self.clamp_to_max(next));
+            self.last
+        } else if self.hint.take() == Some(self.max_in.end) {
+            // we've narrowed down on a hinted upper bound, but never saw it overload the system
+            if !self.spend() {
+                return self.out_of_time();
+            }
+            self.last = Some(self.clamp_to_max(self.max_in.end));
+            self.last
+        } else {
+            self.done = true;
+            // normally just None, but may be Some with filling
+            self.next()
+        }
+    }
+}
+
+/// An iterator over the loads an [`ExponentialCliffSearcher`] probes, along with why each is
+/// probed.
+///
+/// See [`ExponentialCliffSearcher::probes`].
+#[derive(Debug)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Probes<'a, V: SearchValue = usize> {
+    search: &'a mut ExponentialCliffSearcher<V>,
+}
+
+impl<V> Probes<'_, V>
+where
+    V: SearchValue,
+{
+    /// Indicate that the system could not keep up with the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// See [`ExponentialCliffSearcher::overloaded`].
+    pub fn overloaded(&mut self) {
+        self.search.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// See [`ExponentialCliffSearcher::overloaded_by`].
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.search.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// See [`ExponentialCliffSearcher::inconclusive`].
+    pub fn inconclusive(&mut self) {
+        self.search.inconclusive();
+    }
+
+    /// Indicate that the previous load yielded by [`Iterator::next`] could not be run at all.
+    ///
+    /// See [`ExponentialCliffSearcher::skip_last`].
+    pub fn skip_last(&mut self) {
+        self.search.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// See [`ExponentialCliffSearcher::achieved`].
+    pub fn achieved(&mut self, actual: V) {
+        self.search.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    pub fn estimate(&self) -> core::ops::Range<V> {
+        self.search.estimate()
+    }
+}
+
+impl<V> Iterator for Probes<'_, V>
+where
+    V: SearchValue,
+{
+    type Item = (V, ProbeKind);
+    fn next(&mut self) -> Option<Self::Item> {
+        let load = self.search.next()?;
+        // every yielded load has a kind
+        Some((load, self.search.kind?))
+    }
+}
+
+// the cliff location that the most results in `history` agree with,
+// where loads below the cliff are supported, and loads at or above it are not.
+fn best_cliff<V: SearchValue>(history: &[(V, bool)]) -> V {
+    let errors = |cliff: V| {
+        history
+            .iter()
+            .filter(|&&(load, overloaded)| overloaded != (load >= cliff))
+            .count()
+    };
+
+    // the cliff may as well be at one of the overloaded loads, or there may be none.
+    // on a tie, pick the lowest cliff, so we err on the side of caution.
+    let mut best = (errors(V::UNBOUNDED), V::UNBOUNDED);
+    for &(load, overloaded) in history {
+        if overloaded {
+            let candidate = (errors(load), load);
+            if candidate < best {
+                best = candidate;
+            }
+        }
+    }
+    best.1
+}
+
+impl<V> Describe for ExponentialCliffSearcher<V>
+where
+    V: SearchValue,
+{
+    fn name(&self) -> &'static str {
+        "exponential"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("min_width", &self.fidelity);
+        setting("cap", &self.cap);
+        if self.growth != 2.0 {
+            setting("growth_factor", &self.growth);
+        }
+        if let Some(max) = self.max {
+            setting("max", &max);
+        }
+        if let Some(tolerance) = self.clamp_tolerance {
+            setting("clamp_tolerance", &tolerance);
+        }
+    }
+}
+
+#[test]
+fn search_from() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(3000));
+    assert_eq!(scale.next(), Some(3500));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(3250));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 3250..3500);
+
+    // check that it continues to be terminated
+    assert_eq!(scale.next(), None);
+    // even after another "failed"
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    // and the estimate is still the same
+    assert_eq!(scale.estimate(), 3250..3500);
+}
+
+#[test]
+fn search_from_until() {
+    let mut scale = ExponentialCliffSearcher::until(500, 1000);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    assert_eq!(scale.next(), Some(8000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(6000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(5000));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 4000..5000);
+
+    // check that it continues to be terminated
+    assert_eq!(scale.next(), None);
+    // even after another "failed"
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    // and the estimate is still the same
+    assert_eq!(scale.estimate(), 4000..5000);
+}
+
+#[test]
+fn fill_search() {
+    let mut scale = ExponentialCliffSearcher::until(500, 500);
+    scale.fill_left();
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    assert_eq!(scale.next(), Some(8000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(6000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(5000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(4500));
+    scale.overloaded();
+
+    // since filling is enabled, we'll also sample a few
+    // points just _before_ the highest known-good target.
+    assert_eq!(scale.next(), Some(3000));
+    assert_eq!(scale.next(), Some(3500));
+
+    // and then we should be done
+    assert_eq!(scale.next(), None);
+    // check that it continues to be terminated
+    assert_eq!(scale.next(), None);
+}
+
+#[test]
+fn through_trait() {
+    let mut scale = ExponentialCliffSearcher::until(500, 1000);
+    let scale: &mut dyn CliffSearch = &mut scale;
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    assert_eq!(scale.next(), Some(8000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(6000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(5000));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 4000..5000);
+}
+
+#[test]
+fn immediate() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 500..500);
+}
+
+#[test]
+fn nonzero() {
+    let mut scale = ExponentialCliffSearcher::new_nonzero(NonZeroUsize::new(2).unwrap());
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(4));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(3));
+    assert_eq!(scale.next(), None);
+    assert_eq!(
+        scale.estimate_nonzero(),
+        Some(NonZeroUsize::new(3).unwrap()..NonZeroUsize::new(4).unwrap())
+    );
+
+    let scale = ExponentialCliffSearcher::within(0..4, 1);
+    assert_eq!(scale.estimate_nonzero(), None);
+}
+
+#[test]
+fn severity() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    assert_eq!(scale.next(), Some(8000));
+    scale.overloaded_by(8.0);
+    assert_eq!(scale.next(), Some(4500));
+    // mild overloads bisect as usual
+    scale.overloaded_by(1.5);
+    assert_eq!(scale.next(), Some(4250));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 4250..4500);
+}
+
+#[test]
+fn achieved() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.achieved(1000);
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    scale.achieved(3200);
+    assert_eq!(scale.estimate(), 2000..usize::MAX);
+    assert_eq!(scale.next(), Some(2600));
+    assert_eq!(scale.estimate(), 2000..3200);
+    // achieving less than what's known to be supported is just an overload
+    scale.achieved(1500);
+    assert_eq!(scale.next(), Some(2300));
+    assert_eq!(scale.estimate(), 2000..2600);
+}
+
+#[test]
+fn warm_start() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    let seeded = scale.warm_start(&[(1000, false), (4000, true), (3000, true), (3500, false)]);
+    assert_eq!(seeded.consistent, 3);
+    assert_eq!(seeded.inconsistent, 1);
+    assert_eq!(scale.estimate(), 1000..3000);
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.next(), Some(1750));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1750..2000);
+
+    // with only a known failure, the start still needs to be confirmed
+    let mut scale = ExponentialCliffSearcher::new(500);
+    let seeded = scale.warm_start(&[(100, false), (4000, true)]);
+    assert_eq!(seeded.inconsistent, 0);
+    assert_eq!(scale.estimate(), 500..4000);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(2250));
+
+    // with only a known success, the ramp continues from there
+    let mut scale = ExponentialCliffSearcher::new(500);
+    let _ = scale.warm_start(&[(1000, false)]);
+    assert_eq!(scale.next(), Some(2000));
+}
+
+#[test]
+fn kinds() {
+    use ProbeKind::*;
+
+    let mut scale = ExponentialCliffSearcher::until(500, 500);
+    scale.fill_left();
+    assert_eq!(scale.kind(), None);
+    let mut probes = scale.probes();
+    assert_eq!(probes.next(), Some((500, Warmup)));
+    assert_eq!(probes.next(), Some((1000, Ramp)));
+    assert_eq!(probes.next(), Some((2000, Ramp)));
+    assert_eq!(probes.next(), Some((4000, Ramp)));
+    probes.overloaded();
+    assert_eq!(probes.next(), Some((3000, Bisect)));
+    probes.inconclusive();
+    assert_eq!(probes.next(), Some((3000, Verify)));
+    probes.overloaded();
+    assert_eq!(probes.next(), Some((2500, Bisect)));
+    probes.overloaded();
+    assert_eq!(probes.next(), Some((1500, Fill)));
+    assert_eq!(probes.next(), None);
+    assert_eq!(scale.kind(), None);
+    assert_eq!(scale.prev_min(), 1500);
+}
+
+#[test]
+fn skip_known_bounds() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    let _ = scale.warm_start(&[(4000, true)]);
+    scale.skip_known_bounds();
+    assert_eq!(scale.next(), Some(2250));
+    assert_eq!(scale.kind(), Some(ProbeKind::Bisect));
+}
+
+#[test]
+fn fidelity_fn() {
+    let mut scale = ExponentialCliffSearcher::new(1000);
+    scale.fidelity_fn(|bracket| bracket.start / 4);
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    assert_eq!(scale.next(), Some(8000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(6000));
+    assert_eq!(scale.next(), Some(7000));
+    scale.overloaded();
+    // 6000..7000 is narrower than 6000 / 4
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 6000..7000);
+
+    // refining replaces the fidelity function
+    scale.refine(250);
+    assert_eq!(scale.next(), Some(6500));
+    assert_eq!(scale.next(), Some(6750));
+    assert_eq!(scale.next(), None);
+}
+
+#[test]
+fn environment_changed() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.next(), Some(1750));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1500..1750);
+
+    // knowledge at or below the invalidated load is kept
+    scale.invalidate_all_above(1600);
+    assert_eq!(scale.estimate(), 1500..usize::MAX);
+    assert_eq!(scale.next(), Some(3000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(2250));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1875));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1687));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1687..1875);
+
+    // the system shrank, and the new lower bound turns out not to hold
+    scale.shift_bounds(0.5);
+    assert_eq!(scale.estimate(), 843..937);
+    assert_eq!(scale.next(), Some(843));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 843..843);
+}
+
+#[test]
+fn suspend() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded();
+    let suspended = scale.suspend();
+    assert_eq!(suspended.pending, Some(2000));
+    assert!(suspended.overloaded);
+
+    let mut scale = suspended.resume();
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.next(), Some(1750));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1500..1750);
+}
+
+#[test]
+fn finalize_by() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    scale.finalize_by(0);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 500..usize::MAX);
+
+    // a new deadline resumes the search
+    scale.finalize_by(2);
+    assert_eq!(scale.next(), Some(500));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), None);
+
+    // and survives suspension
+    scale.finalize_by(3);
+    let mut scale = scale.suspend().resume();
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1500..2000);
+
+    // a deadline that is not reached changes nothing
+    let mut scale = ExponentialCliffSearcher::new(500);
+    scale.finalize_by(100);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.next(), Some(1750));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1500..1750);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn finalize_by_time() {
+    use std::time::{Duration, Instant};
+
+    let mut scale = ExponentialCliffSearcher::new(500);
+    scale.fill_left();
+    scale.finalize_by_time(Instant::now() + Duration::from_secs(3600));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded_by(8.0);
+    // no severity-based step while finalizing
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.next(), Some(1750));
+    scale.overloaded();
+    // and no filling
+    assert_eq!(scale.next(), None);
+
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    scale.finalize_by_time(Instant::now());
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 500..usize::MAX);
 }
 
-impl CliffSearch for ExponentialCliffSearcher {
-    fn overloaded(&mut self) {
-        ExponentialCliffSearcher::overloaded(self)
-    }
+#[test]
+fn try_variants() {
+    assert_eq!(
+        ExponentialCliffSearcher::try_until(0, 0).err(),
+        Some(Error::InvalidConfig("the starting load must be non-zero"))
+    );
 
-    fn estimate(&self) -> core::ops::Range<usize> {
-        ExponentialCliffSearcher::estimate(self)
-    }
+    let mut scale = ExponentialCliffSearcher::try_until(500, 250).unwrap();
+    assert!(matches!(scale.try_overloaded(), Err(Error::Misuse(_))));
+    assert_eq!(scale.try_next(), Ok(Some(500)));
+    assert_eq!(scale.try_inconclusive(), Ok(()));
+    assert_eq!(scale.try_next(), Ok(Some(500)));
+    assert!(matches!(scale.try_warm_start(&[]), Err(Error::Misuse(_))));
+    assert_eq!(scale.try_next(), Ok(Some(1000)));
+    assert!(matches!(
+        scale.try_overloaded_by(f64::NAN),
+        Err(Error::InvalidConfig(_))
+    ));
+    assert_eq!(scale.try_achieved(900), Ok(()));
+    assert_eq!(scale.try_next(), Ok(Some(700)));
+    assert_eq!(scale.estimate(), 500..900);
+    assert!(matches!(
+        scale.try_shift_bounds(-1.0),
+        Err(Error::InvalidConfig(_))
+    ));
+    assert_eq!(
+        scale.try_shift_bounds(usize::MAX as f64),
+        Err(Error::Overflow)
+    );
+    assert_eq!(scale.estimate(), 500..900);
+    assert_eq!(scale.try_shift_bounds(2.0), Ok(()));
+    assert_eq!(scale.estimate(), 1000..1800);
+
+    // a ramp that runs out of room ends the search where it is
+    let top = usize::MAX / 2 + 1;
+    let mut scale = ExponentialCliffSearcher::new(top);
+    assert_eq!(scale.next(), Some(top));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), top..usize::MAX);
 }
 
-impl Iterator for ExponentialCliffSearcher {
-    type Item = usize;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.done {
-            if self.fill_left {
-                // we've found the range in which the cliff lies: self.max_in
-                // but the user has requested that we also "fill the curve" up to the min
-                // by sampling some data points leading up to the cliff as well
-                let diff = self.max_in.start - self.prev_min;
-                if diff > self.fidelity {
-                    // now just binary search between prev_min and max_in.start
-                    let next = self.prev_min + diff / 2;
-                    self.prev_min = next;
-                    return Some(next);
-                } else {
-                    self.fill_left = false;
+#[test]
+fn max_probes() {
+    const RAMP: usize = ExponentialCliffSearcher::max_ramp_probes(500, 1600);
+    const MAX: usize = ExponentialCliffSearcher::max_probes(500, 250, 1600);
+    assert_eq!(RAMP, 3);
+    assert_eq!(MAX, 5);
+    // a start of 0 is taken to be 1, just like by the search itself
+    assert_eq!(ExponentialCliffSearcher::max_ramp_probes(0, 1600), 12);
+    assert_eq!(
+        ExponentialCliffSearcher::max_probes(0, 0, 1600),
+        ExponentialCliffSearcher::max_probes(1, 0, 1600)
+    );
+    assert_eq!(ExponentialCliffSearcher::ramp_schedule::<4>(0), [1, 2, 4, 8]);
+    assert_eq!(ExponentialCliffSearcher::max_ramp_probes(500, 100), 1);
+    assert_eq!(ExponentialCliffSearcher::max_probes(500, 250, 100), 1);
+    assert_eq!(
+        ExponentialCliffSearcher::max_ramp_probes(usize::MAX / 2 + 1, usize::MAX),
+        1
+    );
+    assert_eq!(
+        ExponentialCliffSearcher::ramp_schedule::<3>(usize::MAX / 2),
+        [usize::MAX / 2, usize::MAX - 1, 0]
+    );
+
+    // check the bound against every cliff the system might have
+    let configs = [(500, 250, 1600), (100, 1, 10_000), (7, 0, 1000), (0, 0, 100)];
+    for &(start, min_width, cap) in &configs {
+        let bound = ExponentialCliffSearcher::max_probes(start, min_width, cap);
+        let mut worst = 0;
+        for cliff in 0..=cap {
+            let mut scale = ExponentialCliffSearcher::until(start, min_width.max(1));
+            let mut probes = 0;
+            while let Some(load) = scale.next() {
+                probes += 1;
+                if load > cliff {
+                    scale.overloaded();
                 }
             }
-            return None;
+            worst = worst.max(probes);
         }
+        assert_eq!(worst, bound, "{} {} {}", start, min_width, cap);
+    }
+}
 
-        if let Some(ref mut last) = self.last {
-            if self.overloaded {
-                // the last thing we tried failed, so it sets an upper limit for max load
-                self.max_in.end = *last;
-                self.overloaded = false;
-            } else {
-                // the last thing succeeded, so that increases the lower limit
-                self.prev_min = self.max_in.start;
-                self.max_in.start = *last;
-            }
-
-            let next = if self.max_in.end == usize::max_value() {
-                // no upper limit, so exponential search
-                2 * self.max_in.start
-            } else {
-                // bisect the range
-                self.max_in.start + (self.max_in.end - self.max_in.start) / 2
-            };
-
-            // we only care about the max down to `fidelity`
-            if self.max_in.end - self.max_in.start > self.fidelity {
-                *last = next;
-                Some(next)
-            } else {
-                self.done = true;
-                // normally just None, but may be Some with filling
-                return self.next();
-            }
-        } else {
-            self.last = Some(self.max_in.start);
-            return self.last;
+#[test]
+fn extremes() {
+    // ramping right up to the top of the type
+    let mut scale = ExponentialCliffSearcher::until(usize::MAX / 2, usize::MAX / 64);
+    let cliff = usize::MAX - usize::MAX / 5;
+    while let Some(load) = scale.next() {
+        if load > cliff {
+            scale.overloaded();
         }
     }
+    let estimate = scale.estimate();
+    assert!(estimate.start <= cliff && cliff < estimate.end);
+    assert!(estimate.end - estimate.start <= usize::MAX / 64);
+
+    // scaling a bounded estimate past the top keeps it bounded
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(750));
+    scale.shift_bounds(usize::MAX as f64);
+    assert_eq!(scale.estimate(), usize::MAX - 1..usize::MAX - 1);
+    assert_eq!(scale.next(), Some(usize::MAX - 1));
 }
 
 #[test]
-fn search_from() {
+fn sanity_cap() {
+    // a benchmark that never overloads the system stops at the default cap
+    let report = ExponentialCliffSearcher::new(1).run_to_completion(|_| true);
+    assert_eq!(report.status, Status::NeverOverloaded);
+    assert_eq!(report.estimate, 1 << 20..usize::MAX);
+    assert_eq!(report.probes, 21);
+
+    // as does one that would overflow first
+    let mut scale = ExponentialCliffSearcher::new(usize::MAX / 2 + 1);
+    assert_eq!(scale.next(), Some(usize::MAX / 2 + 1));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.status(), Status::NeverOverloaded);
+    assert!(!scale.has_upper_bound());
+    assert_eq!(scale.estimate(), usize::MAX / 2 + 1..usize::MAX);
+
+    // raising the cap resumes the search, and survives suspension
     let mut scale = ExponentialCliffSearcher::new(500);
+    scale.sanity_cap(1500);
     assert_eq!(scale.next(), Some(500));
     assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.status(), Status::NeverOverloaded);
+    let mut scale = scale.suspend().resume();
+    assert_eq!(scale.status(), Status::NeverOverloaded);
+    scale.sanity_cap(1 << 20);
+    assert_eq!(scale.status(), Status::Completed);
     assert_eq!(scale.next(), Some(2000));
-    assert_eq!(scale.next(), Some(4000));
     scale.overloaded();
-    assert_eq!(scale.next(), Some(3000));
-    assert_eq!(scale.next(), Some(3500));
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.next(), Some(1750));
     scale.overloaded();
-    assert_eq!(scale.next(), Some(3250));
     assert_eq!(scale.next(), None);
-    assert_eq!(scale.estimate(), 3250..3500);
+    assert_eq!(scale.status(), Status::Completed);
+}
 
-    // check that it continues to be terminated
-    assert_eq!(scale.next(), None);
-    // even after another "failed"
+#[test]
+#[cfg(feature = "alloc")]
+fn formatting() {
+    use alloc::format;
+
+    let mut scale = ExponentialCliffSearcher::until(500, 100);
+    scale.fill_left();
+    assert_eq!(format!("{}", scale), "Exp{500..∞, fid=100, phase=warmup}");
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(format!("{}", scale), "Exp{500..∞, fid=100, phase=ramp}");
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(750));
+    assert_eq!(
+        format!("{:?}", scale),
+        "Exp{500..1000, fid=100, phase=bisect}"
+    );
     scale.overloaded();
+    assert_eq!(scale.next(), Some(625));
+    assert_eq!(scale.next(), Some(687));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(562));
+    assert_eq!(format!("{}", scale), "Exp{625..687, fid=100, phase=fill}");
     assert_eq!(scale.next(), None);
-    // and the estimate is still the same
-    assert_eq!(scale.estimate(), 3250..3500);
+    assert_eq!(format!("{}", scale), "Exp{625..687, fid=100, phase=done}");
+
+    // the alternate form has all the details
+    let verbose = format!("{:#?}", scale);
+    assert!(verbose.starts_with("ExponentialCliffSearcher {\n"));
+    assert!(verbose.contains("prev_min: 562,"));
 }
 
 #[test]
-fn search_from_until() {
-    let mut scale = ExponentialCliffSearcher::until(500, 1000);
+fn probes_left() {
+    let mut scale = ExponentialCliffSearcher::until(500, 100);
+    assert_eq!(scale.probes_left(), None);
     assert_eq!(scale.next(), Some(500));
     assert_eq!(scale.next(), Some(1000));
     assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.probes_left(), None);
+    scale.overloaded();
+    // halving 1000..2000 until it is no wider than 100 takes four runs
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.probes_left(), Some(4));
+    assert_eq!(scale.next(), Some(1750));
+    assert_eq!(scale.probes_left(), Some(3));
+    scale.finalize_by(0);
+    assert_eq!(scale.probes_left(), Some(1));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.probes_left(), Some(0));
+}
+
+#[test]
+fn skip_last() {
+    // nearby loads alternate below and above the skipped one, and stay inside the estimate
+    let mut scale = ExponentialCliffSearcher::until(500, 10);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(750));
+    let mut tried = [0; 6];
+    for t in &mut tried {
+        scale.skip_last();
+        *t = scale.next().unwrap();
+    }
+    assert_eq!(tried, [688, 812, 626, 874, 564, 936]);
+    scale.skip_last();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 500..1000);
+
+    // a skipped warmup moves up
+    let mut scale = ExponentialCliffSearcher::new(800);
+    assert_eq!(scale.next(), Some(800));
+    scale.skip_last();
+    assert_eq!(scale.next(), Some(900));
+    assert_eq!(scale.next(), Some(1800));
+    assert_eq!(scale.estimate(), 900..usize::MAX);
+
+    // skipping only applies to a load that was yielded, and survives suspension
+    let mut scale = ExponentialCliffSearcher::new(500);
+    scale.skip_last();
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.skip_last();
+    let mut scale = scale.suspend().resume();
+    assert_eq!(scale.next(), Some(875));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(687));
+    assert_eq!(scale.estimate(), 500..875);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn auto_fidelity() {
+    use crate::Suspend;
+    use alloc::string::ToString;
+
+    let mut scale = ExponentialCliffSearcher::with_fidelity(100, Fidelity::Auto);
+    assert_eq!(scale.to_string(), "Exp{100..∞, fid=auto, phase=warmup}");
+    for _ in 0..6 {
+        scale.next();
+    }
+    // resuming keeps waiting for the first upper bound
+    let mut scale = ExponentialCliffSearcher::resume(scale.suspend());
+    assert_eq!(scale.next(), Some(6400));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(4800));
+    assert_eq!(scale.to_string(), "Exp{3200..6400, fid=320, phase=bisect}");
+    // the width stays put even as the upper bound comes down
+    scale.overloaded();
     assert_eq!(scale.next(), Some(4000));
-    assert_eq!(scale.next(), Some(8000));
     scale.overloaded();
-    assert_eq!(scale.next(), Some(6000));
+    assert_eq!(scale.next(), Some(3600));
     scale.overloaded();
-    assert_eq!(scale.next(), Some(5000));
+    assert_eq!(scale.next(), Some(3400));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 3400..3600);
+
+    assert_eq!(
+        ExponentialCliffSearcher::with_fidelity(100, 7.into()).to_string(),
+        "Exp{100..∞, fid=7, phase=warmup}"
+    );
+}
+
+#[test]
+fn probes_fidelity() {
+    let mut scale = ExponentialCliffSearcher::with_fidelity(500, Fidelity::Probes(5));
+    assert_eq!(scale.probes_left(), Some(5));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
     scale.overloaded();
+    assert_eq!(scale.next(), Some(1500));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1250));
     assert_eq!(scale.next(), None);
-    assert_eq!(scale.estimate(), 4000..5000);
+    assert_eq!(scale.estimate(), 1250..1500);
 
-    // check that it continues to be terminated
+    // a budget that the ramp uses up leaves no upper bound
+    let mut scale = ExponentialCliffSearcher::with_fidelity(500, Fidelity::Probes(2));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
     assert_eq!(scale.next(), None);
-    // even after another "failed"
+    assert_eq!(scale.estimate(), 1000..usize::MAX);
+}
+
+#[test]
+fn merge_tighter() {
+    let mut scale = ExponentialCliffSearcher::until(500, 10);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    let mut branch = scale.branch();
+    assert_eq!(branch.next(), Some(2000));
+    branch.overloaded();
+    assert_eq!(branch.next(), Some(1500));
+
+    // the branch already took in the outcome of the probe at 1000 that is pending here
+    scale.merge_tighter(&branch).unwrap();
+    assert_eq!(scale.estimate(), 1000..2000);
+    assert_eq!(scale.next(), Some(1500));
     scale.overloaded();
-    assert_eq!(scale.next(), None);
-    // and the estimate is still the same
-    assert_eq!(scale.estimate(), 4000..5000);
+    assert_eq!(scale.next(), Some(1250));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1125));
+    assert_eq!(scale.estimate(), 1000..1250);
+
+    // merging a looser search changes nothing
+    scale
+        .merge_tighter(&ExponentialCliffSearcher::new(500))
+        .unwrap();
+    assert_eq!(scale.estimate(), 1000..1250);
+
+    // a pending probe outside the merged estimate is dropped
+    let other = ExponentialCliffSearcher::within(1000..1100, 10);
+    scale.merge_tighter(&other).unwrap();
+    assert_eq!(scale.estimate(), 1000..1100);
+    assert_eq!(scale.next(), Some(1050));
+
+    let other = ExponentialCliffSearcher::within(1150..usize::MAX, 10);
+    assert_eq!(
+        scale.merge_tighter(&other),
+        Err(Error::Contradiction {
+            supported: 1150,
+            overloaded: 1100
+        })
+    );
+    assert_eq!(scale.estimate(), 1000..1100);
 }
 
 #[test]
-fn fill_search() {
-    let mut scale = ExponentialCliffSearcher::until(500, 500);
-    scale.fill_left();
+fn plan_if() {
+    let mut scale = ExponentialCliffSearcher::until(500, 100);
+    // overloading the starting load ends the search
+    assert!(scale.plan_if(true).eq(Some(500)));
     assert_eq!(scale.next(), Some(500));
     assert_eq!(scale.next(), Some(1000));
     assert_eq!(scale.next(), Some(2000));
-    assert_eq!(scale.next(), Some(4000));
-    assert_eq!(scale.next(), Some(8000));
+    // an outcome already reported for the pending probe does not affect the plan
     scale.overloaded();
-    assert_eq!(scale.next(), Some(6000));
+    assert!(scale
+        .plan_if(false)
+        .take(2)
+        .eq([4000, 8000].iter().copied()));
+    assert!(scale.plan_if(true).take(2).eq([1500, 1250].iter().copied()));
+    assert_eq!(scale.next(), Some(1500));
+    assert!(scale.plan_if(false).eq([1750, 1875, 1937].iter().copied()));
+
+    // the plan ends where the search would
+    let scale = ExponentialCliffSearcher::within(1000..1100, 100);
+    assert_eq!(scale.plan_if(true).count(), 0);
+}
+
+#[test]
+fn clamping() {
+    let mut scale = ExponentialCliffSearcher::until(500, 10);
+    scale.detect_clamping(0.1);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    // a small shortfall is still an overload
+    scale.achieved(950);
+    assert_eq!(scale.next(), Some(725));
+    // as is a large one that was reported as overloaded
+    scale.achieved(400);
     scale.overloaded();
-    assert_eq!(scale.next(), Some(5000));
+    assert_eq!(scale.next(), Some(612));
+    assert_eq!(scale.estimate(), 500..725);
+    scale.achieved(540);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.clamped_at(), Some(540));
+    assert_eq!(scale.estimate(), 540..725);
+    // an overload was seen, so this is not for lack of overloading the system
+    assert_eq!(scale.status(), Status::Completed);
+
+    // the clamp survives suspension, and raising the cap doesn't make a difference
+    let mut scale = ExponentialCliffSearcher::new(500);
+    scale.detect_clamping(0.2);
+    assert_eq!(scale.next(), Some(500));
+    scale.achieved(100);
+    assert_eq!(scale.next(), None);
+    let mut scale = ExponentialCliffSearcher::resume(scale.suspend());
+    scale.sanity_cap(usize::MAX);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.clamped_at(), Some(100));
+    assert_eq!(scale.estimate(), 500..usize::MAX);
+    assert_eq!(scale.status(), Status::NeverOverloaded);
+
+    // the system changed, so the clamp may no longer apply
+    scale.invalidate_all_above(500);
+    assert_eq!(scale.clamped_at(), None);
+    assert_eq!(scale.next(), Some(1000));
+}
+
+#[test]
+fn tiny_starts() {
+    for cliff in 1..20 {
+        for &start in &[1, 2] {
+            let mut scale = ExponentialCliffSearcher::new(start);
+            let mut probes = 0;
+            while let Some(load) = scale.next() {
+                probes += 1;
+                assert!(probes < 20, "start {} did not converge on {}", start, cliff);
+                if load > cliff {
+                    scale.overloaded();
+                }
+            }
+            if cliff >= start {
+                assert_eq!(scale.estimate(), cliff..cliff + 1, "start {}", start);
+            }
+        }
+    }
+
+    let mut scale = ExponentialCliffSearcher::new(1);
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), Some(2));
     scale.overloaded();
-    assert_eq!(scale.next(), Some(4500));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1..2);
+
+    let mut scale = ExponentialCliffSearcher::new(2);
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(4));
     scale.overloaded();
+    assert_eq!(scale.next(), Some(3));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 3..4);
+}
 
-    // since filling is enabled, we'll also sample a few
-    // points just _before_ the highest known-good target.
-    assert_eq!(scale.next(), Some(3000));
-    assert_eq!(scale.next(), Some(3500));
+#[test]
+fn zero_start() {
+    // a start of 0 would double to 0 forever, so it is taken to be 1
+    let mut scale = ExponentialCliffSearcher::new(0);
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(4));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(3));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 2..3);
+    assert_eq!(scale.status(), Status::Completed);
 
-    // and then we should be done
+    let mut scale = ExponentialCliffSearcher::until(0, 0);
+    assert_eq!(scale.next(), Some(1));
+    scale.overloaded();
     assert_eq!(scale.next(), None);
-    // check that it continues to be terminated
+    assert_eq!(scale.estimate(), 1..1);
+
+    // but a bracket may still start at 0
+    let mut scale = ExponentialCliffSearcher::within(0..4, 1);
+    assert_eq!(scale.next(), Some(2));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1));
     assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1..2);
 }
 
 #[test]
-fn through_trait() {
-    let mut scale = ExponentialCliffSearcher::until(500, 1000);
-    let scale: &mut dyn CliffSearch = &mut scale;
+fn hinted() {
+    // a hint that is right on the money is confirmed at the end
+    let mut load = ExponentialCliffSearcher::until(500, 100);
+    load.hint_upper(1000);
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.next(), Some(750));
+    assert_eq!(load.next(), Some(875));
+    assert_eq!(load.next(), Some(937));
+    let suspended = load.suspend();
+    assert_eq!(suspended.hint, Some(1000));
+    let mut load = suspended.resume();
+    assert_eq!(load.next(), Some(1000));
+    assert_eq!(load.kind(), Some(ProbeKind::Bisect));
+    load.overloaded();
+    assert_eq!(load.next(), None);
+    assert_eq!(load.estimate(), 937..1000);
+
+    // an overload below the hint makes it moot
+    let mut load = ExponentialCliffSearcher::until(500, 100);
+    load.hint_upper(1000);
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.next(), Some(750));
+    load.overloaded();
+    assert_eq!(load.next(), Some(625));
+    assert_eq!(load.next(), Some(687));
+    assert_eq!(load.next(), None);
+    assert_eq!(load.estimate(), 687..750);
+
+    // hints outside of the estimate are ignored
+    let mut load = ExponentialCliffSearcher::within(500..800, 100);
+    load.hint_upper(400);
+    load.hint_upper(900);
+    assert_eq!(load.estimate(), 500..800);
+    assert_eq!(load.hint, None);
+}
+
+#[test]
+fn explain() {
+    let mut load = ExponentialCliffSearcher::new(500);
+    load.sanity_cap(1000);
+    assert_eq!(load.explain_next().rule, ProbeRule::Start);
+    assert_eq!(load.next(), Some(500));
+    let why = load.explain_next();
+    assert_eq!((why.load, why.rule), (Some(1000), ProbeRule::Double));
+    assert_eq!(load.next(), Some(1000));
+    load.inconclusive();
+    assert_eq!(load.explain_next().rule, ProbeRule::Repeat);
+    assert_eq!(load.next(), Some(1000));
+    let why = load.explain_next();
+    assert_eq!((why.load, why.rule), (None, ProbeRule::Capped));
+
+    let mut load = ExponentialCliffSearcher::until(500, 100);
+    load.hint_upper(1000);
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.next(), Some(750));
+    load.overloaded_by(3.0);
+    let why = load.explain_next();
+    assert_eq!(why.rule, ProbeRule::Skewed { severity: 3.0 });
+    assert_eq!(why.bounds, 500..750);
+    assert_eq!(why.load, Some(583));
+    assert_eq!(load.next(), Some(583));
+    assert_eq!(load.next(), Some(666));
+    let why = load.explain_next();
+    assert_eq!((why.load, why.rule), (None, ProbeRule::Converged));
+    assert_eq!(why.bounds, 666..750);
+
+    let mut load = ExponentialCliffSearcher::until(500, 100);
+    load.hint_upper(600);
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.explain_next().rule, ProbeRule::CheckHint);
+}
+
+#[test]
+fn coarse_fidelity() {
+    // by default, the bracket is taken as it is
+    let mut load = ExponentialCliffSearcher::until(500, 1000);
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.next(), Some(1000));
+    load.overloaded();
+    assert_eq!(load.next(), None);
+    assert_eq!(load.estimate(), 500..1000);
+    assert_eq!(load.coarse_fidelity(), Some((1000, 500)));
+    assert_eq!(load.suspend().coarse_fidelity, Some((1000, 500)));
+
+    // a bracket that is wider than the width is not coarse, even if later ones are not
+    let mut load = ExponentialCliffSearcher::until(500, 300);
+    while let Some(l) = load.next() {
+        if l > 1600 {
+            load.overloaded();
+        }
+    }
+    assert_eq!(load.estimate(), 1500..1750);
+    assert_eq!(load.coarse_fidelity(), None);
+
+    let mut load = ExponentialCliffSearcher::within(1000..1100, 100);
+    load.on_coarse_fidelity(CoarseFidelity::Shrink);
+    assert_eq!(load.next(), Some(1050));
+    assert_eq!(load.coarse_fidelity(), Some((100, 100)));
+    // the width is now 5% of 1100
+    load.overloaded();
+    assert_eq!(load.next(), None);
+    assert_eq!(load.estimate(), 1000..1050);
+
+    let mut load = ExponentialCliffSearcher::until(500, 1000);
+    load.on_coarse_fidelity(CoarseFidelity::Error);
+    assert_eq!(load.try_next(), Ok(Some(500)));
+    assert_eq!(load.try_next(), Ok(Some(1000)));
+    load.overloaded();
+    assert!(matches!(load.try_next(), Err(Error::InvalidConfig(_))));
+    assert_eq!(load.try_next(), Ok(None));
+    assert_eq!(load.estimate(), 500..1000);
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut report = crate::Report::new();
+        load.warn(&mut report);
+        assert_eq!(
+            report.warnings,
+            [
+                "the first bracket around the cliff was 500 wide, which is no wider than the \
+              requested width of 1000"
+            ]
+        );
+    }
+}
+
+#[test]
+fn growth_factor() {
+    let mut scale = ExponentialCliffSearcher::with_growth_factor(500, 4.0);
     assert_eq!(scale.next(), Some(500));
-    assert_eq!(scale.next(), Some(1000));
     assert_eq!(scale.next(), Some(2000));
-    assert_eq!(scale.next(), Some(4000));
     assert_eq!(scale.next(), Some(8000));
     scale.overloaded();
-    assert_eq!(scale.next(), Some(6000));
-    scale.overloaded();
     assert_eq!(scale.next(), Some(5000));
+    let mut scale = scale.suspend().resume();
     scale.overloaded();
+    assert_eq!(scale.next(), Some(3500));
+    assert_eq!(scale.estimate(), 2000..5000);
+
+    // the ramp is explained with the factor it uses
+    #[cfg(feature = "alloc")]
+    {
+        use alloc::string::ToString;
+        let mut scale = ExponentialCliffSearcher::with_growth_factor(1000, 1.5);
+        assert_eq!(scale.next(), Some(1000));
+        let why = scale.explain_next();
+        assert_eq!((why.rule, why.growth_factor), (ProbeRule::Double, 1.5));
+        assert_eq!(
+            why.to_string(),
+            "run 1500, 1.5 times the highest sustained load, since nothing has overloaded the \
+             system yet"
+        );
+        let mut scale = ExponentialCliffSearcher::new(1000);
+        assert_eq!(scale.next(), Some(1000));
+        assert_eq!(
+            scale.explain_next().to_string(),
+            "run 2000, twice the highest sustained load, since nothing has overloaded the system \
+             yet"
+        );
+    }
+
+    // a small factor still makes progress on small loads
+    let mut scale = ExponentialCliffSearcher::with_growth_factor(1, 1.1);
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(3));
+
+    // and one that would never grow is taken to be the default
+    let mut scale = ExponentialCliffSearcher::with_growth_factor(500, f64::NAN);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+
+    // a ramp that would overflow ends there
+    let mut scale = ExponentialCliffSearcher::with_growth_factor(usize::MAX / 3 * 2, 1.5);
+    assert_eq!(scale.next(), Some(usize::MAX / 3 * 2));
     assert_eq!(scale.next(), None);
-    assert_eq!(scale.estimate(), 4000..5000);
+    assert_eq!(scale.status(), Status::NeverOverloaded);
 }
 
 #[test]
-fn immediate() {
-    let mut scale = ExponentialCliffSearcher::new(500);
+fn with_max() {
+    let mut scale = ExponentialCliffSearcher::with_max(500, 3000);
     assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(3000));
     scale.overloaded();
+    // the cap overloaded the system, so the search carries on below it
+    assert_eq!(scale.next(), Some(2500));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(2250));
     assert_eq!(scale.next(), None);
-    assert_eq!(scale.estimate(), 500..500);
+    assert_eq!(scale.estimate(), 2250..2500);
+    assert_eq!(scale.status(), Status::Completed);
+
+    // the sanity cap still applies, unless it is raised
+    let mut scale = ExponentialCliffSearcher::with_max(1, 1 << 24);
+    assert_eq!(scale.by_ref().last(), Some(1 << 20));
+    assert_eq!(scale.estimate(), (1 << 20)..usize::MAX);
+    scale.sanity_cap(usize::MAX);
+    assert_eq!(scale.by_ref().last(), Some(1 << 24));
+    assert_eq!(scale.estimate(), (1 << 24)..usize::MAX);
+    assert!(!scale.has_upper_bound());
+    assert_eq!(scale.status(), Status::NeverOverloaded);
+
+    // a max of usize::MAX is run too, even though there is no room above it
+    let mut scale = ExponentialCliffSearcher::with_max(usize::MAX / 2 + 1, usize::MAX);
+    assert_eq!(scale.next(), Some(usize::MAX / 2 + 1));
+    assert_eq!(scale.next(), Some(usize::MAX));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), usize::MAX..usize::MAX);
+    assert_eq!(scale.status(), Status::NeverOverloaded);
+
+    // a start above max is taken to be max
+    let mut scale = ExponentialCliffSearcher::with_max(500, 300);
+    assert_eq!(scale.next(), Some(300));
+    assert_eq!(scale.explain_next().rule, ProbeRule::AtMax);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 300..usize::MAX);
+
+    // a report of a search that ran into its max claims no upper bound it never saw
+    #[cfg(feature = "alloc")]
+    {
+        let report = ExponentialCliffSearcher::with_max(500, 3000).run_to_completion(|_| true);
+        assert_eq!(report.estimate, 3000..usize::MAX);
+        assert_eq!(report.status, Status::NeverOverloaded);
+        assert_eq!(report.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn marginal() {
+    use super::{BinaryMinSearcher, Verdict};
+
+    let mut scale = ExponentialCliffSearcher::until(500, 100);
+    assert_eq!(scale.next(), Some(500));
+    // a marginal run during the ramp steps just above it rather than doubling
+    scale.marginal();
+    let why = scale.explain_next();
+    assert_eq!(why.rule, ProbeRule::Marginal);
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        alloc::string::ToString::to_string(&why),
+        "run 600, just above 500, since the system only just kept up with it"
+    );
+    assert_eq!(scale.next(), Some(600));
+    // and once that is sustained, the ramp carries on from there
+    assert_eq!(scale.next(), Some(1200));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(900));
+    scale.marginal();
+    let mut scale = scale.suspend().resume();
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 900..1000);
+
+    // a marginal verdict is sustained to a search that doesn't act on it
+    let mut scale = BinaryMinSearcher::until(1024, 256);
+    assert_eq!(scale.next(), Some(1024));
+    Verdict::Marginal.apply(&mut scale);
+    assert_eq!(scale.next(), Some(512));
+}
+
+#[test]
+fn generic_values() {
+    let mut scale = ExponentialCliffSearcher::starting_at(500u64, 100);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.next(), Some(1750));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1625));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1562));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1562..1625);
+
+    // bounds that can no longer be split still end the search
+    let mut scale = ExponentialCliffSearcher::starting_at(1e18, 1.0);
+    let mut probes = 0;
+    while let Some(load) = scale.next() {
+        probes += 1;
+        assert!(probes < 1000, "search did not terminate");
+        if load > 1.5e18 {
+            scale.overloaded();
+        }
+    }
+    assert!(scale.estimate().start <= 1.5e18);
+    assert!(scale.estimate().end > 1.5e18);
+
+    // the ramp stops when doubling would overflow the type
+    let mut scale = ExponentialCliffSearcher::starting_at(1u32 << 30, 1);
+    scale.sanity_cap(u32::MAX);
+    assert_eq!(scale.next(), Some(1 << 30));
+    assert_eq!(scale.next(), Some(1 << 31));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), (1 << 31)..u32::MAX);
 }
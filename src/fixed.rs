@@ -0,0 +1,87 @@
+use core::fmt;
+
+/// A floating-point number formatted with a fixed number of decimals.
+///
+/// The [`Display`](fmt::Display) implementation upholds the same promise as the rest of this
+/// crate's [output](crate#output-formats): plain ASCII decimal digits with `.` as the decimal
+/// separator, no thousands separators, and never an exponent, however large or small the number.
+/// The number is rounded to the given number of decimals, and a negative number that rounds to
+/// zero is written without a sign, so that `-0.0001` at two decimals is `0.00`, not `-0.00`.
+///
+/// ```rust
+/// use cliff::Fixed;
+///
+/// let p99 = Fixed::new(12.3456, 2).unwrap();
+/// assert_eq!(p99.to_string(), "12.35");
+/// assert_eq!(Fixed::new(1e21, 1).unwrap().to_string(), "1000000000000000000000.0");
+/// assert_eq!(Fixed::new(-1e-9, 3).unwrap().to_string(), "0.000");
+/// assert!(Fixed::new(f64::NAN, 2).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fixed {
+    value: f64,
+    decimals: usize,
+}
+
+impl Fixed {
+    /// Format `value` with `decimals` digits after the decimal point.
+    ///
+    /// Returns `None` if `value` is infinite or NaN, since those have no representation as a
+    /// number in CSV or JSON. It is up to the caller to write those as whatever the format calls
+    /// for, like an empty field or `null`.
+    pub fn new(value: f64, decimals: usize) -> Option<Self> {
+        if value.is_finite() {
+            Some(Fixed { value, decimals })
+        } else {
+            None
+        }
+    }
+
+    /// The number being formatted.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Notes whether any digit other than `0` gets written.
+        struct NonZero(bool);
+        impl fmt::Write for NonZero {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 |= s.bytes().any(|b| (b'1'..=b'9').contains(&b));
+                Ok(())
+            }
+        }
+
+        let mut value = self.value;
+        if value.is_sign_negative() {
+            let mut nonzero = NonZero(false);
+            fmt::write(&mut nonzero, format_args!("{:.*}", self.decimals, value))?;
+            if !nonzero.0 {
+                value = 0.0;
+            }
+        }
+        write!(f, "{:.*}", self.decimals, value)
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn fixed() {
+    use alloc::string::ToString;
+
+    let s = |v: f64, d: usize| Fixed::new(v, d).unwrap().to_string();
+    assert_eq!(s(0.0, 0), "0");
+    assert_eq!(s(-0.0, 2), "0.00");
+    assert_eq!(s(1.5, 0), "2");
+    assert_eq!(s(-1.25, 1), "-1.2");
+    assert_eq!(s(-0.004, 2), "0.00");
+    assert_eq!(s(-0.005001, 2), "-0.01");
+    assert_eq!(s(1e-7, 8), "0.00000010");
+    assert_eq!(s(12_345_678.9, 1), "12345678.9");
+    assert!(s(f64::MAX, 0).bytes().all(|b| b.is_ascii_digit()));
+    assert_eq!(Fixed::new(2.5, 1).unwrap().value(), 2.5);
+    assert!(Fixed::new(f64::INFINITY, 1).is_none());
+    assert!(Fixed::new(f64::NEG_INFINITY, 1).is_none());
+}
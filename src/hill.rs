@@ -0,0 +1,492 @@
+use super::smoothing::Smoother;
+use super::{CliffSearch, Describe, RandomSource, Refine, Smoothing, SplitMix64};
+
+/// An iterator that settles on a good value for a parameter whose response is not monotone.
+///
+/// Some tunables (like compaction concurrency) do not have a single cliff: performance improves up
+/// to some point and then degrades again, and the surface may be noisy. This searcher performs an
+/// adaptive-step hill climb: it probes one step away from the best value seen so far, moves there
+/// and doubles the step if that was an improvement, tries the other direction if it was not, and
+/// halves the step if neither direction helped. The search ends once the step falls below the
+/// requested minimum.
+///
+/// Probes that are marked [`overloaded`](HillClimbSearcher::overloaded) are never considered an
+/// improvement. For probes that are not, you can report a metric with
+/// [`score`](HillClimbSearcher::score), where higher is better. If no score is given, the probed
+/// value itself is used as the score, so that the search prefers the highest value the system
+/// keeps up with.
+///
+/// On its own, the climb is deterministic. With [`randomized`](HillClimbSearcher::randomized),
+/// it becomes a stochastic hill climb that varies every step at random, which keeps it from
+/// probing the same grid of values on every run and lets it step past narrow dips.
+///
+/// ```rust
+/// use cliff::HillClimbSearcher;
+///
+/// // throughput peaks at 40 and falls off on either side
+/// let throughput = |threads: usize| 1000.0 - (threads as f64 - 40.0).abs() * 10.0;
+///
+/// let mut threads = HillClimbSearcher::until(8, 8, 2);
+/// while let Some(t) = threads.next() {
+///     threads.score(throughput(t));
+/// }
+/// assert_eq!(threads.best(), Some(40));
+/// ```
+///
+/// See also the [crate-level documentation](..) for details.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct HillClimbSearcher<R = SplitMix64> {
+    center: usize,
+    center_score: Option<f64>,
+    step: usize,
+    fidelity: usize,
+    up: bool,
+    flipped: bool,
+    last: Option<usize>,
+    started: bool,
+    score: Option<f64>,
+    overloaded: bool,
+    inconclusive: bool,
+    done: bool,
+    smoother: Smoother,
+    rng: Option<R>,
+}
+
+impl HillClimbSearcher {
+    /// Perform a hill climb starting at `start` with an initial step size of `step`, and ending
+    /// when the step size falls below `min_step`.
+    pub fn until(start: usize, step: usize, min_step: usize) -> Self {
+        Self {
+            center: start,
+            center_score: None,
+            step,
+            fidelity: min_step,
+            up: true,
+            flipped: false,
+            last: None,
+            started: false,
+            score: None,
+            overloaded: false,
+            inconclusive: false,
+            done: false,
+            smoother: Smoother::default(),
+            rng: None,
+        }
+    }
+}
+
+impl<R> HillClimbSearcher<R>
+where
+    R: RandomSource,
+{
+    /// Vary every step at random, using randomness from `rng`.
+    ///
+    /// Each probe is between half and one and a half times the current step size away from the
+    /// best value so far, rather than exactly one step, and the direction of the first step is
+    /// picked at random. The step size itself still doubles and halves as usual, so the search
+    /// ends just the same. With a seeded generator like [`SplitMix64`], the probes are the same
+    /// every time the search is run with the same outcomes, and the seed ends up in the
+    /// [`Report`](crate::Report::seed).
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, HillClimbSearcher, SplitMix64};
+    ///
+    /// let throughput = |threads: usize| 1000.0 - (threads as f64 - 40.0).abs() * 10.0;
+    ///
+    /// let mut threads = HillClimbSearcher::until(8, 8, 2).randomized(SplitMix64::new(42));
+    /// while let Some(t) = threads.next() {
+    ///     threads.score(throughput(t));
+    /// }
+    /// assert_eq!(threads.seed(), Some(42));
+    /// assert!((36..=44).contains(&threads.best().unwrap()));
+    /// ```
+    pub fn randomized<R2>(self, mut rng: R2) -> HillClimbSearcher<R2>
+    where
+        R2: RandomSource,
+    {
+        let up = if self.started {
+            self.up
+        } else {
+            rng.next_u64() & 1 == 0
+        };
+        HillClimbSearcher {
+            center: self.center,
+            center_score: self.center_score,
+            step: self.step,
+            fidelity: self.fidelity,
+            up,
+            flipped: self.flipped,
+            last: self.last,
+            started: self.started,
+            score: self.score,
+            overloaded: self.overloaded,
+            inconclusive: self.inconclusive,
+            done: self.done,
+            smoother: self.smoother,
+            rng: Some(rng),
+        }
+    }
+
+    /// Run every value several times, and compare values by the moving average of their score
+    /// over those runs rather than by a single run.
+    ///
+    /// The next call to [`Iterator::next`] yields the same value again until all its runs are in,
+    /// unless the system could not keep up with it. If no score is given, the value itself is the
+    /// score of every run, as usual.
+    ///
+    /// ```rust
+    /// use cliff::{HillClimbSearcher, Smoothing};
+    ///
+    /// let mut threads = HillClimbSearcher::until(8, 8, 2);
+    /// threads.smooth(Smoothing::new(0.3, 3));
+    /// let mut runs = 0;
+    /// while let Some(t) = threads.next() {
+    ///     runs += 1;
+    ///     // every third run is hit by a hiccup that makes it look far better than it is
+    ///     let hiccup = if runs % 3 == 0 { 500.0 } else { 0.0 };
+    ///     threads.score(1000.0 - (t as f64 - 40.0).abs() * 10.0 + hiccup);
+    /// }
+    /// assert_eq!(threads.best(), Some(40));
+    /// ```
+    pub fn smooth(&mut self, smoothing: Smoothing) {
+        self.smoother.set(smoothing);
+    }
+
+    // NOTE: we provide inherent methods for CliffSearch so that those who do not need LoadIterator
+    // do not need to think about the trait at all.
+
+    /// Indicate that the system could not keep up with the previous value yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This will affect what value the next call to [`Iterator::next`] yields.
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+    }
+
+    /// Indicate that the benchmark run for the previous value yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same value again, so that it can be re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
+    /// Report a metric for the previous value yielded by [`Iterator::next`], where higher is
+    /// better.
+    ///
+    /// A `NaN` score is never considered an improvement.
+    pub fn score(&mut self, score: f64) {
+        self.score = Some(score);
+    }
+
+    /// The best value found so far, if any probe has succeeded.
+    pub fn best(&self) -> Option<usize> {
+        self.center_score.map(|_| self.center)
+    }
+
+    /// Give the range around the best value found so far that the search has yet to explore.
+    ///
+    /// The range is centered on the current best value, and is as wide as twice the current step
+    /// size.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.center.saturating_sub(self.step)..self.center.saturating_add(self.step)
+    }
+
+    /// Change the minimum step size, and resume the search if it had ended with a step that is at
+    /// least that large.
+    ///
+    /// This provides [`Refine::refine`] without having to `use` the trait.
+    pub fn refine(&mut self, min_step: usize) {
+        self.fidelity = min_step;
+        if self.done && self.step != 0 && self.step >= min_step {
+            self.done = false;
+        }
+    }
+
+    fn candidate(&mut self) -> Option<usize> {
+        let step = match &mut self.rng {
+            // anywhere from half a step to one and a half steps, but always at least one
+            Some(rng) => ((self.step as f64 * (0.5 + rng.next_f64())) as usize).max(1),
+            None => self.step,
+        };
+        let next = if self.up {
+            self.center.checked_add(step)
+        } else {
+            self.center.checked_sub(step)
+        };
+        next.filter(|&n| n != self.center)
+    }
+
+    fn no_improvement(&mut self) {
+        if self.flipped {
+            // neither direction helped, so look closer
+            self.step /= 2;
+            self.flipped = false;
+        } else {
+            self.up = !self.up;
+            self.flipped = true;
+        }
+    }
+}
+
+impl<R> CliffSearch for HillClimbSearcher<R>
+where
+    R: RandomSource,
+{
+    fn overloaded(&mut self) {
+        HillClimbSearcher::overloaded(self)
+    }
+
+    fn inconclusive(&mut self) {
+        HillClimbSearcher::inconclusive(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        HillClimbSearcher::estimate(self)
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.rng.as_ref().and_then(RandomSource::seed)
+    }
+}
+
+impl<R> Refine for HillClimbSearcher<R>
+where
+    R: RandomSource,
+{
+    fn refine(&mut self, min_step: usize) {
+        HillClimbSearcher::refine(self, min_step)
+    }
+}
+
+impl<R> Iterator for HillClimbSearcher<R>
+where
+    R: RandomSource,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.inconclusive && self.last.is_some() {
+            // the last run told us nothing, so run it again
+            self.inconclusive = false;
+            self.overloaded = false;
+            self.score = None;
+            return self.last;
+        }
+        self.inconclusive = false;
+
+        if let Some(last) = self.last {
+            if self.overloaded {
+                self.smoother.reset();
+            } else {
+                let score = self.score.take().unwrap_or(last as f64);
+                match self.smoother.observe(score) {
+                    Some(smoothed) => self.score = Some(smoothed),
+                    // not all runs of the value are in yet
+                    None => return self.last,
+                }
+            }
+        }
+
+        if let Some(last) = self.last.take() {
+            let score = if self.overloaded {
+                None
+            } else {
+                Some(self.score.unwrap_or(last as f64)).filter(|s| !s.is_nan())
+            };
+
+            if last == self.center {
+                // this was the very first probe (later probes are never at the center)
+                self.center_score = score;
+            } else {
+                let better = match (score, self.center_score) {
+                    (Some(s), Some(c)) => s > c,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                };
+                if better {
+                    // keep going in the same direction, and more boldly
+                    self.center = last;
+                    self.center_score = score;
+                    self.step = self.step.saturating_mul(2);
+                    self.flipped = false;
+                } else {
+                    self.no_improvement();
+                }
+            }
+        }
+        // if no probe was outstanding (like after a refine), there is nothing to adjudicate
+        self.overloaded = false;
+        self.score = None;
+
+        loop {
+            if self.step == 0 || self.step < self.fidelity {
+                self.done = true;
+                return None;
+            }
+
+            if !self.started {
+                self.started = true;
+                self.last = Some(self.center);
+                return self.last;
+            }
+
+            if let Some(next) = self.candidate() {
+                self.last = Some(next);
+                return self.last;
+            }
+
+            // stepping in this direction would leave the domain
+            self.no_improvement();
+        }
+    }
+}
+
+impl<R> Describe for HillClimbSearcher<R> {
+    fn name(&self) -> &'static str {
+        "hill_climb"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("step", &self.step);
+        setting("min_width", &self.fidelity);
+        self.smoother.settings(setting);
+        if self.rng.is_some() {
+            setting("randomized", &true);
+        }
+    }
+}
+
+#[test]
+fn climb_to_peak() {
+    let mut scale = HillClimbSearcher::until(100, 50, 25);
+    assert_eq!(scale.next(), Some(100));
+    scale.score(1.0);
+    // an improvement, so we keep going with a bigger step
+    assert_eq!(scale.next(), Some(150));
+    scale.score(2.0);
+    assert_eq!(scale.next(), Some(250));
+    scale.score(1.5);
+    // that was worse, so try the other direction
+    assert_eq!(scale.next(), Some(50));
+    scale.score(0.5);
+    // neither helped, so halve the step
+    assert_eq!(scale.next(), Some(100));
+    scale.score(1.0);
+    assert_eq!(scale.next(), Some(200));
+    scale.score(1.8);
+    assert_eq!(scale.next(), Some(175));
+    scale.score(1.9);
+    assert_eq!(scale.next(), Some(125));
+    scale.score(1.9);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.best(), Some(150));
+    assert_eq!(scale.estimate(), 138..162);
+
+    // check that it continues to be terminated
+    assert_eq!(scale.next(), None);
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+}
+
+#[test]
+fn no_score_prefers_higher() {
+    let mut scale = HillClimbSearcher::until(100, 100, 50);
+    assert_eq!(scale.next(), Some(100));
+    assert_eq!(scale.next(), Some(200));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(0));
+    assert_eq!(scale.next(), Some(50));
+    assert_eq!(scale.next(), Some(150));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.best(), Some(100));
+}
+
+#[test]
+fn through_trait() {
+    let mut scale = HillClimbSearcher::until(100, 100, 50);
+    let scale: &mut dyn CliffSearch = &mut scale;
+    assert_eq!(scale.next(), Some(100));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(200));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(0));
+    // even 0 is better than nothing
+    assert_eq!(scale.next(), Some(200));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(100));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(50));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 0..25);
+}
+
+#[test]
+fn smoothed() {
+    let mut scale = HillClimbSearcher::until(100, 50, 25);
+    scale.smooth(Smoothing::new(0.5, 2));
+    assert_eq!(scale.next(), Some(100));
+    scale.score(1.0);
+    assert_eq!(scale.next(), Some(100));
+    scale.score(1.0);
+    assert_eq!(scale.next(), Some(150));
+    scale.score(3.0);
+    assert_eq!(scale.next(), Some(150));
+    // the outlier is averaged away, so 150 is no better than 100
+    scale.score(-1.0);
+    assert_eq!(scale.next(), Some(50));
+    // a value that overloads the system needs no more runs
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(75));
+    // and without a score, the value itself is the score
+    assert_eq!(scale.next(), Some(75));
+    assert_eq!(scale.next(), Some(25));
+    assert_eq!(scale.best(), Some(75));
+}
+
+#[test]
+fn randomized() {
+    let throughput = |t: usize| 1000.0 - (t as f64 - 400.0).abs();
+    let run = |seed| {
+        let mut scale = HillClimbSearcher::until(100, 64, 4).randomized(SplitMix64::new(seed));
+        let mut probes = [0; 64];
+        let mut n = 0;
+        while let Some(t) = scale.next() {
+            probes[n] = t;
+            n += 1;
+            scale.score(throughput(t));
+        }
+        assert_eq!(scale.seed(), Some(seed));
+        (probes, n, scale.best().unwrap())
+    };
+
+    // the same seed gives the same climb
+    assert_eq!(run(42), run(42));
+    // and other seeds another one, that gets to the peak all the same
+    assert_ne!(run(42).0, run(7).0);
+    for seed in 0..20 {
+        let (probes, n, best) = run(seed);
+        assert_eq!(probes[0], 100);
+        assert!(n < probes.len());
+        assert!((396..=404).contains(&best), "seed {} settled on {}", seed, best);
+    }
+
+    // every step is between half and one and a half of the step size
+    let mut scale = HillClimbSearcher::until(1000, 100, 100).randomized(SplitMix64::new(3));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    let first = scale.next().unwrap();
+    assert!((50..=150).contains(&(first as isize - 1000).abs()));
+}
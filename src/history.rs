@@ -0,0 +1,187 @@
+//! Trends across historical reports.
+//!
+//! Teams that run cliff as a nightly capacity regression test accumulate a directory full of
+//! reports written with [`Report::to_json`]. [`History`] loads such a directory, and detects when
+//! the cliff has moved down by more than some tolerance from one report to the next.
+//!
+//! ```rust,no_run
+//! use cliff::history::History;
+//!
+//! let history = History::load_dir("reports/").unwrap();
+//! for (name, estimate) in history.cliffs() {
+//!     println!("{}: {}..{}", name, estimate.start, estimate.end);
+//! }
+//! for regression in history.regressions(0.05) {
+//!     println!("regressed in {}", history.entries()[regression.index].name);
+//! }
+//! ```
+//!
+//! This module is only available with the `std` feature.
+
+use crate::{Comparison, Report};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+use std::path::Path;
+use std::{fs, io};
+
+/// A report along with the name it was stored under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The name of the report, like the stem of the file it was loaded from.
+    pub name: String,
+    /// The report itself.
+    pub report: Report,
+}
+
+/// A point where the cliff moved down from one report to the next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    /// The index of the report that regressed in [`History::entries`].
+    pub index: usize,
+    /// The estimate of the report before it.
+    pub from: Range<usize>,
+    /// The estimate of the report that regressed.
+    pub to: Range<usize>,
+}
+
+/// A sequence of reports, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Start an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `.json` report in `dir`, in order of file name.
+    ///
+    /// Naming reports by date (like `2024-03-01.json`) thus orders them oldest first. A file that
+    /// is not a valid report is an error of kind [`io::ErrorKind::InvalidData`].
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(false, |ext| ext == "json") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut history = Self::new();
+        for path in paths {
+            let json = fs::read_to_string(&path)?;
+            let report = Report::parse(&json).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    std::format!("{}: {}", path.display(), e),
+                )
+            })?;
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            history.push(name, report);
+        }
+        Ok(history)
+    }
+
+    /// Add a report to the end of the history.
+    pub fn push<S: Into<String>>(&mut self, name: S, report: Report) {
+        self.entries.push(Entry {
+            name: name.into(),
+            report,
+        });
+    }
+
+    /// Give all the reports, oldest first.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Give the name and estimate of every report, oldest first.
+    pub fn cliffs(&self) -> impl Iterator<Item = (&str, Range<usize>)> + '_ {
+        self.entries
+            .iter()
+            .map(|e| (&*e.name, e.report.estimate.clone()))
+    }
+
+    /// Find the reports whose cliff lies clearly below that of the report before them.
+    ///
+    /// A report counts as a regression only if its entire estimate lies more than a fraction
+    /// `tolerance` below the entire estimate of the report before it, as determined by
+    /// [`Report::compare`]. Comparing the estimates rather than single points keeps noise within
+    /// the precision of the searches from being flagged.
+    pub fn regressions(&self, tolerance: f64) -> Vec<Regression> {
+        self.entries
+            .windows(2)
+            .enumerate()
+            .filter_map(|(i, pair)| {
+                let (from, to) = (&pair[0].report, &pair[1].report);
+                if to.compare(from, tolerance) == Comparison::Regressed {
+                    Some(Regression {
+                        index: i + 1,
+                        from: from.estimate.clone(),
+                        to: to.estimate.clone(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+fn report(estimate: Range<usize>) -> Report {
+    let mut r = Report::new();
+    r.estimate = estimate;
+    r
+}
+
+#[test]
+fn regressions() {
+    let mut history = History::new();
+    history.push("mon", report(1000..1100));
+    // within the precision of the search
+    history.push("tue", report(950..1050));
+    // within the tolerance
+    history.push("wed", report(900..940));
+    history.push("thu", report(700..800));
+    history.push("fri", report(1000..1100));
+    assert_eq!(
+        history.regressions(0.05),
+        alloc::vec![Regression {
+            index: 3,
+            from: 900..940,
+            to: 700..800
+        }]
+    );
+    assert_eq!(history.regressions(0.5), alloc::vec![]);
+    assert_eq!(history.cliffs().nth(3), Some(("thu", 700..800)));
+}
+
+#[test]
+fn load_dir() {
+    let dir = std::env::temp_dir().join(std::format!("cliff-history-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("2024-01-02.json"), report(800..900).to_json()).unwrap();
+    fs::write(dir.join("2024-01-01.json"), report(1000..1100).to_json()).unwrap();
+    fs::write(dir.join("notes.txt"), "not a report").unwrap();
+
+    let history = History::load_dir(&dir).unwrap();
+    assert_eq!(
+        history.cliffs().collect::<Vec<_>>(),
+        alloc::vec![("2024-01-01", 1000..1100), ("2024-01-02", 800..900)]
+    );
+    assert_eq!(history.regressions(0.0).len(), 1);
+
+    fs::write(dir.join("2024-01-03.json"), "{").unwrap();
+    let err = History::load_dir(&dir).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
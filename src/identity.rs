@@ -0,0 +1,338 @@
+use super::{CliffSearch, Describe, RandomSource, Refine, SplitMix64, Status};
+
+/// An ID for a search, along with a seed derived from it.
+///
+/// Correlating the logs, report files, and retries of a search across a pipeline by timestamp is
+/// fragile, since timestamps collide. Instead, give each search an ID, and carry it everywhere:
+/// wrap the search in [`Identified`] so that its [`Report`](crate::Report) and the lines written
+/// by [`Streamed`](crate::Streamed) include it, and pass it on to benchmark processes through
+/// [environment variables](Identity::env).
+///
+/// The seed is derived from the ID, so the same ID always gives the same seed, and any
+/// randomness seeded from it, like [`rng`](Identity::rng), is reproducible from the ID alone.
+///
+/// ```rust
+/// use cliff::{CliffSearch, ExponentialCliffSearcher, Identified, Identity};
+///
+/// let id = Identity::new(42);
+/// assert_eq!(Identity::new(42).seed(), id.seed());
+///
+/// let search = Identified::new(ExponentialCliffSearcher::new(500), id);
+/// let report = search.run_to_completion(|load| load <= 1600);
+/// assert_eq!(report.search_id, Some(42));
+/// assert_eq!(report.seed, Some(id.seed()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Identity {
+    id: u64,
+    seed: u64,
+}
+
+impl Identity {
+    /// The environment variable that holds the ID, in decimal.
+    pub const ID_VAR: &'static str = "CLIFF_SEARCH_ID";
+
+    /// The environment variable that holds the seed, in decimal.
+    pub const SEED_VAR: &'static str = "CLIFF_SEED";
+
+    /// Identify a search by `id`, with the seed derived from it.
+    pub fn new(id: u64) -> Self {
+        Self::with_seed(id, SplitMix64::new(id).next_u64())
+    }
+
+    /// Identify a search by `id`, with a seed of your own.
+    pub fn with_seed(id: u64, seed: u64) -> Self {
+        Identity { id, seed }
+    }
+
+    /// Identify a search by a freshly generated ID, with the seed derived from it.
+    ///
+    /// The ID mixes the current time with the process ID and a per-process counter, so that
+    /// searches started at the same time, in the same process or not, still get different IDs.
+    ///
+    /// This method is only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn generate() -> Self {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        let mut rng = SplitMix64::new(now ^ (u64::from(std::process::id()) << 32));
+        let id = rng.next_u64() ^ COUNTER.fetch_add(1, Ordering::Relaxed);
+        Self::new(SplitMix64::new(id).next_u64())
+    }
+
+    /// Read the identity passed on by a parent process through [`env`](Identity::env).
+    ///
+    /// Returns `None` if either variable is unset or is not a number.
+    ///
+    /// This method is only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Option<Self> {
+        let var = |name| std::env::var(name).ok()?.parse().ok();
+        Some(Self::with_seed(var(Self::ID_VAR)?, var(Self::SEED_VAR)?))
+    }
+
+    /// Give the ID.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Give the seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Give a random source seeded with the seed, for example for [`Paced::jitter`].
+    ///
+    /// [`Paced::jitter`]: crate::Paced::jitter
+    pub fn rng(&self) -> SplitMix64 {
+        SplitMix64::new(self.seed)
+    }
+
+    /// Give the environment variables that pass this identity on to a child process, like a
+    /// benchmark run, in the form taken by `std::process::Command::envs`.
+    ///
+    /// The child can read them back with [`from_env`](Identity::from_env).
+    ///
+    /// ```rust
+    /// use cliff::Identity;
+    /// use std::process::Command;
+    ///
+    /// let id = Identity::new(42);
+    /// let mut benchmark = Command::new("./bench");
+    /// benchmark.envs(id.env().iter().map(|(k, v)| (k, v)));
+    /// assert_eq!(id.env()[0], ("CLIFF_SEARCH_ID", String::from("42")));
+    /// ```
+    ///
+    /// This method is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn env(&self) -> [(&'static str, alloc::string::String); 2] {
+        use alloc::string::ToString;
+        [
+            (Self::ID_VAR, self.id.to_string()),
+            (Self::SEED_VAR, self.seed.to_string()),
+        ]
+    }
+}
+
+/// A search that carries an [`Identity`].
+///
+/// The search's [`search_id`](CliffSearch::search_id) is the identity's ID. Its
+/// [`seed`](CliffSearch::seed) is that of the wrapped search if it has one, and the identity's
+/// seed otherwise. See [`Identity`] for an example.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Identified<S> {
+    inner: S,
+    identity: Identity,
+}
+
+impl<S> Identified<S>
+where
+    S: CliffSearch,
+{
+    /// Give `search` the identity `identity`.
+    pub fn new(search: S, identity: Identity) -> Self {
+        Identified {
+            inner: search,
+            identity,
+        }
+    }
+
+    /// Give the identity of the search.
+    pub fn identity(&self) -> Identity {
+        self.identity
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        self.inner.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> CliffSearch for Identified<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Identified::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Identified::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Identified::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Identified::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Identified::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Identified::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Identified::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.probes_left()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed().or(Some(self.identity.seed))
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        Some(self.identity.id)
+    }
+}
+
+impl<S> Refine for Identified<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for Identified<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<S> Describe for Identified<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "identified"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("search_id", &self.identity.id);
+        setting("seed", &self.identity.seed);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn identity() {
+    use super::{ExponentialCliffSearcher, Paced};
+    use core::time::Duration;
+
+    let id = Identity::new(7);
+    assert_eq!(id.id(), 7);
+    assert_eq!(id.seed(), SplitMix64::new(7).next_u64());
+    assert_ne!(Identity::new(8).seed(), id.seed());
+    assert_eq!(Identity::with_seed(7, 1).seed(), 1);
+    assert_eq!(id.rng().seed(), Some(id.seed()));
+
+    let search = Identified::new(ExponentialCliffSearcher::new(500), id);
+    assert_eq!(search.search_id(), Some(7));
+    assert_eq!(search.seed(), Some(id.seed()));
+    // a seed of the search's own takes precedence
+    let paced = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(1))
+        .jitter(0.1, SplitMix64::new(3));
+    let search = Identified::new(paced, id);
+    assert_eq!(search.seed(), Some(3));
+    // and the ID survives further wrapping
+    let search = super::Tracked::new(search);
+    assert_eq!(search.search_id(), Some(7));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn generate() {
+    let a = Identity::generate();
+    let b = Identity::generate();
+    assert_ne!(a.id(), b.id());
+    assert_eq!(Identity::new(a.id()), a);
+
+    std::env::set_var(Identity::ID_VAR, "12");
+    std::env::set_var(Identity::SEED_VAR, "34");
+    assert_eq!(Identity::from_env(), Some(Identity::with_seed(12, 34)));
+    std::env::set_var(Identity::SEED_VAR, "x");
+    assert_eq!(Identity::from_env(), None);
+    std::env::remove_var(Identity::ID_VAR);
+    std::env::remove_var(Identity::SEED_VAR);
+}
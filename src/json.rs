@@ -0,0 +1,293 @@
+// A minimal JSON reader, just enough for reading reports back in without pulling in any
+// dependencies, along with a writer for the one kind of value that needs escaping.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    // kept as written, so that integers round-trip exactly
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_usize(&self) -> Option<usize> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(vs) => Some(vs),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+}
+
+/// Parse `s` as a single JSON value, or give the byte offset at which it stopped making sense.
+pub(crate) fn parse(s: &str) -> Result<Value, usize> {
+    let mut p = Parser { s, pos: 0 };
+    let v = p.value()?;
+    p.whitespace();
+    if p.pos != s.len() {
+        return Err(p.pos);
+    }
+    Ok(v)
+}
+
+/// Write `s` to `out` as a JSON string, quoted and with everything JSON requires escaped.
+pub(crate) fn write_string<W: core::fmt::Write>(out: &mut W, s: &str) -> core::fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.pos).copied()
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, b: u8) -> Result<(), usize> {
+        self.whitespace();
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.pos)
+        }
+    }
+
+    fn literal(&mut self, lit: &str, v: Value) -> Result<Value, usize> {
+        if self.s[self.pos..].starts_with(lit) {
+            self.pos += lit.len();
+            Ok(v)
+        } else {
+            Err(self.pos)
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, usize> {
+        self.whitespace();
+        match self.peek() {
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                self.whitespace();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Value::Array(items));
+                        }
+                        _ => return Err(self.pos),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                self.whitespace();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(fields));
+                }
+                loop {
+                    self.whitespace();
+                    let key = self.string()?;
+                    self.eat(b':')?;
+                    let value = self.value()?;
+                    fields.push((key, value));
+                    self.whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Value::Object(fields));
+                        }
+                        _ => return Err(self.pos),
+                    }
+                }
+            }
+            Some(b'-') | Some(b'0'..=b'9') => {
+                let start = self.pos;
+                while let Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e') | Some(b'E')
+                | Some(b'0'..=b'9') = self.peek()
+                {
+                    self.pos += 1;
+                }
+                Ok(Value::Number(String::from(&self.s[start..self.pos])))
+            }
+            _ => Err(self.pos),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, usize> {
+        let digits = self.s.get(self.pos..self.pos + 4).ok_or(self.pos)?;
+        let v = u32::from_str_radix(digits, 16).map_err(|_| self.pos)?;
+        self.pos += 4;
+        Ok(v)
+    }
+
+    fn string(&mut self) -> Result<String, usize> {
+        if self.peek() != Some(b'"') {
+            return Err(self.pos);
+        }
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let rest = &self.s[self.pos..];
+            let c = rest.chars().next().ok_or(self.pos)?;
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            let at = self.pos;
+                            let mut cp = self.hex4()?;
+                            if (0xD800..0xDC00).contains(&cp) {
+                                // a surrogate pair
+                                if !self.s[self.pos..].starts_with("\\u") {
+                                    return Err(self.pos);
+                                }
+                                self.pos += 2;
+                                let lo = self.hex4()?;
+                                if !(0xDC00..0xE000).contains(&lo) {
+                                    return Err(self.pos);
+                                }
+                                cp = 0x10000 + ((cp - 0xD800) << 10) + (lo - 0xDC00);
+                            }
+                            out.push(core::char::from_u32(cp).ok_or(at)?);
+                            continue;
+                        }
+                        _ => return Err(self.pos),
+                    };
+                    self.pos += 1;
+                    out.push(escaped);
+                }
+                c if (c as u32) < 0x20 => return Err(self.pos - 1),
+                c => out.push(c),
+            }
+        }
+    }
+}
+
+#[test]
+fn roundtrip() {
+    let v = parse(r#" {"a": [1, -2.5e3, true, null], "b\né😀": {}} "#).unwrap();
+    assert_eq!(
+        v.get("a"),
+        Some(&Value::Array(alloc::vec![
+            Value::Number("1".into()),
+            Value::Number("-2.5e3".into()),
+            Value::Bool(true),
+            Value::Null,
+        ]))
+    );
+    assert_eq!(v.get("b\né😀"), Some(&Value::Object(Vec::new())));
+
+    assert_eq!(
+        parse(r#""b\né😀\"\u0001\ud83d\ude00""#),
+        Ok(Value::String("b\né😀\"\u{1}😀".into()))
+    );
+
+    assert_eq!(parse("[1,]"), Err(3));
+    assert_eq!(parse("{} x"), Err(3));
+    assert_eq!(parse(r#""\ud83d""#), Err(7));
+}
+
+#[test]
+fn write_roundtrip() {
+    let s = "p99 \"14ms\" > 10ms\n\\\u{1}é";
+    let mut json = String::new();
+    write_string(&mut json, s).unwrap();
+    assert_eq!(json, r#""p99 \"14ms\" > 10ms\n\\\u0001é""#);
+    assert_eq!(parse(&json), Ok(Value::String(s.into())));
+}
@@ -0,0 +1,161 @@
+use super::{CliffSearch, SearchValue};
+
+/// The outcome of a benchmark run, as decided by a [`Judge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Verdict {
+    /// The system kept up.
+    Sustained,
+    /// The system did not keep up.
+    Overloaded,
+    /// The run was too close to call, and should be repeated.
+    Inconclusive,
+    /// The system kept up, but only just.
+    ///
+    /// See [`CliffSearch::marginal`].
+    Marginal,
+}
+
+impl Verdict {
+    /// Report this outcome to `search` for the load it last yielded.
+    ///
+    /// An [`Inconclusive`](Verdict::Inconclusive) verdict makes the search yield the same load
+    /// again.
+    pub fn apply<V, S>(self, search: &mut S)
+    where
+        V: SearchValue,
+        S: CliffSearch<V> + ?Sized,
+    {
+        match self {
+            Verdict::Sustained => {}
+            Verdict::Overloaded => search.overloaded(),
+            Verdict::Inconclusive => search.inconclusive(),
+            Verdict::Marginal => search.marginal(),
+        }
+    }
+}
+
+/// Decides whether a benchmark run met a service-level objective, with a margin of error.
+///
+/// The judged metric is one where lower is better, like a latency percentile. Without margins, a
+/// run is overloaded if its metric exceeds the SLA, and sustained otherwise. Noisy metrics tend
+/// to fall on either side of the SLA by chance when the load is close to the cliff, though, which
+/// sends the search the wrong way. With margins, a run is only overloaded if its metric exceeds
+/// the SLA by more than [`overload_margin`](Judge::overload_margin), and only sustained if it
+/// lies below the SLA by at least [`sustain_margin`](Judge::sustain_margin). Runs in between are
+/// [inconclusive](Verdict::Inconclusive), and are repeated. A load is repeated for as long as its
+/// runs land in between, so the margins should be about as wide as the run-to-run noise of the
+/// metric, and no wider.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Judge, Verdict};
+///
+/// // p99 latency must stay below 100ms, give or take 10%
+/// let mut judge = Judge::at_most(100.0);
+/// judge.overload_margin(0.1).sustain_margin(0.1);
+/// assert_eq!(judge.judge(85.0), Verdict::Sustained);
+/// assert_eq!(judge.judge(105.0), Verdict::Inconclusive);
+/// assert_eq!(judge.judge(115.0), Verdict::Overloaded);
+///
+/// let mut load = ExponentialCliffSearcher::new(500);
+/// # let p99 = |load: usize| if load > 1600 { 150.0 } else { 50.0 };
+/// while let Some(l) = load.next() {
+///     judge.judge(p99(l)).apply(&mut load);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Judge {
+    sla: f64,
+    overload_margin: f64,
+    sustain_margin: f64,
+}
+
+impl Judge {
+    /// Judge runs against an SLA that the metric must not exceed.
+    pub fn at_most(sla: f64) -> Self {
+        Judge {
+            sla,
+            overload_margin: 0.0,
+            sustain_margin: 0.0,
+        }
+    }
+
+    /// Only count a run as overloaded if its metric exceeds the SLA by more than this fraction of
+    /// it.
+    ///
+    /// Defaults to `0.0`.
+    pub fn overload_margin(&mut self, fraction: f64) -> &mut Self {
+        self.overload_margin = fraction;
+        self
+    }
+
+    /// Only count a run as sustained if its metric lies below the SLA by at least this fraction
+    /// of it.
+    ///
+    /// Defaults to `0.0`.
+    pub fn sustain_margin(&mut self, fraction: f64) -> &mut Self {
+        self.sustain_margin = fraction;
+        self
+    }
+
+    /// Decide the outcome of a run whose metric came out as `metric`.
+    pub fn judge(&self, metric: f64) -> Verdict {
+        if metric > self.sla * (1.0 + self.overload_margin) {
+            Verdict::Overloaded
+        } else if metric <= self.sla * (1.0 - self.sustain_margin) {
+            Verdict::Sustained
+        } else {
+            Verdict::Inconclusive
+        }
+    }
+}
+
+#[test]
+fn margins() {
+    let judge = Judge::at_most(100.0);
+    assert_eq!(judge.judge(100.0), Verdict::Sustained);
+    assert_eq!(judge.judge(100.5), Verdict::Overloaded);
+
+    let mut judge = Judge::at_most(100.0);
+    judge.overload_margin(0.2).sustain_margin(0.05);
+    assert_eq!(judge.judge(95.0), Verdict::Sustained);
+    assert_eq!(judge.judge(96.0), Verdict::Inconclusive);
+    assert_eq!(judge.judge(120.0), Verdict::Inconclusive);
+    assert_eq!(judge.judge(121.0), Verdict::Overloaded);
+}
+
+#[test]
+fn reprobe() {
+    use super::ExponentialCliffSearcher;
+
+    let mut judge = Judge::at_most(100.0);
+    judge.overload_margin(0.1).sustain_margin(0.1);
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    judge.judge(50.0).apply(&mut scale);
+    assert_eq!(scale.next(), Some(1000));
+    judge.judge(105.0).apply(&mut scale);
+    // the gray zone is run again
+    assert_eq!(scale.next(), Some(1000));
+    judge.judge(112.0).apply(&mut scale);
+    assert_eq!(scale.next(), Some(750));
+    assert_eq!(scale.estimate(), 500..1000);
+}
+
+#[test]
+fn report() {
+    use super::{CliffSearch, ExponentialCliffSearcher};
+
+    let mut scale = ExponentialCliffSearcher::until(500, 100);
+    assert_eq!(scale.next(), Some(500));
+    scale.report(Verdict::Sustained);
+    assert_eq!(scale.next(), Some(1000));
+    scale.report(Verdict::Inconclusive);
+    assert_eq!(scale.next(), Some(1000));
+    scale.report(Verdict::Marginal);
+    assert_eq!(scale.next(), Some(1100));
+    scale.report(Verdict::Overloaded);
+    // the cliff was right above the marginal load, so the estimate is already narrow enough
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1000..1100);
+}
@@ -12,16 +12,71 @@
 //! a binary search between the upper and lower bounds, tightening the range until you reach the
 //! fidelity you want.
 //!
-//! If you instead want to search for the _minimum_ for a given parameter, use
-//! [`BinaryMinSearcher`]. It performs a binary search for a parameter between `0` and the starting
-//! point you give. No exponential phase is needed for the min searchers, since `0` already bounds
-//! the minimum.
+//! Every search implements [`CliffSearch`]: it is an iterator over the loads to benchmark, and is
+//! told through [`CliffSearch::overloaded`] when the system did not keep up. Searches are
+//! `no_std`, `Send`, and `Sync`, and can be picked at runtime as a `dyn CliffSearch`. Most search
+//! over `usize` loads by default, but are generic over any [`SearchValue`] (like `u64` or `f64`).
+//! Wrappers add behavior to any search, and can be stacked.
 //!
-//! So that you can easily support manual override, the crate also provides [`LoadIterator`], which
-//! implements the same interface ([`CliffSearch`]) over a pre-defined list of loads. It simply
-//! stops iteration when the test runner indicates that the system is no longer keeping up through
-//! [`CliffSearch::overloaded`]. To dynamically switch between these depending on user choices, use
-//! `dyn CliffSearch`.
+//! # Overview
+//!
+//! **Searches**
+//!
+//! - [`ExponentialCliffSearcher`] finds the maximum load, as described above.
+//! - [`BinaryMinSearcher`] finds the minimum of a parameter below a starting point, and
+//!   [`PercentSearcher`] is a preset of it for percentages.
+//! - [`OrdinalSearcher`] searches an ordered list of named tiers, like instance sizes.
+//! - [`MinMax`] finds both the minimum and the maximum of a parameter, sharing runs between them.
+//! - [`LoadIterator`] walks a pre-defined list of loads, for manual overrides.
+//! - [`SoakSearcher`] finds how long a system holds up under a fixed load.
+//! - [`MixSearcher`] finds the largest share of a second workload in a mix of two.
+//! - [`ConcurrencySearcher`] finds the concurrency of a closed-loop benchmark beyond which adding
+//!   clients is no longer worth it.
+//! - [`HillClimbSearcher`] climbs, optionally at random, towards the best value of a parameter
+//!   with no clean cliff.
+//! - [`BayesianSearcher`] bisects a posterior over the cliff's location, for expensive and noisy
+//!   runs.
+//!
+//! **Wrappers**
+//!
+//! - Precision: [`TwoStage`] and [`Refine`] refine a coarse estimate later, and
+//!   [`CliffSearch::with_domain`] keeps a search within the valid values of its parameter.
+//! - Noise: [`RetryingSearcher`], [`SubRuns`], [`Coalesced`], [`Canary`], and [`Smoke`], and
+//!   [`Smoothing`] for the searches that average a metric.
+//! - Pacing: [`Paced`] suggests recovery time between probes, and [`Escalation`] lengthens runs
+//!   as the estimate narrows.
+//! - Records: [`Tracked`], [`Recorded`], [`RecordingSearcher`], [`Streamed`], [`Plotted`], and
+//!   [`Identified`] with an [`Identity`].
+//! - Safety: [`Checked`] catches loads that were never judged.
+//!
+//! **Running searches**
+//!
+//! - [`CliffSearch::run_to_completion`], [`CliffSearch::run_cancellable`] with a [`Cancel`]
+//!   signal, and [`CliffSearch::drive_once`] with a [`Judge`] that gives a [`Verdict`].
+//! - The [`coordinator`] module runs a search across a fleet of load generators, and a
+//!   [`SearchSet`] shares one rig between several searches according to a [`Policy`].
+//! - [`Confirm`] checks with two runs that the cliff is still where a report found it.
+//! - The [`boxed`] module has shorthands for picking a search at runtime.
+//! - The `try_` methods, like [`ExponentialCliffSearcher::try_next`], return an [`Error`] rather
+//!   than panic.
+//!
+//! **Results**
+//!
+//! - A [`Report`] sums up a search, including its [`Status`] and, through [`Describe`], its
+//!   configuration.
+//! - The [`history`] module detects regressions across reports, and the [`campaign`] module
+//!   bundles related reports into a single artifact.
+//! - [`RandomSource`] makes randomized searches reproducible with a fixed seed.
+//! - The [`testing`] module and [`assert_cliff_within!`] test code against simulated systems.
+//!
+//! # Features
+//!
+//! - `alloc` enables the searches, wrappers, and helpers that need to allocate, like
+//!   [`BayesianSearcher`], [`Coalesced`], [`SearchSet`], and JSON reports.
+//! - `std` (which implies `alloc`) enables those that need the standard library, like
+//!   [`Streamed`], the [`coordinator`], and [`history`] modules.
+//! - `serde` implements `Serialize` and `Deserialize` for the state of [suspended](Suspend)
+//!   searches, so that they can be checkpointed in any serde format.
 //!
 //!   [exponential search]: https://en.wikipedia.org/wiki/Exponential_search
 //!
@@ -43,6 +98,24 @@
 //! println!("maximum supported load is between {} and {}", supported.start, supported.end);
 //! ```
 //!
+//! Or, equivalently, but without the risk of forgetting to report overloads:
+//!
+//! ```rust
+//! use cliff::{CliffSearch, ExponentialCliffSearcher};
+//! # let benchmark = |load: usize| -> bool { load > 12345 };
+//!
+//! let report = ExponentialCliffSearcher::new(500).run_to_completion(benchmark);
+//! println!("maximum supported load is between {} and {}", report.estimate.start, report.estimate.end);
+//! ```
+//!
+//! And if all you need is the estimate, [`search`] does the same in one line, and
+//! [`search_with`] does so for any other search:
+//!
+//! ```rust
+//! # let benchmark = |load: usize| -> bool { load > 12345 };
+//! let estimate = cliff::search(500, benchmark);
+//! ```
+//!
 //! Stepping through the search bit by bit:
 //!
 //! ```rust
@@ -104,25 +177,954 @@
 //! let supported = loads.estimate();
 //! println!("maximum supported load is between {} and {}", supported.start, supported.end);
 //! ```
+//!
+//! # Output formats
+//!
+//! Everything this crate writes out for other tools to read, like [`Report::to_json`],
+//! [`campaign::Campaign::to_json`], and the CSV and JSON lines of [`Streamed`], writes numbers the
+//! same way, and this is kept stable across releases: as plain ASCII decimal digits, with `.` as
+//! the decimal separator if there is a fractional part, without thousands separators, and never
+//! in scientific notation. None of it depends on the locale of the machine it runs on. Loads and
+//! counts are always written as integers. For fractional values of your own, like latency
+//! percentiles in a campaign's metadata, [`Fixed`] gives the same guarantees at a fixed
+//! precision.
 #![warn(missing_docs, missing_debug_implementations, rust_2018_idioms)]
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+mod bayes;
+
 mod binmin;
+#[cfg(feature = "alloc")]
+pub mod boxed;
+#[cfg(feature = "alloc")]
+pub mod campaign;
+mod cancel;
+mod canary;
+mod checked;
+#[cfg(feature = "alloc")]
+mod coalesced;
+mod concurrency;
+mod confirm;
+#[cfg(feature = "std")]
+pub mod coordinator;
+mod describe;
+mod domain;
+mod error;
+mod escalation;
+mod estimate;
 mod exponential;
+mod fixed;
+mod hill;
+#[cfg(feature = "std")]
+pub mod history;
+mod identity;
+#[cfg(feature = "alloc")]
+mod json;
+mod judge;
 mod linear;
+mod minmax;
+mod mix;
+mod multi;
+mod ordinal;
+mod paced;
+mod percent;
+#[cfg(feature = "alloc")]
+mod plotted;
+mod random;
+mod recorded;
+#[cfg(feature = "alloc")]
+mod recording;
+mod repeated;
+mod report;
+mod retrying;
+#[cfg(feature = "alloc")]
+mod searchset;
+mod smoke;
+mod smoothing;
+mod soak;
+#[cfg(feature = "std")]
+mod streamed;
+#[cfg(feature = "alloc")]
+mod subruns;
+pub mod testing;
+mod tracked;
+mod twostage;
+mod value;
 
-pub use binmin::BinaryMinSearcher;
-pub use exponential::ExponentialCliffSearcher;
-pub use linear::LoadIterator;
+#[cfg(feature = "alloc")]
+pub use bayes::BayesianSearcher;
+pub use binmin::{BinaryMinSearcher, SuspendedBinaryMin};
+pub use cancel::{Cancel, CancelFn};
+pub use canary::{Canary, Drift};
+pub use checked::{Checked, CheckedIter};
+#[cfg(feature = "alloc")]
+pub use coalesced::{Coalesced, Substitution};
+pub use concurrency::ConcurrencySearcher;
+pub use confirm::Confirm;
+pub use describe::{Describe, Layers};
+pub use domain::Domain;
+pub use error::Error;
+pub use escalation::Escalation;
+pub use estimate::{ConfirmationPolicy, Estimate, SummaryPolicy};
+pub use exponential::{
+    CoarseFidelity, ExponentialCliffSearcher, Fidelity, ProbeKind, ProbeRationale, ProbeRule,
+    Probes, SuspendedExponential,
+};
+pub use fixed::Fixed;
+pub use hill::HillClimbSearcher;
+pub use identity::{Identified, Identity};
+pub use judge::{Judge, Verdict};
+pub use linear::{FallibleLoads, LoadIterator};
+#[cfg(feature = "alloc")]
+pub use linear::SuspendedLoadIterator;
+pub use minmax::MinMax;
+pub use mix::{MixJudge, MixSearcher};
+pub use multi::MultiThresholdSearch;
+pub use ordinal::OrdinalSearcher;
+pub use paced::{Paced, SuspendedPaced};
+pub use percent::PercentSearcher;
+#[cfg(feature = "alloc")]
+pub use plotted::Plotted;
+pub use random::{RandomSource, SplitMix64};
+pub use recorded::{Recorded, Sample};
+#[cfg(feature = "alloc")]
+pub use recording::RecordingSearcher;
+pub use repeated::{BoundaryVotes, Repeated, Votes};
+#[cfg(feature = "alloc")]
+pub use report::Snapshot;
+pub use report::{Comparison, ParseError, Report, Status, WarmStart};
+pub use retrying::RetryingSearcher;
+#[cfg(feature = "alloc")]
+pub use searchset::{Policy, SearchSet};
+pub use smoke::Smoke;
+pub use smoothing::Smoothing;
+pub use soak::SoakSearcher;
+#[cfg(feature = "std")]
+pub use streamed::{Format, Streamed};
+#[cfg(feature = "alloc")]
+pub use subruns::{SubRun, SubRuns};
+pub use tracked::{BoundProvenance, Probe, SuspendedTracked, Tracked};
+pub use twostage::TwoStage;
+pub use value::SearchValue;
 
 /// A class of type that can estimate the performance cliff for a system.
-pub trait CliffSearch: Iterator<Item = usize> {
+///
+/// Loads are `usize` by default, but a search may yield any [`SearchValue`] instead, like an
+/// `f64` rate of requests per second. The methods that build a [`Report`], which records loads as
+/// `usize`, are only available for searches over `usize` loads.
+pub trait CliffSearch<V: SearchValue = usize>: Iterator<Item = V> {
     /// Indicate that the system could not keep up with the previous load factor yielded by
     /// [`Iterator::next`].
     ///
     /// This will affect what value the next call to [`Iterator::next`] yields.
     fn overloaded(&mut self);
 
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// `factor` says how badly the system missed its target, such as the measured latency divided
+    /// by the latency objective. Searchers that make use of it take a larger step away from the
+    /// overloaded load when `factor` is large, which converges in fewer runs on systems with a
+    /// sharp cliff. By default, this is equivalent to [`CliffSearch::overloaded`].
+    fn overloaded_by(&mut self, factor: f64) {
+        let _ = factor;
+        self.overloaded();
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// Searchers that support this yield the same load again so that it can be re-run. By default,
+    /// an inconclusive run is treated as [overloaded](CliffSearch::overloaded), since that never
+    /// makes a search claim support for a load the system did not demonstrably keep up with.
+    fn inconclusive(&mut self) {
+        self.overloaded();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// A marginal run counts as sustained, but tells the search that the cliff is probably right
+    /// above that load, like when a latency objective was met with hardly any room to spare.
+    /// Searchers that make use of it narrow the estimate down around that load next, rather than
+    /// splitting the whole estimate. By default, a marginal run is treated as sustained, and so
+    /// is ignored.
+    fn marginal(&mut self) {}
+
+    /// Report the outcome of the benchmark run for the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This is a single entry point for every outcome a run can have: [sustained], [marginal],
+    /// [overloaded], or [inconclusive]. It is the same as [`Verdict::apply`].
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher, Verdict};
+    ///
+    /// let mut load = ExponentialCliffSearcher::until(500, 100);
+    /// assert_eq!(load.next(), Some(500));
+    /// load.report(Verdict::Marginal);
+    /// assert_eq!(load.next(), Some(600));
+    /// load.report(Verdict::Overloaded);
+    /// assert_eq!(load.next(), None);
+    /// assert_eq!(load.estimate(), 500..600);
+    /// ```
+    ///
+    ///   [sustained]: Verdict::Sustained
+    ///   [marginal]: CliffSearch::marginal
+    ///   [overloaded]: CliffSearch::overloaded
+    ///   [inconclusive]: CliffSearch::inconclusive
+    fn report(&mut self, verdict: Verdict) {
+        verdict.apply(self);
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// If a load generator saturates before reaching the requested load, the achieved load is
+    /// already an upper bound on what the system can support. Searchers that make use of this
+    /// treat a probe whose achieved load is below the requested load as overloaded, and use the
+    /// achieved load rather than the requested one as the new upper bound. By default, this is
+    /// ignored, since not all searchers search over load.
+    fn achieved(&mut self, actual: V) {
+        let _ = actual;
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all, for example because the load generator cannot be configured for that exact load.
+    ///
+    /// Unlike [`CliffSearch::inconclusive`], this does not imply that a run happened. Searches
+    /// that can make do with a different load, like [`ExponentialCliffSearcher`], try a nearby
+    /// one instead, and [`LoadIterator`] moves on to the next entry. By default, the load is
+    /// treated as [inconclusive](CliffSearch::inconclusive).
+    fn skip_last(&mut self) {
+        self.inconclusive();
+    }
+
     /// Give the current estimate of the maximum load the system-under-test can support.
-    fn estimate(&self) -> core::ops::Range<usize>;
+    ///
+    /// The returned range converts into an [`Estimate`], which provides helpers for working with
+    /// it.
+    fn estimate(&self) -> core::ops::Range<V>;
+
+    /// Give the current estimate as the inclusive range of loads that may still be the highest
+    /// load the system-under-test can support.
+    ///
+    /// [`CliffSearch::estimate`] returns a half-open range whose `end` is the lowest load known
+    /// _not_ to be supported. This instead returns the range from the highest load known to be
+    /// supported up to and including the load just below that, which is what many consumers of
+    /// the estimate (like plots and capacity tables) want. See [`Estimate::to_inclusive`].
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// # while let Some(l) = load.next() { if l > 1600 { load.overloaded(); } }
+    /// assert_eq!(load.estimate(), 1500..1750);
+    /// assert_eq!(load.estimate_inclusive(), 1500..=1749);
+    /// ```
+    fn estimate_inclusive(&self) -> core::ops::RangeInclusive<usize>
+    where
+        V: Into<usize>,
+    {
+        Estimate::from(usize_range(self.estimate())).to_inclusive()
+    }
+
+    /// Returns `true` if the search has found a load the system-under-test could not keep up with.
+    ///
+    /// Until then, the maximum supported load could be anything above the lower bound of the
+    /// estimate, which may be a cue to, say, provision more load generators. This saves comparing
+    /// the end of [`CliffSearch::estimate`] against [`SearchValue::UNBOUNDED`] (`usize::MAX` for
+    /// `usize` loads), which stands for "no upper bound yet".
+    fn has_upper_bound(&self) -> bool {
+        self.estimate().end != V::UNBOUNDED
+    }
+
+    /// Recommend how much effort to spend on benchmarking the load just yielded by
+    /// [`Iterator::next`], as a multiplier of the effort needed to detect gross overload.
+    ///
+    /// Early probes, taken while the estimate is still wide, only need to run long enough to tell
+    /// whether the system falls over entirely, whereas probes taken when the estimate is narrow are
+    /// close to the cliff and need long, careful runs to get right. The recommendation is `1` while
+    /// the estimate has no upper bound or is at least as wide as its lower bound, and grows by one
+    /// every time the estimate halves in width relative to its lower bound. Harnesses will usually
+    /// map this to the duration of each benchmark run.
+    fn effort(&self) -> usize {
+        let estimate = self.estimate();
+        if estimate.end == V::UNBOUNDED {
+            return 1;
+        }
+        // count how often the width can double before it is wider than the lower bound
+        let mut width = value::max(estimate.start.width(estimate.end), V::ONE);
+        let mut effort = 1;
+        while let Some(doubled) = width.doubled().filter(|&d| d <= estimate.start) {
+            width = doubled;
+            effort += 1;
+        }
+        effort
+    }
+
+    /// Tell how the search ended, or is ending.
+    ///
+    /// Most searches always end with [`Status::Completed`]. Those that can tell that the
+    /// benchmark itself is likely broken, like an [`ExponentialCliffSearcher`] that hits its
+    /// [sanity cap](ExponentialCliffSearcher::sanity_cap) or a [`LoadIterator`] whose very first
+    /// entry overloads the system, say so here, so that a CI job can flag
+    /// the benchmark rather than publish a meaningless estimate.
+    fn status(&self) -> Status {
+        Status::Completed
+    }
+
+    /// Give the seed of the random source the search uses, if it uses one that was seeded.
+    ///
+    /// The searches in this crate are deterministic unless told otherwise, like [`Paced`] with
+    /// [`jitter`](Paced::jitter). Knowing the seed makes it possible to replay a search exactly.
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+
+    /// Give the ID of the search, if it was given one with [`Identified`].
+    ///
+    /// Searches include this in their [`Report`], so that the reports, logs, and retries of the
+    /// same search can be told apart from those of others.
+    fn search_id(&self) -> Option<u64> {
+        None
+    }
+
+    /// Estimate how many more benchmark runs the search will perform, counting the one for the
+    /// load just yielded by [`Iterator::next`].
+    ///
+    /// This is `None` if the search cannot tell, like while it is still looking for an upper
+    /// bound. The estimate assumes that every run narrows the estimate as little as it can, and
+    /// does not count runs that will have to be repeated because they were
+    /// [inconclusive](CliffSearch::inconclusive). It is used by [`Escalation`] to give the last
+    /// few runs of a search extra care.
+    fn probes_left(&self) -> Option<usize> {
+        None
+    }
+
+    /// Drive this search to completion, and report on the outcome.
+    ///
+    /// `judge` is called with every load the search yields, and should run the benchmark at that
+    /// load and return `false` if the system did not keep up. This saves writing the loop that
+    /// calls [`Iterator::next`] and [`CliffSearch::overloaded`] by hand, and in particular makes it
+    /// impossible to forget to report an overload (which would silently give an unbounded
+    /// estimate).
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher};
+    ///
+    /// let report = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1600);
+    /// assert_eq!(report.estimate, 1500..1750);
+    /// assert_eq!(report.probes, 5);
+    /// assert_eq!(report.overloaded, 2);
+    /// ```
+    fn run_to_completion<F>(mut self, mut judge: F) -> Report
+    where
+        Self: Sized,
+        V: Into<usize>,
+        F: FnMut(V) -> bool,
+    {
+        let mut report = Report::new();
+        while let Some(load) = self.next() {
+            report.settle(usize_range(self.estimate()));
+            let overloaded = !judge(load);
+            if overloaded {
+                self.overloaded();
+            }
+            report.probed(load.into(), overloaded);
+        }
+        report.settle(usize_range(self.estimate()));
+        report.status = self.status();
+        report.seed = self.seed();
+        report.search_id = self.search_id();
+        report
+    }
+
+    /// Like [`run_to_completion`](CliffSearch::run_to_completion), but stop early if `cancel` says
+    /// so.
+    ///
+    /// Cancellation is checked between benchmark runs. Once cancelled, the outcome of the last
+    /// run is still taken into account, and the report has [`Status::Cancelled`] along with the
+    /// estimate as it stood. See [`Cancel`] for an example.
+    fn run_cancellable<C, F>(mut self, cancel: C, mut judge: F) -> Report
+    where
+        Self: Sized,
+        V: Into<usize>,
+        C: Cancel,
+        F: FnMut(V) -> bool,
+    {
+        let mut report = Report::new();
+        while let Some(load) = self.next() {
+            report.settle(usize_range(self.estimate()));
+            if cancel.is_cancelled() {
+                report.status = Status::Cancelled;
+                report.seed = self.seed();
+                report.search_id = self.search_id();
+                return report;
+            }
+            let overloaded = !judge(load);
+            if overloaded {
+                self.overloaded();
+            }
+            report.probed(load.into(), overloaded);
+        }
+        report.settle(usize_range(self.estimate()));
+        report.status = self.status();
+        report.seed = self.seed();
+        report.search_id = self.search_id();
+        report
+    }
+
+    /// Like [`run_to_completion`](CliffSearch::run_to_completion), but `judge` gives a full
+    /// [`Verdict`] for every run.
+    ///
+    /// Runs can then also be [inconclusive](Verdict::Inconclusive), in which case the search
+    /// yields the same load again. Inconclusive runs are counted in
+    /// [`Report::inconclusive`], so that the report shows everything the search cost.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher, Verdict};
+    ///
+    /// let mut first = true;
+    /// let report = ExponentialCliffSearcher::new(500).run_with_verdicts(|load| {
+    ///     if load == 2000 && std::mem::take(&mut first) {
+    ///         Verdict::Inconclusive
+    ///     } else if load > 1600 {
+    ///         Verdict::Overloaded
+    ///     } else {
+    ///         Verdict::Sustained
+    ///     }
+    /// });
+    /// assert_eq!(report.estimate, 1500..1750);
+    /// assert_eq!(report.sustained_probes(), 3);
+    /// assert_eq!(report.overloaded_probes(), 2);
+    /// assert_eq!(report.inconclusive_probes(), 1);
+    /// ```
+    fn run_with_verdicts<J>(mut self, mut judge: J) -> Report
+    where
+        Self: Sized,
+        V: Into<usize>,
+        J: FnMut(V) -> Verdict,
+    {
+        let mut report = Report::new();
+        while let Some(load) = self.next() {
+            report.settle(usize_range(self.estimate()));
+            let verdict = judge(load);
+            verdict.apply(&mut self);
+            match verdict {
+                Verdict::Inconclusive => report.inconclusive_probe(),
+                _ => report.probed(load.into(), verdict == Verdict::Overloaded),
+            }
+        }
+        report.settle(usize_range(self.estimate()));
+        report.status = self.status();
+        report.seed = self.seed();
+        report.search_id = self.search_id();
+        report
+    }
+
+    /// Run a single benchmark, and tell the search how it went.
+    ///
+    /// This pulls the next load from the search, calls `judge` with it, and reports the
+    /// [`Verdict`] it returns to the search with [`Verdict::apply`]. Returns the load that was
+    /// judged, or `None` if the search has ended. Driving a search this way, rather than calling
+    /// [`Iterator::next`] and [`CliffSearch::overloaded`] by hand, gives the same semantics for
+    /// every search and every stack of wrappers, and leaves no room to forget an outcome.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher, Verdict};
+    ///
+    /// let mut load = ExponentialCliffSearcher::new(500);
+    /// let judge = |load| if load > 1600 { Verdict::Overloaded } else { Verdict::Sustained };
+    /// assert_eq!(load.drive_once(judge), Some(500));
+    /// assert_eq!(load.drive_once(judge), Some(1000));
+    /// assert_eq!(load.drive_all(judge), 1500..1750);
+    /// assert_eq!(load.drive_once(judge), None);
+    /// ```
+    ///
+    /// A boxed search can be driven directly, since `Box<dyn CliffSearch>` is itself a
+    /// [`CliffSearch`]. So is `&mut dyn CliffSearch`, so a search behind a reference is driven with
+    /// `(&mut search).drive_once(judge)`.
+    fn drive_once<J>(&mut self, mut judge: J) -> Option<V>
+    where
+        Self: Sized,
+        J: FnMut(V) -> Verdict,
+    {
+        let load = self.next()?;
+        judge(load).apply(self);
+        Some(load)
+    }
+
+    /// Drive the search to completion with [`drive_once`](CliffSearch::drive_once), and give its
+    /// final estimate.
+    ///
+    /// Unlike [`run_to_completion`](CliffSearch::run_to_completion), this takes a full
+    /// [`Verdict`], so runs can also be [inconclusive](Verdict::Inconclusive), and the search is
+    /// borrowed rather than consumed.
+    fn drive_all<J>(&mut self, mut judge: J) -> core::ops::Range<V>
+    where
+        Self: Sized,
+        J: FnMut(V) -> Verdict,
+    {
+        while self.drive_once(&mut judge).is_some() {}
+        self.estimate()
+    }
+
+    /// Keep this search within `domain`, so that it never yields a load outside of it, and its
+    /// estimate is always clipped to it.
+    ///
+    /// See [`Domain`] for details. Since a domain is a range of `usize` values, this is only
+    /// available for searches over `usize` loads:
+    ///
+    /// ```compile_fail
+    /// use cliff::{CliffSearch, LoadIterator};
+    ///
+    /// let rates = LoadIterator::over(vec![0.5, 1.0, 2.0]).with_domain(1..10);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `domain` is empty.
+    fn with_domain(self, domain: core::ops::Range<usize>) -> Domain<Self>
+    where
+        Self: Sized,
+        Domain<Self>: CliffSearch,
+    {
+        Domain::new(self, domain)
+    }
+
+    /// Box this search up as a trait object that can be sent to another thread.
+    ///
+    /// All the searches in this crate are `Send` and `Sync` (as long as any types they are generic
+    /// over are), so this is a convenient way to pick a search strategy at runtime and then move it
+    /// into a benchmark-runner thread:
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher, LoadIterator};
+    /// # let user_list: Vec<usize> = Vec::new();
+    ///
+    /// let mut loads = if user_list.is_empty() {
+    ///     ExponentialCliffSearcher::new(500).boxed()
+    /// } else {
+    ///     LoadIterator::from(user_list).boxed()
+    /// };
+    /// std::thread::spawn(move || {
+    ///     while let Some(load) = loads.next() {
+    ///         # if load > 12345 { loads.overloaded(); }
+    ///         // ...
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// This method is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    fn boxed<'a>(self) -> alloc::boxed::Box<dyn CliffSearch<V> + Send + 'a>
+    where
+        Self: Sized + Send + 'a,
+    {
+        alloc::boxed::Box::new(self)
+    }
+}
+
+// forwards every method a search may override, so that wrappers and harnesses see the same
+// search through a reference or a box as they would without one
+macro_rules! forward_search {
+    () => {
+        fn overloaded(&mut self) {
+            (**self).overloaded()
+        }
+
+        fn overloaded_by(&mut self, factor: f64) {
+            (**self).overloaded_by(factor)
+        }
+
+        fn inconclusive(&mut self) {
+            (**self).inconclusive()
+        }
+
+        fn marginal(&mut self) {
+            (**self).marginal()
+        }
+
+        fn report(&mut self, verdict: Verdict) {
+            (**self).report(verdict)
+        }
+
+        fn achieved(&mut self, actual: V) {
+            (**self).achieved(actual)
+        }
+
+        fn skip_last(&mut self) {
+            (**self).skip_last()
+        }
+
+        fn estimate(&self) -> core::ops::Range<V> {
+            (**self).estimate()
+        }
+
+        fn has_upper_bound(&self) -> bool {
+            (**self).has_upper_bound()
+        }
+
+        fn effort(&self) -> usize {
+            (**self).effort()
+        }
+
+        fn status(&self) -> Status {
+            (**self).status()
+        }
+
+        fn seed(&self) -> Option<u64> {
+            (**self).seed()
+        }
+
+        fn search_id(&self) -> Option<u64> {
+            (**self).search_id()
+        }
+
+        fn probes_left(&self) -> Option<usize> {
+            (**self).probes_left()
+        }
+    };
+}
+
+impl<V, S> CliffSearch<V> for &mut S
+where
+    V: SearchValue,
+    S: CliffSearch<V> + ?Sized,
+{
+    forward_search!();
+}
+
+#[cfg(feature = "alloc")]
+impl<V, S> CliffSearch<V> for alloc::boxed::Box<S>
+where
+    V: SearchValue,
+    S: CliffSearch<V> + ?Sized,
+{
+    forward_search!();
+}
+
+/// A [`CliffSearch`] whose fidelity can be changed after the search has started.
+pub trait Refine: CliffSearch {
+    /// Change how precisely the search should determine its estimate.
+    ///
+    /// If the search had already ended, but its estimate is wider than `min_width`, the search
+    /// resumes, and the next call to [`Iterator::next`] yields a new probe.
+    fn refine(&mut self, min_width: usize);
+}
+
+/// A [`CliffSearch`] that can be suspended, and resumed later, possibly in another process.
+///
+/// The suspended state is plain data with public fields, so that it can be written out and read
+/// back in whatever format suits the benchmark harness. With the `serde` feature, it implements
+/// `Serialize` and `Deserialize`, so that it can be checkpointed to disk with any serde format.
+/// Wrappers include the state of the search they wrap. A [`LoadIterator`] over any finite list of
+/// loads can be suspended with [`LoadIterator::suspend_remaining`] (with the `alloc` feature).
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Suspend, Tracked};
+///
+/// let mut load = Tracked::new(ExponentialCliffSearcher::new(500));
+/// assert_eq!(load.next(), Some(500));
+/// assert_eq!(load.next(), Some(1000));
+/// load.overloaded();
+///
+/// // ... save `suspended` somewhere, and restart the process ...
+/// let suspended = load.suspend();
+///
+/// let mut load: Tracked<ExponentialCliffSearcher> = suspended.resume();
+/// assert_eq!(load.next(), Some(750));
+/// ```
+pub trait Suspend: CliffSearch + Sized {
+    /// Everything needed to continue the search later.
+    type Suspended;
+
+    /// Stop the search, and capture its state.
+    fn suspend(self) -> Self::Suspended;
+
+    /// Continue a search from the state captured by [`suspend`](Suspend::suspend).
+    fn resume(suspended: Self::Suspended) -> Self;
+}
+
+/// Find the maximum load the system can support, starting at `start`.
+///
+/// `judge` is called with every load of an [`ExponentialCliffSearcher`] starting at `start`, and
+/// should run the benchmark at that load and return `false` if the system did not keep up. The
+/// final estimate is returned.
+///
+/// ```rust
+/// assert_eq!(cliff::search(500, |load| load <= 1600), 1500..1750);
+/// ```
+pub fn search<F>(start: usize, judge: F) -> core::ops::Range<usize>
+where
+    F: FnMut(usize) -> bool,
+{
+    search_with(ExponentialCliffSearcher::new(start), judge)
+}
+
+/// Drive `search` to completion, and give its final estimate.
+///
+/// Like [`search`], but for any [`CliffSearch`], including stacks of wrappers. Use
+/// [`CliffSearch::run_to_completion`] instead if you also want a [`Report`] of how the search
+/// went, or [`CliffSearch::drive_all`] if runs can be inconclusive.
+///
+/// ```rust
+/// use cliff::BinaryMinSearcher;
+///
+/// // the system needs at least 300 MB of memory
+/// let estimate = cliff::search_with(BinaryMinSearcher::until(1024, 32), |mb| mb >= 300);
+/// assert!(estimate.start < 300 && estimate.end >= 300);
+/// ```
+pub fn search_with<V, S, F>(mut search: S, mut judge: F) -> core::ops::Range<V>
+where
+    V: SearchValue,
+    S: CliffSearch<V>,
+    F: FnMut(V) -> bool,
+{
+    while let Some(load) = search.next() {
+        if !judge(load) {
+            search.overloaded();
+        }
+    }
+    search.estimate()
+}
+
+// an estimate over loads that fit in a `usize`, as a `Report` records it
+fn usize_range<V: Into<usize>>(range: core::ops::Range<V>) -> core::ops::Range<usize> {
+    range.start.into()..range.end.into()
+}
+
+#[test]
+fn send_sync() {
+    fn is<T: Send + Sync>() {}
+    is::<BinaryMinSearcher>();
+    is::<Confirm>();
+    is::<Estimate>();
+    is::<ExponentialCliffSearcher>();
+    is::<HillClimbSearcher>();
+    is::<LoadIterator<core::slice::Iter<'static, usize>>>();
+    is::<MinMax<ExponentialCliffSearcher>>();
+    is::<OrdinalSearcher<'static, &'static str>>();
+    is::<Paced<ExponentialCliffSearcher>>();
+    is::<PercentSearcher>();
+    is::<Recorded<ExponentialCliffSearcher, 16>>();
+    is::<Tracked<ExponentialCliffSearcher>>();
+    is::<TwoStage<ExponentialCliffSearcher, fn(core::ops::Range<usize>) -> bool>>();
+    #[cfg(feature = "alloc")]
+    is::<BayesianSearcher>();
+    #[cfg(feature = "alloc")]
+    is::<alloc::boxed::Box<dyn CliffSearch + Send + Sync>>();
+}
+
+#[test]
+fn object_safe() {
+    // this fails to compile if a method is added to the trait that makes it not object safe
+    fn take(_: &mut dyn CliffSearch) {}
+    take(&mut ExponentialCliffSearcher::new(500));
+    take(&mut LoadIterator::from(&[1, 2, 3]));
+}
+
+#[test]
+fn drive_dyn() {
+    let judge = |load| if load > 1600 { Verdict::Overloaded } else { Verdict::Sustained };
+
+    // searches behind a reference or a box can be driven, and report, like any other
+    let mut scale = ExponentialCliffSearcher::new(500);
+    let mut search: &mut dyn CliffSearch = &mut scale;
+    assert_eq!((&mut search).drive_once(judge), Some(500));
+    assert_eq!((&mut search).drive_all(judge), 1500..1750);
+    assert_eq!(scale.estimate(), 1500..1750);
+
+    let search: &mut dyn CliffSearch = &mut ExponentialCliffSearcher::new(500);
+    let report = search.run_to_completion(|load| load <= 1600);
+    assert_eq!(report.estimate, 1500..1750);
+    assert_eq!(report.probes, 5);
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut search = ExponentialCliffSearcher::new(500).boxed();
+        assert_eq!(search.drive_once(judge), Some(500));
+        assert_eq!(search.drive_all(judge), 1500..1750);
+        let report = search.run_with_verdicts(judge);
+        assert_eq!(report.estimate, 1500..1750);
+        assert_eq!(report.probes, 0);
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn boxed() {
+    let mut scale = ExponentialCliffSearcher::new(500).boxed();
+    assert_eq!(scale.next(), Some(500));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 500..500);
+}
+
+#[test]
+fn effort() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.effort(), 1);
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.effort(), 1);
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.effort(), 1);
+    assert_eq!(scale.next(), Some(1750));
+    assert_eq!(scale.effort(), 2);
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.effort(), 3);
+
+    let mut scale = BinaryMinSearcher::until(1024, 8);
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(512));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(768));
+    assert_eq!(scale.effort(), 1);
+    assert_eq!(scale.next(), Some(640));
+    assert_eq!(scale.effort(), 2);
+}
+
+#[test]
+fn has_upper_bound() {
+    let mut scale = ExponentialCliffSearcher::new(500);
+    assert!(!CliffSearch::has_upper_bound(&scale));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert!(!scale.has_upper_bound());
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(750));
+    assert!(scale.has_upper_bound());
+
+    // a min search is always bounded by where it started
+    let scale = BinaryMinSearcher::until(1024, 8);
+    assert!(scale.has_upper_bound());
+}
+
+#[test]
+fn drive_through_wrappers() {
+    let mut flaky = true;
+    let mut judge = |load: usize| {
+        if load == 1000 && core::mem::replace(&mut flaky, false) {
+            Verdict::Inconclusive
+        } else if load > 1600 {
+            Verdict::Overloaded
+        } else {
+            Verdict::Sustained
+        }
+    };
+    let mut search = Tracked::new(Paced::new(
+        ExponentialCliffSearcher::new(500),
+        core::time::Duration::from_secs(1),
+    ));
+    assert_eq!(search.drive_once(&mut judge), Some(500));
+    assert_eq!(search.drive_once(&mut judge), Some(1000));
+    // the inconclusive run is repeated
+    assert_eq!(search.drive_once(&mut judge), Some(1000));
+    assert_eq!(search.drive_all(&mut judge), 1500..1750);
+    assert_eq!(search.bound_provenance().upper.map(|p| p.load), Some(1750));
+}
+
+#[test]
+fn search_fn() {
+    let mut runs = 0;
+    let estimate = search(500, |load| {
+        runs += 1;
+        load <= 1600
+    });
+    assert_eq!(estimate, 1500..1750);
+    assert_eq!(runs, 5);
+
+    // the same holds through wrappers
+    let estimate = search_with(Tracked::new(ExponentialCliffSearcher::new(500)), |load| {
+        load <= 1600
+    });
+    assert_eq!(estimate, 1500..1750);
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[test]
+fn serde_suspend() {
+    fn checkpoint<T>(state: T) -> T
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let json = serde_json::to_string(&state).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    let mut load = Tracked::new(Paced::new(
+        ExponentialCliffSearcher::new(500),
+        core::time::Duration::from_secs(1),
+    ));
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.next(), Some(1000));
+    assert_eq!(load.next(), Some(2000));
+    load.overloaded();
+    let suspended = load.suspend();
+    let mut load: Tracked<Paced<ExponentialCliffSearcher>> = checkpoint(suspended).resume();
+    assert_eq!(load.next(), Some(1500));
+    assert_eq!(load.next(), Some(1750));
+    load.overloaded();
+    assert_eq!(load.next(), None);
+    assert_eq!(load.estimate(), 1500..1750);
+
+    let mut limit = BinaryMinSearcher::until(512, 32);
+    assert_eq!(limit.next(), Some(512));
+    assert_eq!(limit.next(), Some(256));
+    let mut limit = checkpoint(limit.suspend()).resume();
+    assert_eq!(limit.next(), Some(128));
+
+    // a list search need not walk a `Vec` to be suspended
+    let mut load = LoadIterator::from((1..=4).map(|l| l * 1000));
+    assert_eq!(load.next(), Some(1000));
+    let mut load = checkpoint(load.suspend_remaining()).resume();
+    assert_eq!(load.next(), Some(2000));
+    assert_eq!(load.next(), Some(3000));
+    load.overloaded();
+    assert_eq!(load.next(), None);
+    assert_eq!(load.estimate(), 2000..3000);
+}
+
+#[test]
+fn generic_values() {
+    // the trait works the same for loads that are not `usize`
+    fn take(_: &mut dyn CliffSearch<f64>) {}
+    take(&mut ExponentialCliffSearcher::starting_at(500.0, 100.0));
+    take(&mut LoadIterator::over([0.5, 1.0].iter().copied()));
+
+    let estimate = search_with(ExponentialCliffSearcher::starting_at(500.0, 100.0), |load| {
+        load <= 1600.0
+    });
+    assert_eq!(estimate, 1562.5..1625.0);
+
+    let mut scale = ExponentialCliffSearcher::starting_at(500u64, 250);
+    assert!(!scale.has_upper_bound());
+    assert_eq!(scale.effort(), 1);
+    let judge = |load| {
+        if load > 1600 {
+            Verdict::Overloaded
+        } else {
+            Verdict::Sustained
+        }
+    };
+    assert_eq!(scale.drive_all(judge), 1500u64..1750);
+    assert!(scale.has_upper_bound());
+    assert_eq!(scale.effort(), 3);
+
+    let mut scale = BinaryMinSearcher::starting_at(1024u32, 8);
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(512));
+    Verdict::Overloaded.apply(&mut scale);
+    assert_eq!(scale.next(), Some(768));
 }
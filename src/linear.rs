@@ -1,54 +1,429 @@
-use super::CliffSearch;
+#[cfg(feature = "alloc")]
+use super::Suspend;
+use super::{CliffSearch, Describe, ExponentialCliffSearcher, SearchValue, Status};
 use core::borrow::Borrow;
 
 /// An iterator that determines the maximum supported load by walking an iterator until the system
 /// cannot keep up.
 ///
+/// Since the list is walked in order, the estimate is only as precise as the gap between the two
+/// entries around the cliff. With [`intermediate_probes`](LoadIterator::intermediate_probes), a
+/// few more evenly spaced loads are tried between those two entries once the cliff has been
+/// found.
+///
+/// If the system cannot keep up with even the first entry, the estimate is just `0..first`, and
+/// [`CliffSearch::status`] gives [`Status::NothingSustained`] so that the benchmark can be flagged
+/// as broken.
+///
+/// With the `alloc` feature, a search over a finite list of loads can be suspended with
+/// [`suspend_remaining`](LoadIterator::suspend_remaining), whatever iterator it walks, and resumes
+/// as one that walks a `Vec` of the loads that were still to come. A `LoadIterator` over a `Vec`
+/// also implements [`Suspend`](crate::Suspend).
+///
 /// See the [crate-level documentation](..) for details.
 #[derive(Debug, Clone)]
-pub struct LoadIterator<I> {
-    max_in: core::ops::Range<usize>,
-    last: Option<usize>,
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct LoadIterator<I, V = usize> {
+    max_in: core::ops::Range<V>,
+    last: Option<V>,
     overloaded: bool,
+    inconclusive: bool,
+    achieved: Option<V>,
+    done: bool,
+    iter: I,
+    between: usize,
+    // the bracket between the list entries around the cliff, and the next intermediate to try
+    filling: Option<(core::ops::Range<V>, usize)>,
+    neighbors: (Option<V>, Option<V>),
+    #[cfg(feature = "alloc")]
+    original: Option<alloc::vec::Vec<V>>,
+}
+
+#[cfg(feature = "alloc")]
+impl LoadIterator<alloc::vec::IntoIter<usize>> {
+    /// Walk the loads in `loads` from lowest to highest, trying each distinct load only once.
+    ///
+    /// Lists pasted from elsewhere often have duplicates or entries out of order, and walking such
+    /// a list as-is gives a wrong estimate without any indication that something is off. The list
+    /// as given is kept, and is available through
+    /// [`original_order`](LoadIterator::original_order). If it had to be reordered or had
+    /// duplicates, [`warn`](LoadIterator::warn) records it in the report of the search.
+    ///
+    /// This method is only available with the `alloc` feature.
+    ///
+    /// ```rust
+    /// use cliff::LoadIterator;
+    ///
+    /// let mut load = LoadIterator::from_sorted_dedup(&[2000, 1000, 4000, 2000]);
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// assert_eq!(load.next(), Some(4000));
+    /// assert_eq!(load.next(), None);
+    /// assert_eq!(load.original_order(), Some(&[2000, 1000, 4000, 2000][..]));
+    /// ```
+    pub fn from_sorted_dedup<J, T>(loads: J) -> Self
+    where
+        J: IntoIterator<Item = T>,
+        T: Borrow<usize>,
+    {
+        let original: alloc::vec::Vec<usize> = loads.into_iter().map(|l| *l.borrow()).collect();
+        let mut sorted = original.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        let mut it = LoadIterator::from(sorted);
+        it.original = Some(original);
+        it
+    }
+}
+
+/// A schedule of loads read from a source that may fail, like a file or a socket.
+///
+/// This yields the loads from the source until the source yields an error, and then holds on to
+/// that error and ends. See [`LoadIterator::fallible`].
+#[derive(Debug, Clone)]
+pub struct FallibleLoads<I, E> {
     iter: I,
+    error: Option<E>,
+}
+
+impl<I, E> Iterator for FallibleLoads<I, E>
+where
+    I: Iterator<Item = Result<usize, E>>,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None;
+        }
+        match self.iter.next()? {
+            Ok(load) => Some(load),
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
 }
 
-impl<I, T> CliffSearch for LoadIterator<I>
+impl<I, E> LoadIterator<FallibleLoads<I, E>>
+where
+    I: Iterator<Item = Result<usize, E>>,
+{
+    /// Walk the loads read from `source` in order, where reading any one of them may fail.
+    ///
+    /// The source is read one entry at a time, as the search gets to it, so a long schedule does
+    /// not have to be read in full and checked up front. If reading an entry fails, the search
+    /// ends with the estimate it has, just as if the schedule had ended there. Use
+    /// [`try_next`](LoadIterator::try_next) rather than [`Iterator::next`] to get the error.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, LoadIterator};
+    ///
+    /// let schedule = "1000\n2000\nfour thousand\n8000";
+    /// let mut load = LoadIterator::fallible(schedule.lines().map(|l| l.parse::<usize>()));
+    /// assert_eq!(load.try_next(), Ok(Some(1000)));
+    /// assert_eq!(load.try_next(), Ok(Some(2000)));
+    /// assert!(load.try_next().is_err());
+    /// assert_eq!(load.estimate(), 2000..usize::MAX);
+    /// assert_eq!(load.try_next(), Ok(None));
+    /// ```
+    pub fn fallible<J>(source: J) -> Self
+    where
+        J: IntoIterator<IntoIter = I>,
+    {
+        LoadIterator::from(FallibleLoads {
+            iter: source.into_iter(),
+            error: None,
+        })
+    }
+
+    /// Like [`Iterator::next`], but returns the error of the source if reading the next entry of
+    /// the schedule failed.
+    ///
+    /// The search then ends, and later calls return `Ok(None)`. If [`Iterator::next`] already
+    /// ended the search because of an error, the next call to this returns that error.
+    pub fn try_next(&mut self) -> Result<Option<usize>, E> {
+        let next = self.next();
+        match self.iter.error.take() {
+            Some(e) => Err(e),
+            None => Ok(next),
+        }
+    }
+}
+
+impl<I, V> LoadIterator<I, V>
+where
+    V: SearchValue,
+{
+    /// Walk the loads of any [`SearchValue`] type yielded by `loads` in order.
+    ///
+    /// This is [`LoadIterator::from`] for loads that are not `usize`, like an `f64` rate of
+    /// requests per second. Unlike `from`, the loads must be given by value rather than by
+    /// reference, so that their type is clear.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, LoadIterator};
+    ///
+    /// let mut load = LoadIterator::over([0.5, 1.0, 1.5, 2.0].iter().copied());
+    /// while let Some(l) = load.next() {
+    ///     if l > 1.2 {
+    ///         load.overloaded();
+    ///     }
+    /// }
+    /// assert_eq!(load.estimate(), 1.0..1.5);
+    /// ```
+    pub fn over<J>(loads: J) -> Self
+    where
+        J: IntoIterator<IntoIter = I, Item = V>,
+    {
+        Self::walking(loads.into_iter())
+    }
+
+    // a search over `iter`, whose items may be loads or references to them
+    fn walking(iter: I) -> Self {
+        LoadIterator {
+            max_in: V::ZERO..V::UNBOUNDED,
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+            achieved: None,
+            done: false,
+            iter,
+            between: 0,
+            filling: None,
+            neighbors: (None, None),
+            #[cfg(feature = "alloc")]
+            original: None,
+        }
+    }
+
+    /// Give the list of loads as it was given to
+    /// [`from_sorted_dedup`](LoadIterator::from_sorted_dedup), before it was sorted and
+    /// deduplicated.
+    ///
+    /// Returns `None` if the search was not made with `from_sorted_dedup`.
+    ///
+    /// This method is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn original_order(&self) -> Option<&[V]> {
+        self.original.as_deref()
+    }
+
+    /// Add a warning to `report` with the [original order](LoadIterator::original_order) of the
+    /// list if [`from_sorted_dedup`](LoadIterator::from_sorted_dedup) had to sort it or remove
+    /// duplicates from it.
+    ///
+    /// This method is only available with the `alloc` feature.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, LoadIterator};
+    ///
+    /// let list = LoadIterator::from_sorted_dedup(&[2000, 1000, 4000, 2000]);
+    /// let mut report = list.clone().run_to_completion(|load| load <= 3000);
+    /// list.warn(&mut report);
+    /// assert_eq!(
+    ///     report.warnings,
+    ///     ["the list of loads was sorted and deduplicated from 2000, 1000, 4000, 2000"]
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn warn(&self, report: &mut crate::Report) {
+        use core::fmt::Write;
+
+        let original = match &self.original {
+            Some(original) => original,
+            None => return,
+        };
+        if original.windows(2).all(|w| w[0] < w[1]) {
+            // the list was already as it would be walked
+            return;
+        }
+        let mut warning =
+            alloc::string::String::from("the list of loads was sorted and deduplicated from ");
+        for (i, load) in original.iter().enumerate() {
+            if i != 0 {
+                warning.push_str(", ");
+            }
+            let _ = write!(warning, "{}", load);
+        }
+        report.warnings.push(warning);
+    }
+
+    /// After the first list entry the system cannot keep up with, try `probes` evenly spaced loads
+    /// between that entry and the one before it, from low to high.
+    ///
+    /// This narrows the estimate without a full binary search, and keeps the loads tried at
+    /// predictable points. Intermediate loads stop at the first one the system cannot keep up
+    /// with. Nothing is inserted if not even the first list entry was supported.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, LoadIterator};
+    ///
+    /// let mut load = LoadIterator::from(&[1000, 2000, 4000, 8000]);
+    /// load.intermediate_probes(3);
+    /// let mut tried = Vec::new();
+    /// while let Some(l) = load.next() {
+    ///     tried.push(l);
+    ///     if l > 5500 {
+    ///         load.overloaded();
+    ///     }
+    /// }
+    /// assert_eq!(tried, [1000, 2000, 4000, 8000, 5000, 6000]);
+    /// assert_eq!(load.estimate(), 5000..6000);
+    /// assert_eq!(load.neighbors(), (Some(4000), Some(8000)));
+    /// ```
+    pub fn intermediate_probes(&mut self, probes: usize) {
+        self.between = probes;
+    }
+
+    /// Give the last list entry the system kept up with, and the first it did not.
+    ///
+    /// Unlike [`CliffSearch::estimate`], this only considers the entries of the list itself, not
+    /// any [intermediate](LoadIterator::intermediate_probes) loads or achieved loads.
+    pub fn neighbors(&self) -> (Option<V>, Option<V>) {
+        self.neighbors
+    }
+
+    fn next_intermediate(&mut self) -> Option<V> {
+        let (range, i) = self.filling.as_mut()?;
+        let width = range.end - range.start;
+        while *i <= self.between {
+            let at = range.start + width.scaled(*i, self.between + 1);
+            *i += 1;
+            if at >= self.max_in.end {
+                break;
+            }
+            if at > self.max_in.start {
+                return Some(at);
+            }
+        }
+        None
+    }
+}
+
+impl<I> LoadIterator<I> {
+    /// Hand the estimate over to an [`ExponentialCliffSearcher`] that narrows it down to within
+    /// `min_width`.
+    ///
+    /// This is meant for once the list has been walked, and is the same as passing the estimate
+    /// to [`ExponentialCliffSearcher::within`]. The outcome of a pending load that has not been
+    /// taken into account by a call to [`Iterator::next`] is lost.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, LoadIterator};
+    ///
+    /// let mut list = LoadIterator::from(&[1000, 2000, 4000]);
+    /// # while let Some(l) = list.next() { if l > 2500 { list.overloaded(); } }
+    /// let mut load = list.into_exponential(500);
+    /// assert_eq!(load.estimate(), 2000..4000);
+    /// assert_eq!(load.next(), Some(3000));
+    /// ```
+    pub fn into_exponential(self, min_width: usize) -> ExponentialCliffSearcher {
+        ExponentialCliffSearcher::within(self.max_in, min_width)
+    }
+}
+
+impl<I, T, V> CliffSearch<V> for LoadIterator<I, V>
 where
     I: Iterator<Item = T>,
-    T: Borrow<usize>,
+    T: Borrow<V>,
+    V: SearchValue,
 {
     fn overloaded(&mut self) {
         self.overloaded = true;
     }
 
-    fn estimate(&self) -> core::ops::Range<usize> {
+    fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
+    fn achieved(&mut self, actual: V) {
+        self.achieved = Some(actual);
+    }
+
+    fn skip_last(&mut self) {
+        // forget the entry was ever tried, and move on to the next
+        if self.last.take().is_some() {
+            self.overloaded = false;
+            self.inconclusive = false;
+            self.achieved = None;
+        }
+    }
+
+    fn estimate(&self) -> core::ops::Range<V> {
         self.max_in.clone()
     }
+
+    fn status(&self) -> Status {
+        // the estimate is then just `0..first`, which says nothing about the system
+        if let (None, Some(_)) = self.neighbors {
+            Status::NothingSustained
+        } else {
+            Status::Completed
+        }
+    }
 }
 
-impl<I, T> Iterator for LoadIterator<I>
+impl<I, T, V> Iterator for LoadIterator<I, V>
 where
     I: Iterator<Item = T>,
-    T: Borrow<usize>,
+    T: Borrow<V>,
+    V: SearchValue,
 {
-    type Item = usize;
+    type Item = V;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(ref mut last) = self.last {
-            if self.overloaded {
-                self.max_in.end = *last;
+        if self.done {
+            return None;
+        }
+
+        if let Some(mut last) = self.last.take() {
+            let requested = last;
+            if self.inconclusive {
+                // the last run told us nothing, so run it again
+                self.inconclusive = false;
+                self.overloaded = false;
+                self.achieved = None;
+                self.last = Some(last);
+                return self.last;
+            }
+
+            if let Some(achieved) = self.achieved.take().filter(|&a| a < last) {
+                // we couldn't even generate the load we tried, so we're overloaded
+                self.overloaded = true;
+                if achieved > self.max_in.start {
+                    last = achieved;
+                }
+            }
+            if core::mem::replace(&mut self.overloaded, false) {
+                self.max_in.end = last;
+                if self.filling.is_none() {
+                    self.neighbors.1 = Some(requested);
+                    if self.between > 0 && self.neighbors.0.is_some() {
+                        self.filling = Some((self.max_in.clone(), 1));
+                    }
+                } else {
+                    // no need to try the intermediates above one that failed
+                    self.filling = None;
+                }
             } else {
-                self.max_in.start = *last;
+                self.max_in.start = last;
+                if self.filling.is_none() {
+                    self.neighbors.0 = Some(requested);
+                }
             }
         }
 
-        if self.overloaded {
-            return None;
+        if self.neighbors.1.is_some() {
+            let next = self.next_intermediate();
+            self.last = next;
+            self.done = next.is_none();
+            return next;
         }
 
-        let next = *self.iter.next()?.borrow();
-        self.last = Some(next);
-        Some(next)
+        let next = self.iter.next().map(|n| *n.borrow());
+        self.last = next;
+        self.done = next.is_none();
+        next
     }
 }
 
@@ -58,16 +433,154 @@ where
     T: Borrow<usize>,
 {
     fn from(v: I) -> Self {
+        LoadIterator::walking(v.into_iter())
+    }
+}
+
+/// The state of a suspended [`LoadIterator`] over a list of loads.
+///
+/// The loads that are still to come are captured along with the rest of the search. See
+/// [`LoadIterator::suspend_remaining`] and [`Suspend`].
+///
+/// This type is only available with the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuspendedLoadIterator<V = usize> {
+    /// The lower bound of the estimate.
+    pub start: V,
+    /// The upper bound of the estimate, or [`SearchValue::UNBOUNDED`] if there is none yet.
+    pub end: V,
+    /// The probe whose outcome has yet to be taken into account.
+    pub pending: Option<V>,
+    /// Whether the pending probe was reported as overloaded.
+    pub overloaded: bool,
+    /// Whether the pending probe was reported as inconclusive.
+    pub inconclusive: bool,
+    /// The load achieved by the pending probe, if it was reported.
+    pub achieved: Option<V>,
+    /// Whether the search has ended.
+    pub done: bool,
+    /// The list entries that have yet to be tried.
+    pub remaining: alloc::vec::Vec<V>,
+    /// The number of [intermediate](LoadIterator::intermediate_probes) loads to try.
+    pub intermediate_probes: usize,
+    /// The bracket that intermediate loads are being tried in, if any.
+    pub filling: Option<core::ops::Range<V>>,
+    /// The index of the next intermediate load to try within `filling`.
+    pub next_intermediate: usize,
+    /// The last list entry the system kept up with.
+    pub lower_neighbor: Option<V>,
+    /// The first list entry the system did not keep up with.
+    pub upper_neighbor: Option<V>,
+    /// The list as originally given to
+    /// [`from_sorted_dedup`](LoadIterator::from_sorted_dedup), if it was.
+    pub original: Option<alloc::vec::Vec<V>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<V> SuspendedLoadIterator<V> {
+    /// Continue the suspended search.
+    pub fn resume(self) -> LoadIterator<alloc::vec::IntoIter<V>, V> {
+        let next_intermediate = self.next_intermediate;
         LoadIterator {
-            max_in: 0..usize::max_value(),
-            last: None,
-            overloaded: false,
-            iter: v.into_iter(),
+            max_in: self.start..self.end,
+            last: self.pending,
+            overloaded: self.overloaded,
+            inconclusive: self.inconclusive,
+            achieved: self.achieved,
+            done: self.done,
+            iter: self.remaining.into_iter(),
+            between: self.intermediate_probes,
+            filling: self.filling.map(|range| (range, next_intermediate)),
+            neighbors: (self.lower_neighbor, self.upper_neighbor),
+            original: self.original,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, T, V> LoadIterator<I, V>
+where
+    I: Iterator<Item = T>,
+    T: Borrow<V>,
+    V: SearchValue,
+{
+    /// Suspend the search, so that it can be resumed later, possibly in another process.
+    ///
+    /// The loads that are still to come are collected along with the rest of the search, so this
+    /// must only be called on a search over a finite list of loads, and not, say, an unbounded
+    /// range. The search resumes as a `LoadIterator` over a `Vec` of those loads, which
+    /// implements [`Suspend`] for `usize` loads.
+    ///
+    /// This method is only available with the `alloc` feature.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, LoadIterator};
+    ///
+    /// let mut load = LoadIterator::from(&[1000, 2000, 4000, 8000]);
+    /// assert_eq!(load.next(), Some(1000));
+    /// assert_eq!(load.next(), Some(2000));
+    /// let suspended = load.suspend_remaining();
+    /// assert_eq!(suspended.remaining, [4000, 8000]);
+    ///
+    /// let mut load = suspended.resume();
+    /// assert_eq!(load.next(), Some(4000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), None);
+    /// assert_eq!(load.estimate(), 2000..4000);
+    /// ```
+    pub fn suspend_remaining(self) -> SuspendedLoadIterator<V> {
+        let (filling, next_intermediate) = match self.filling {
+            Some((range, i)) => (Some(range), i),
+            None => (None, 0),
+        };
+        SuspendedLoadIterator {
+            start: self.max_in.start,
+            end: self.max_in.end,
+            pending: self.last,
+            overloaded: self.overloaded,
+            inconclusive: self.inconclusive,
+            achieved: self.achieved,
+            done: self.done,
+            remaining: self.iter.map(|l| *l.borrow()).collect(),
+            intermediate_probes: self.between,
+            filling,
+            next_intermediate,
+            lower_neighbor: self.neighbors.0,
+            upper_neighbor: self.neighbors.1,
+            original: self.original,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Suspend for LoadIterator<alloc::vec::IntoIter<usize>> {
+    type Suspended = SuspendedLoadIterator;
+
+    fn suspend(self) -> Self::Suspended {
+        self.suspend_remaining()
+    }
+
+    fn resume(suspended: Self::Suspended) -> Self {
+        suspended.resume()
+    }
+}
+
+impl<I, V> Describe for LoadIterator<I, V> {
+    fn name(&self) -> &'static str {
+        "list"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        if self.between != 0 {
+            setting("between", &self.between);
         }
     }
 }
 
 #[test]
+#[allow(clippy::legacy_numeric_constants)]
 fn linear_nofail() {
     let mut scale = LoadIterator::from(&[1, 2, 3, 4]);
     assert_eq!(scale.next(), Some(1));
@@ -99,3 +612,226 @@ fn linear_fail() {
     scale.overloaded();
     assert_eq!(scale.next(), None);
 }
+
+#[test]
+fn linear_achieved() {
+    let mut scale = LoadIterator::from(&[1000, 2000, 3000, 4000]);
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(3000));
+    scale.achieved(2500);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 2000..2500);
+}
+
+#[test]
+fn linear_inconclusive() {
+    let mut scale = LoadIterator::from(&[1, 2, 3, 4]);
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), Some(2));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(3));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 2..3);
+
+    // inconclusive after the fact changes nothing
+    scale.inconclusive();
+    assert_eq!(scale.next(), None);
+}
+
+#[test]
+fn linear_intermediate() {
+    let mut scale = LoadIterator::from(&[1000, 2000, 4000]);
+    scale.intermediate_probes(3);
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.achieved(1800);
+    assert_eq!(scale.neighbors(), (Some(1000), None));
+    // the intermediates lie between the last good entry and the achieved load
+    assert_eq!(scale.next(), Some(1200));
+    assert_eq!(scale.next(), Some(1400));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(1400));
+    assert_eq!(scale.next(), Some(1600));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1600..1800);
+    assert_eq!(scale.neighbors(), (Some(1000), Some(2000)));
+
+    // nothing to fill below the first entry
+    let mut scale = LoadIterator::from(&[1000, 2000]);
+    scale.intermediate_probes(3);
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 0..1000);
+    assert_eq!(scale.neighbors(), (None, Some(1000)));
+
+    // intermediates that would round to the same load are only tried once
+    let mut scale = LoadIterator::from(&[1, 3]);
+    scale.intermediate_probes(5);
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), Some(3));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 2..3);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn linear_sorted_dedup() {
+    let mut scale = LoadIterator::from_sorted_dedup(alloc::vec![3, 1, 3, 2, 1]);
+    assert_eq!(scale.next(), Some(1));
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(3));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 2..3);
+    assert_eq!(scale.original_order(), Some(&[3, 1, 3, 2, 1][..]));
+
+    // the original order ends up in the report
+    let list = LoadIterator::from_sorted_dedup(alloc::vec![3, 1, 3, 2, 1]);
+    let mut report = list.clone().run_to_completion(|load| load <= 2);
+    list.warn(&mut report);
+    assert_eq!(report.estimate, 2..3);
+    assert_eq!(
+        report.warnings,
+        ["the list of loads was sorted and deduplicated from 3, 1, 3, 2, 1"]
+    );
+
+    // but only if it had to be changed
+    let list = LoadIterator::from_sorted_dedup(alloc::vec![1, 2, 3]);
+    let mut report = list.clone().run_to_completion(|load| load <= 2);
+    list.warn(&mut report);
+    assert!(report.warnings.is_empty());
+    let list = LoadIterator::from(&[3, 1, 2]);
+    let mut report = list.clone().run_to_completion(|load| load <= 2);
+    list.warn(&mut report);
+    assert!(report.warnings.is_empty());
+
+    assert_eq!(LoadIterator::from(&[1, 2]).original_order(), None);
+}
+
+#[test]
+fn linear_nothing_sustained() {
+    let report = LoadIterator::from(&[1000, 2000]).run_to_completion(|_| false);
+    assert_eq!(report.estimate, 0..1000);
+    assert_eq!(report.probes, 1);
+    assert_eq!(report.status, Status::NothingSustained);
+
+    // falling short of the first entry counts too
+    let mut scale = LoadIterator::from(&[1000, 2000]);
+    assert_eq!(scale.next(), Some(1000));
+    scale.achieved(600);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 0..600);
+    assert_eq!(scale.status(), Status::NothingSustained);
+
+    let report = LoadIterator::from(&[1000, 2000]).run_to_completion(|load| load < 2000);
+    assert_eq!(report.estimate, 1000..2000);
+    assert_eq!(report.status, Status::Completed);
+}
+
+#[test]
+fn linear_skip() {
+    let mut scale = LoadIterator::from(&[1000, 2000, 3000]);
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.skip_last();
+    assert_eq!(scale.next(), Some(3000));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1000..3000);
+}
+
+#[test]
+fn linear_into_exponential() {
+    // the list never found an upper bound, so the handover ramps up from the last entry
+    let mut scale = LoadIterator::from(&[100, 200]);
+    assert_eq!(scale.next(), Some(100));
+    assert_eq!(scale.next(), Some(200));
+    assert_eq!(scale.next(), None);
+    let mut scale = scale.into_exponential(50);
+    assert_eq!(scale.next(), Some(400));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(300));
+    assert_eq!(scale.next(), Some(350));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 300..350);
+}
+
+#[test]
+fn fallible() {
+    let source = [Ok(1000), Ok(2000), Err("bad entry"), Ok(8000)];
+    let mut load = LoadIterator::fallible(source.iter().copied());
+    assert_eq!(load.try_next(), Ok(Some(1000)));
+    assert_eq!(load.try_next(), Ok(Some(2000)));
+    load.inconclusive();
+    assert_eq!(load.try_next(), Ok(Some(2000)));
+    assert_eq!(load.try_next(), Err("bad entry"));
+    assert_eq!(load.try_next(), Ok(None));
+    assert_eq!(load.estimate(), 2000..usize::MAX);
+
+    // an error after the cliff is never read
+    let source = [Ok(1000), Ok(2000), Err("bad entry")];
+    let mut load = LoadIterator::fallible(source.iter().copied());
+    assert_eq!(load.try_next(), Ok(Some(1000)));
+    assert_eq!(load.try_next(), Ok(Some(2000)));
+    load.overloaded();
+    assert_eq!(load.try_next(), Ok(None));
+    assert_eq!(load.estimate(), 1000..2000);
+
+    // plain `next` just ends the search, but the error is kept
+    let source = [Ok(1000), Err("bad entry")];
+    let mut load = LoadIterator::fallible(source.iter().copied());
+    assert_eq!(load.next(), Some(1000));
+    assert_eq!(load.next(), None);
+    assert_eq!(load.try_next(), Err("bad entry"));
+    assert_eq!(load.try_next(), Ok(None));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn suspend() {
+    let mut scale = LoadIterator::from(alloc::vec![1000, 2000, 4000, 8000]);
+    scale.intermediate_probes(3);
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    assert_eq!(scale.next(), Some(8000));
+    scale.overloaded();
+    let mut scale = scale.suspend().resume();
+    assert_eq!(scale.next(), Some(5000));
+    assert_eq!(scale.next(), Some(6000));
+    // suspending again mid-fill picks up at the same intermediate
+    let mut scale = LoadIterator::resume(scale.suspend());
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 5000..6000);
+    assert_eq!(scale.neighbors(), (Some(4000), Some(8000)));
+
+    // any finite list of any type of load can be suspended
+    let mut scale = LoadIterator::over([0.5, 1.0, 1.5, 2.0].iter().copied());
+    assert_eq!(scale.next(), Some(0.5));
+    let suspended = scale.suspend_remaining();
+    assert_eq!(suspended.remaining, [1.0, 1.5, 2.0]);
+    let mut scale = suspended.resume();
+    assert_eq!(scale.next(), Some(1.0));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 0.5..1.0);
+}
+
+#[test]
+fn floats() {
+    let mut scale = LoadIterator::over([0.5, 1.0, 1.5, 2.0].iter().copied());
+    assert_eq!(scale.next(), Some(0.5));
+    assert_eq!(scale.next(), Some(1.0));
+    assert_eq!(scale.next(), Some(1.5));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1.0..1.5);
+}
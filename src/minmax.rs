@@ -0,0 +1,285 @@
+use super::{BinaryMinSearcher, CliffSearch, Describe};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Min,
+    Max,
+}
+
+/// A search for both the minimum viable and the maximum sustainable value of one parameter.
+///
+/// This interleaves a [`BinaryMinSearcher`] with a cliff search (like an
+/// [`ExponentialCliffSearcher`](crate::ExponentialCliffSearcher)), and shares the outcome of
+/// each benchmark run between the two. Whenever one search proposes a value whose outcome is
+/// already implied by earlier runs, that outcome is handed to it directly, and the value is not
+/// yielded again.
+///
+/// The two searches are assumed to bracket a single viable range: every value between two values
+/// that worked also works, a failure proposed by the minimum search means every smaller value
+/// fails too, and a failure proposed by the maximum search means every larger value does. For
+/// this to hold, both searches should start at a value that is expected to be viable, at which
+/// point their first probes coincide and only one run is needed. A value known not to work is
+/// reported as a failure to whichever search proposes it.
+///
+/// ```rust
+/// use cliff::{BinaryMinSearcher, ExponentialCliffSearcher, MinMax};
+///
+/// // connection pools between 12 and 80 connections work
+/// let mut pool = MinMax::new(
+///     BinaryMinSearcher::until(32, 4),
+///     ExponentialCliffSearcher::until(32, 8),
+/// );
+/// while let Some(size) = pool.next() {
+///     if size < 12 || size > 80 {
+///         pool.overloaded();
+///     }
+/// }
+/// assert_eq!(pool.min_estimate(), 8..12);
+/// assert_eq!(pool.max_estimate(), 80..88);
+/// assert_eq!(pool.reused(), 1);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct MinMax<S> {
+    min: BinaryMinSearcher,
+    max: S,
+    min_done: bool,
+    max_done: bool,
+    turn: Side,
+    last: Option<(Side, usize)>,
+    overloaded: bool,
+    inconclusive: bool,
+    worked: Option<(usize, usize)>,
+    too_small: Option<usize>,
+    too_large: Option<usize>,
+    reused: usize,
+}
+
+impl<S> MinMax<S>
+where
+    S: CliffSearch,
+{
+    /// Search for the minimum with `min` and the maximum with `max`, sharing benchmark runs
+    /// between the two.
+    pub fn new(min: BinaryMinSearcher, max: S) -> Self {
+        MinMax {
+            min,
+            max,
+            min_done: false,
+            max_done: false,
+            turn: Side::Min,
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+            worked: None,
+            too_small: None,
+            too_large: None,
+            reused: 0,
+        }
+    }
+
+    /// Indicate that the system did not work with the previous value yielded by
+    /// [`Iterator::next`].
+    ///
+    /// The failure is attributed to whichever search proposed that value.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+    }
+
+    /// Indicate that the benchmark run for the previous value yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same value again, so that it can be re-run.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
+    /// Give the current estimate of the minimum viable value.
+    pub fn min_estimate(&self) -> core::ops::Range<usize> {
+        self.min.estimate()
+    }
+
+    /// Give the current estimate of the maximum sustainable value.
+    pub fn max_estimate(&self) -> core::ops::Range<usize> {
+        self.max.estimate()
+    }
+
+    /// Give the number of probes that were answered from earlier runs rather than run again.
+    pub fn reused(&self) -> usize {
+        self.reused
+    }
+
+    /// Give back the minimum and maximum searches.
+    pub fn into_inner(self) -> (BinaryMinSearcher, S) {
+        (self.min, self.max)
+    }
+
+    // the outcome of running `load` for `side`, if earlier runs already tell us.
+    // `Some(true)` means it failed.
+    fn known(&self, load: usize) -> Option<bool> {
+        if let Some((lo, hi)) = self.worked {
+            if lo <= load && load <= hi {
+                return Some(false);
+            }
+        }
+        if self.too_small.map_or(false, |s| load <= s)
+            || self.too_large.map_or(false, |l| load >= l)
+        {
+            return Some(true);
+        }
+        None
+    }
+
+    fn fail(&mut self, side: Side) {
+        match side {
+            Side::Min => self.min.overloaded(),
+            Side::Max => self.max.overloaded(),
+        }
+    }
+}
+
+impl<S> Iterator for MinMax<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((side, load)) = self.last.take() {
+            if self.inconclusive {
+                match side {
+                    Side::Min => self.min.inconclusive(),
+                    Side::Max => self.max.inconclusive(),
+                }
+            } else if self.overloaded {
+                match side {
+                    Side::Min => {
+                        self.too_small = Some(self.too_small.map_or(load, |s| s.max(load)))
+                    }
+                    Side::Max => {
+                        self.too_large = Some(self.too_large.map_or(load, |l| l.min(load)))
+                    }
+                }
+                self.fail(side);
+            } else {
+                self.worked = Some(match self.worked {
+                    Some((lo, hi)) => (lo.min(load), hi.max(load)),
+                    None => (load, load),
+                });
+            }
+        }
+        self.overloaded = false;
+        self.inconclusive = false;
+
+        loop {
+            if self.min_done && self.max_done {
+                return None;
+            }
+
+            let side = match self.turn {
+                Side::Min if !self.min_done => Side::Min,
+                Side::Max if !self.max_done => Side::Max,
+                Side::Min => Side::Max,
+                Side::Max => Side::Min,
+            };
+            self.turn = match side {
+                Side::Min => Side::Max,
+                Side::Max => Side::Min,
+            };
+
+            let next = match side {
+                Side::Min => self.min.next(),
+                Side::Max => self.max.next(),
+            };
+            let load = match next {
+                Some(load) => load,
+                None => {
+                    match side {
+                        Side::Min => self.min_done = true,
+                        Side::Max => self.max_done = true,
+                    }
+                    continue;
+                }
+            };
+
+            match self.known(load) {
+                Some(failed) => {
+                    self.reused += 1;
+                    if failed {
+                        self.fail(side);
+                    }
+                }
+                None => {
+                    self.last = Some((side, load));
+                    return Some(load);
+                }
+            }
+        }
+    }
+}
+
+/// The min search is described by the settings of this layer, and the max search is the layer it
+/// wraps.
+impl<S> Describe for MinMax<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "min_max"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        self.min.settings(setting);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.max)
+    }
+}
+
+#[test]
+fn shared() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = MinMax::new(
+        BinaryMinSearcher::until(100, 25),
+        ExponentialCliffSearcher::until(100, 25),
+    );
+    // both start at 100, which only needs to run once
+    assert_eq!(scale.next(), Some(100));
+    assert_eq!(scale.next(), Some(50));
+    assert_eq!(scale.next(), Some(200));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(25));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(150));
+    assert_eq!(scale.next(), Some(175));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.min_estimate(), 25..50);
+    assert_eq!(scale.max_estimate(), 150..175);
+    assert_eq!(scale.reused(), 1);
+}
+
+#[test]
+fn inconclusive() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = MinMax::new(
+        BinaryMinSearcher::until(100, 50),
+        ExponentialCliffSearcher::until(100, 50),
+    );
+    assert_eq!(scale.next(), Some(100));
+    assert_eq!(scale.last, Some((Side::Min, 100)));
+    scale.inconclusive();
+    // the turn passes to the maximum search after every probe, so this 100 is its first probe,
+    // and the repeat the minimum search owes is still to come
+    assert_eq!(scale.next(), Some(100));
+    assert_eq!(scale.last, Some((Side::Max, 100)));
+    scale.overloaded();
+    // so 100 is too much for the maximum search, and since the minimum search's repeat of 100 is
+    // known to fail from that run, the minimum is above 100 without running it again
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.min_estimate(), 100..100);
+    assert_eq!(scale.max_estimate(), 100..100);
+    assert_eq!(scale.reused(), 1);
+}
@@ -0,0 +1,247 @@
+use super::{CliffSearch, Describe, ExponentialCliffSearcher, Judge, Refine, Verdict};
+
+/// An iterator that determines the largest share of a second workload that a system supports.
+///
+/// The probed value is the percentage of the offered load that comes from the second of two
+/// workloads, like the percentage of writes in a mix of reads and writes, in the domain `0..=100`.
+/// This answers questions like "what is the highest fraction of writes at which reads still meet
+/// their SLA?". The search bisects the domain down to a precision of a single percentage point.
+/// A mix of only the first workload (`0`) is taken to be supported, and is not probed. An
+/// estimate of `100..101` means that even the second workload on its own is supported.
+///
+/// [`MixJudge`] decides the outcome of each run from the metrics of both workloads.
+///
+/// ```rust
+/// use cliff::{Judge, MixJudge, MixSearcher};
+///
+/// // read p99 latency grows with the share of writes
+/// let read_p99 = |writes: usize| 2.0 + writes as f64 / 4.0;
+///
+/// let judge = MixJudge::first(Judge::at_most(10.0));
+/// let mut writes = MixSearcher::new();
+/// while let Some(pct) = writes.next() {
+///     judge.judge(pct, read_p99(pct), 1.0).apply(&mut writes);
+/// }
+/// assert_eq!(writes.estimate(), 32..33);
+/// ```
+///
+/// See also the [crate-level documentation](..) for details.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct MixSearcher {
+    inner: ExponentialCliffSearcher,
+}
+
+impl Default for MixSearcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MixSearcher {
+    /// Search for the largest share of the second workload to within a percentage point.
+    pub fn new() -> Self {
+        Self::until(1)
+    }
+
+    /// Search for the largest share of the second workload to within `min_width` percentage
+    /// points.
+    pub fn until(min_width: usize) -> Self {
+        MixSearcher {
+            inner: ExponentialCliffSearcher::within(0..101, min_width),
+        }
+    }
+
+    /// Split `total` load into the loads of the first and the second workload for a run with
+    /// `pct` percent of the second workload.
+    ///
+    /// The second workload's share is rounded down, and values of `pct` above 100 are clamped to
+    /// 100.
+    ///
+    /// ```rust
+    /// use cliff::MixSearcher;
+    ///
+    /// assert_eq!(MixSearcher::split(25, 1000), (750, 250));
+    /// ```
+    pub fn split(pct: usize, total: usize) -> (usize, usize) {
+        let pct = pct.min(100);
+        // avoid overflowing on large loads by splitting off the whole hundreds first
+        let second = total / 100 * pct + total % 100 * pct / 100;
+        (total - second, second)
+    }
+
+    /// Indicate that the system could not keep up with the previous mix yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the benchmark run for the previous mix yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same mix again, so that it can be re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inner.inconclusive();
+    }
+
+    /// Give the current estimate of the largest percentage of the second workload the
+    /// system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Change the number of percentage points to which the largest share should be determined,
+    /// and resume the search if it had ended with a wider estimate than that.
+    ///
+    /// This provides [`Refine::refine`] without having to `use` the trait.
+    pub fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl CliffSearch for MixSearcher {
+    fn overloaded(&mut self) {
+        MixSearcher::overloaded(self)
+    }
+
+    fn inconclusive(&mut self) {
+        MixSearcher::inconclusive(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        MixSearcher::estimate(self)
+    }
+}
+
+impl Refine for MixSearcher {
+    fn refine(&mut self, min_width: usize) {
+        MixSearcher::refine(self, min_width)
+    }
+}
+
+impl Iterator for MixSearcher {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Decides the outcome of a run of a mix of two workloads from the metrics of both.
+///
+/// Each workload has its own [`Judge`], or none if its metric does not matter. A run is
+/// overloaded if either judge finds it so, inconclusive if either judge finds it inconclusive and
+/// neither finds it overloaded, and sustained otherwise. A workload that is not part of the mix at
+/// all, because the mix is `0` or `100` percent, is not judged, so its metric can be anything,
+/// like `NaN`.
+///
+/// See [`MixSearcher`] for an example.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MixJudge {
+    first: Option<Judge>,
+    second: Option<Judge>,
+}
+
+impl MixJudge {
+    /// Judge runs on the metric of the first workload only.
+    pub fn first(judge: Judge) -> Self {
+        MixJudge {
+            first: Some(judge),
+            second: None,
+        }
+    }
+
+    /// Judge runs on the metric of the second workload only.
+    pub fn second(judge: Judge) -> Self {
+        MixJudge {
+            first: None,
+            second: Some(judge),
+        }
+    }
+
+    /// Judge runs on the metrics of both workloads.
+    pub fn both(first: Judge, second: Judge) -> Self {
+        MixJudge {
+            first: Some(first),
+            second: Some(second),
+        }
+    }
+
+    /// Decide the outcome of a run with `pct` percent of the second workload, whose metrics came
+    /// out as `first` and `second`.
+    pub fn judge(&self, pct: usize, first: f64, second: f64) -> Verdict {
+        let first = self.first.filter(|_| pct < 100).map(|j| j.judge(first));
+        let second = self.second.filter(|_| pct > 0).map(|j| j.judge(second));
+        let verdicts = [first, second];
+        if verdicts.contains(&Some(Verdict::Overloaded)) {
+            Verdict::Overloaded
+        } else if verdicts.contains(&Some(Verdict::Inconclusive)) {
+            Verdict::Inconclusive
+        } else {
+            Verdict::Sustained
+        }
+    }
+}
+
+impl Describe for MixSearcher {
+    fn name(&self) -> &'static str {
+        "mix"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        self.inner.settings(setting);
+    }
+}
+
+#[test]
+fn search() {
+    let mut mix = MixSearcher::new();
+    assert_eq!(mix.next(), Some(50));
+    mix.overloaded();
+    assert_eq!(mix.next(), Some(25));
+    assert_eq!(mix.next(), Some(37));
+    mix.overloaded();
+    assert_eq!(mix.next(), Some(31));
+    assert_eq!(mix.next(), Some(34));
+    mix.overloaded();
+    assert_eq!(mix.next(), Some(32));
+    mix.overloaded();
+    assert_eq!(mix.next(), None);
+    assert_eq!(mix.estimate(), 31..32);
+
+    let mut mix = MixSearcher::new();
+    while mix.next().is_some() {}
+    assert_eq!(mix.estimate(), 100..101);
+}
+
+#[test]
+fn split() {
+    assert_eq!(MixSearcher::split(0, 1000), (1000, 0));
+    assert_eq!(MixSearcher::split(100, 1000), (0, 1000));
+    assert_eq!(MixSearcher::split(150, 1000), (0, 1000));
+    assert_eq!(MixSearcher::split(33, 10), (7, 3));
+    assert_eq!(MixSearcher::split(50, usize::MAX).1, usize::MAX / 2);
+}
+
+#[test]
+fn judge() {
+    let mut reads = Judge::at_most(10.0);
+    reads.sustain_margin(0.1);
+    let both = MixJudge::both(reads, Judge::at_most(20.0));
+    assert_eq!(both.judge(50, 5.0, 15.0), Verdict::Sustained);
+    assert_eq!(both.judge(50, 9.5, 15.0), Verdict::Inconclusive);
+    assert_eq!(both.judge(50, 9.5, 25.0), Verdict::Overloaded);
+    assert_eq!(both.judge(50, 50.0, 15.0), Verdict::Overloaded);
+    // a workload that isn't in the mix isn't judged
+    assert_eq!(both.judge(0, 5.0, f64::NAN), Verdict::Sustained);
+    assert_eq!(both.judge(100, f64::NAN, 15.0), Verdict::Sustained);
+
+    let writes = MixJudge::second(Judge::at_most(20.0));
+    assert_eq!(writes.judge(50, 100.0, 15.0), Verdict::Sustained);
+    assert_eq!(writes.judge(50, 1.0, 25.0), Verdict::Overloaded);
+}
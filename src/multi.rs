@@ -0,0 +1,221 @@
+use super::{Judge, Verdict};
+
+/// A search for the cliffs of several service-level objectives at once.
+///
+/// SLAs on different metrics of the same system, like latency objectives for p50, p99, and p999,
+/// each have their own cliff, but every benchmark run measures all of the metrics. Rather than
+/// running a separate search for each, this keeps one estimate per objective, and judges every
+/// run against all of them. Each run is picked to narrow whichever estimate is currently widest,
+/// and the ramp up to the first overload is shared, so all the cliffs are usually found in not
+/// much more than the runs a single search would take.
+///
+/// The metrics of each run are reported with [`measured`](MultiThresholdSearch::measured), in the
+/// same order as the judges the search was made with. A run whose metrics are not reported is
+/// run again. A judge that finds a run [inconclusive](Verdict::Inconclusive) leaves its estimate
+/// as it was, so the load is run again if that estimate is still the widest.
+///
+/// ```rust
+/// use cliff::{Judge, MultiThresholdSearch};
+///
+/// // the system's latency percentiles, in ms, at a given load
+/// let measure = |load: usize| {
+///     let load = load as f64;
+///     [load / 1_000.0, load / 150.0, load / 20.0]
+/// };
+///
+/// let mut load = MultiThresholdSearch::new(
+///     500,
+///     [Judge::at_most(1.0), Judge::at_most(10.0), Judge::at_most(100.0)],
+/// );
+/// while let Some(l) = load.next() {
+///     load.measured(&measure(l));
+/// }
+/// assert_eq!(load.estimate(0), 1000..1250);
+/// assert_eq!(load.estimate(1), 1500..1750);
+/// assert_eq!(load.estimate(2), 2000..2250);
+/// // three separate searches would have taken 17 runs
+/// assert_eq!(load.probes(), 10);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about its measurements"]
+pub struct MultiThresholdSearch<const N: usize> {
+    judges: [Judge; N],
+    lower: [usize; N],
+    upper: [usize; N],
+    start: usize,
+    min_width: usize,
+    last: Option<usize>,
+    measured: bool,
+    probes: usize,
+}
+
+impl<const N: usize> MultiThresholdSearch<N> {
+    /// Find the cliff of each of `judges`, ramping up from `start`, and ending when every cliff
+    /// has been determined to within a range of half of `start`.
+    pub fn new(start: usize, judges: [Judge; N]) -> Self {
+        Self::until(start, start / 2, judges)
+    }
+
+    /// Find the cliff of each of `judges`, ramping up from `start`, and ending when every cliff
+    /// has been determined to within a range of `min_width`.
+    ///
+    /// A `start` of `0` is treated as `1`, since the load can then never be doubled.
+    pub fn until(start: usize, min_width: usize, judges: [Judge; N]) -> Self {
+        MultiThresholdSearch {
+            judges,
+            lower: [0; N],
+            upper: [usize::MAX; N],
+            start: start.max(1),
+            min_width: min_width.max(1),
+            last: None,
+            measured: false,
+            probes: 0,
+        }
+    }
+
+    /// Report the metrics measured when running the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// `metrics[i]` is judged by the `i`th judge the search was made with. Judges without a
+    /// corresponding metric leave their estimate as it was.
+    pub fn measured(&mut self, metrics: &[f64]) {
+        let load = match self.last {
+            Some(load) => load,
+            None => return,
+        };
+        self.measured = true;
+        for (i, (judge, &metric)) in self.judges.iter().zip(metrics).enumerate() {
+            if load <= self.lower[i] || load >= self.upper[i] {
+                // the estimate already knows better
+                continue;
+            }
+            match judge.judge(metric) {
+                Verdict::Sustained | Verdict::Marginal => self.lower[i] = load,
+                Verdict::Overloaded => self.upper[i] = load,
+                Verdict::Inconclusive => {}
+            }
+        }
+    }
+
+    /// Give the current estimate of the cliff of the `i`th judge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no judge with that index.
+    pub fn estimate(&self, i: usize) -> core::ops::Range<usize> {
+        self.lower[i]..self.upper[i]
+    }
+
+    /// Returns `true` if the cliff of the `i`th judge has been determined to within the requested
+    /// width.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no judge with that index.
+    pub fn is_done(&self, i: usize) -> bool {
+        self.upper[i] - self.lower[i] <= self.min_width
+    }
+
+    /// The number of benchmark runs yielded so far, including repeated ones.
+    pub fn probes(&self) -> usize {
+        self.probes
+    }
+
+    /// The judge whose estimate is widest and still needs narrowing, if any.
+    ///
+    /// Ties go to the judge that comes first.
+    fn widest(&self) -> Option<usize> {
+        (0..N)
+            .filter(|&i| !self.is_done(i))
+            .fold(None, |widest: Option<usize>, i| match widest {
+                Some(w) if self.upper[w] - self.lower[w] >= self.upper[i] - self.lower[i] => {
+                    Some(w)
+                }
+                _ => Some(i),
+            })
+    }
+}
+
+impl<const N: usize> Iterator for MultiThresholdSearch<N> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if !core::mem::replace(&mut self.measured, false) && self.last.is_some() {
+            // no metrics were reported, so we know nothing new; run it again
+            self.probes += 1;
+            return self.last;
+        }
+
+        let i = self.widest()?;
+        let load = if self.upper[i] == usize::MAX && self.lower[i] == 0 {
+            self.start
+        } else if self.upper[i] == usize::MAX {
+            // keep one below usize::MAX, which stands for "no upper bound"
+            self.lower[i].saturating_mul(2).min(usize::MAX - 1)
+        } else {
+            self.lower[i] + (self.upper[i] - self.lower[i]) / 2
+        };
+        self.probes += 1;
+        self.last = Some(load);
+        self.last
+    }
+}
+
+#[test]
+fn shared_runs() {
+    use super::ExponentialCliffSearcher;
+
+    let measure = |load: usize| [load as f64 / 10.0, load as f64 / 40.0];
+    let judges = [Judge::at_most(100.0), Judge::at_most(100.0)];
+    let mut load = MultiThresholdSearch::until(100, 50, judges);
+    while let Some(l) = load.next() {
+        load.measured(&measure(l));
+    }
+    assert_eq!(load.estimate(0), 1000..1050);
+    assert_eq!(load.estimate(1), 4000..4050);
+    assert!(load.is_done(0) && load.is_done(1));
+
+    // no more than two separate searches would have taken
+    let separate: usize = (0..2)
+        .map(|i| {
+            let mut search = ExponentialCliffSearcher::until(100, 50);
+            let mut runs = 0;
+            while let Some(l) = search.next() {
+                runs += 1;
+                judges[i].judge(measure(l)[i]).apply(&mut search);
+            }
+            assert_eq!(search.estimate(), load.estimate(i));
+            runs
+        })
+        .sum();
+    assert!(
+        load.probes() < separate,
+        "{} vs {}",
+        load.probes(),
+        separate
+    );
+}
+
+#[test]
+fn unmeasured() {
+    let mut load = MultiThresholdSearch::new(500, [Judge::at_most(1.0)]);
+    assert_eq!(load.next(), Some(500));
+    // no metrics, so the run is repeated
+    assert_eq!(load.next(), Some(500));
+    load.measured(&[0.5]);
+    assert_eq!(load.next(), Some(1000));
+    // an inconclusive run is repeated too
+    let mut judge = Judge::at_most(1.0);
+    judge.sustain_margin(0.1);
+    let mut load = MultiThresholdSearch::new(500, [judge]);
+    assert_eq!(load.next(), Some(500));
+    load.measured(&[0.95]);
+    assert_eq!(load.next(), Some(500));
+    load.measured(&[2.0]);
+    assert_eq!(load.next(), Some(250));
+    assert_eq!(load.estimate(0), 0..500);
+    assert_eq!(load.probes(), 3);
+
+    // nothing to measure against
+    let mut load = MultiThresholdSearch::new(500, []);
+    assert_eq!(load.next(), None);
+}
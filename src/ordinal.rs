@@ -0,0 +1,179 @@
+use super::{BinaryMinSearcher, CliffSearch, Describe};
+
+/// An iterator that determines the smallest sufficient entry in an ordered list of tiers.
+///
+/// This is useful for questions like "which instance size is sufficient", where the candidates
+/// are not numbers, but are still ordered from least to most capable. The searcher bisects over
+/// the tiers' indices, starting with the most capable tier, and yields the _index_ of each tier to
+/// try. Use [`tier`](OrdinalSearcher::tier) to map an index back to the tier itself, and
+/// [`bracket`](OrdinalSearcher::bracket) to learn which tiers bracket the answer.
+///
+/// ```rust
+/// use cliff::OrdinalSearcher;
+///
+/// let sizes = ["nano", "micro", "small", "medium", "large"];
+/// let cores = [1, 2, 4, 8, 16];
+/// let mut tiers = OrdinalSearcher::new(&sizes);
+/// while let Some(i) = tiers.next() {
+///     if cores[i] < 5 {
+///         // too small!
+///         tiers.overloaded();
+///     }
+/// }
+/// assert_eq!(tiers.bracket(), (Some(&"small"), Some(&"medium")));
+/// ```
+///
+/// See also the [crate-level documentation](..) for details.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct OrdinalSearcher<'a, T> {
+    tiers: &'a [T],
+    // searches over 1-based ranks, so that rank 0 stands for "below the lowest tier"
+    inner: BinaryMinSearcher,
+}
+
+impl<'a, T> OrdinalSearcher<'a, T> {
+    /// Search for the smallest sufficient tier in `tiers`, which must be ordered from least to
+    /// most capable.
+    pub fn new(tiers: &'a [T]) -> Self {
+        OrdinalSearcher {
+            tiers,
+            inner: BinaryMinSearcher::until(tiers.len(), 1),
+        }
+    }
+
+    /// Get the tier at index `i`, as yielded by [`Iterator::next`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn tier(&self, i: usize) -> &'a T {
+        &self.tiers[i]
+    }
+
+    /// Indicate that the previous tier yielded by [`Iterator::next`] was insufficient.
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the benchmark run for the previous tier yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same tier again, so that it can be re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inner.inconclusive();
+    }
+
+    /// Give the index of the most capable tier known to be insufficient, up to the index of the
+    /// least capable tier known to be sufficient.
+    ///
+    /// These are the same indices as those yielded by [`Iterator::next`], so the estimate reads
+    /// like that of a [`BinaryMinSearcher`] over the indices. Until a tier has been found to be
+    /// insufficient, the start is `0`. The range is therefore empty both if the least capable tier
+    /// is sufficient and if not even the most capable one is; use
+    /// [`bracket`](OrdinalSearcher::bracket) to tell the two apart.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        let ranks = self.inner.estimate();
+        ranks.start.saturating_sub(1)..ranks.end.saturating_sub(1)
+    }
+
+    /// Give the most capable tier known to be insufficient, and the least capable tier that is
+    /// sufficient.
+    ///
+    /// The first is `None` if no tier has been found to be insufficient. The second is `None` if no
+    /// tier is sufficient; until the most capable tier has been tried, it is assumed to be.
+    pub fn bracket(&self) -> (Option<&'a T>, Option<&'a T>) {
+        let ranks = self.inner.estimate();
+        let insufficient = ranks.start.checked_sub(1).map(|i| &self.tiers[i]);
+        let sufficient = if ranks.end > ranks.start {
+            Some(&self.tiers[ranks.end - 1])
+        } else {
+            None
+        };
+        (insufficient, sufficient)
+    }
+}
+
+impl<'a, T> CliffSearch for OrdinalSearcher<'a, T> {
+    fn overloaded(&mut self) {
+        OrdinalSearcher::overloaded(self)
+    }
+
+    fn inconclusive(&mut self) {
+        OrdinalSearcher::inconclusive(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        OrdinalSearcher::estimate(self)
+    }
+}
+
+impl<'a, T> Iterator for OrdinalSearcher<'a, T> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        // rank 0 is never probed unless there are no tiers at all
+        self.inner.next()?.checked_sub(1)
+    }
+}
+
+impl<T> Describe for OrdinalSearcher<'_, T> {
+    fn name(&self) -> &'static str {
+        "ordinal"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("tiers", &self.tiers.len());
+    }
+}
+
+#[test]
+fn search() {
+    let tiers = ["a", "b", "c", "d", "e", "f", "g", "h"];
+    let mut scale = OrdinalSearcher::new(&tiers);
+    assert_eq!(scale.bracket(), (None, Some(&"h")));
+    assert_eq!(scale.next(), Some(7));
+    assert_eq!(scale.next(), Some(3));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(5));
+    assert_eq!(scale.next(), Some(4));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    // the cliff lies between the tiers at index 4 and 5
+    assert_eq!(scale.estimate(), 4..5);
+    assert_eq!(scale.bracket(), (Some(&"e"), Some(&"f")));
+
+    // so a report's estimate is bounded by the indices that were run
+    let report = OrdinalSearcher::new(&tiers).run_to_completion(|i| i >= 5);
+    assert_eq!(report.estimate, 4..5);
+}
+
+#[test]
+fn lowest_suffices() {
+    let tiers = ["a", "b", "c"];
+    let mut scale = OrdinalSearcher::new(&tiers);
+    assert_eq!(scale.next(), Some(2));
+    assert_eq!(scale.next(), Some(0));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 0..0);
+    assert_eq!(scale.bracket(), (None, Some(&"a")));
+}
+
+#[test]
+fn nothing_suffices() {
+    let tiers = ["a", "b", "c"];
+    let mut scale = OrdinalSearcher::new(&tiers);
+    assert_eq!(scale.next(), Some(2));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 2..2);
+    assert_eq!(scale.bracket(), (Some(&"c"), None));
+
+    let mut scale = OrdinalSearcher::<()>::new(&[]);
+    assert_eq!(scale.next(), None);
+}
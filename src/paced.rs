@@ -0,0 +1,475 @@
+use super::{CliffSearch, Describe, RandomSource, SplitMix64, Status, Suspend};
+use core::time::Duration;
+
+/// A search that suggests how long to let the system recover before each probe.
+///
+/// After an overloaded run, the system-under-test is often still degraded for a while (queues are
+/// draining, caches are cold, and so on), and probing it again immediately yields misleading
+/// results. This wrapper tracks whether the previous probe overloaded the system, and after each
+/// call to [`Iterator::next`], [`delay`](Paced::delay) gives the minimum time to wait before
+/// running the yielded load.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Paced};
+/// use std::time::Duration;
+///
+/// let mut load = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(30));
+/// assert_eq!(load.next(), Some(500));
+/// assert_eq!(load.delay(), Duration::from_secs(0));
+/// assert_eq!(load.next(), Some(1000));
+/// load.overloaded();
+/// assert_eq!(load.next(), Some(750));
+/// assert_eq!(load.delay(), Duration::from_secs(30));
+/// ```
+///
+/// With [`jitter`](Paced::jitter), delays are spread out at random, so that several searches
+/// started at the same time do not keep hitting a shared system in lockstep.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Paced<S, R = SplitMix64> {
+    inner: S,
+    after_overload: Duration,
+    after_sustained: Duration,
+    delay: Duration,
+    last: Option<usize>,
+    started: bool,
+    overloaded: Option<f64>,
+    jitter: Option<(f64, R)>,
+}
+
+impl<S> Paced<S>
+where
+    S: CliffSearch,
+{
+    /// Wrap `search` so that probes following an overloaded probe are delayed by
+    /// `after_overload`.
+    pub fn new(search: S, after_overload: Duration) -> Self {
+        Paced {
+            inner: search,
+            after_overload,
+            after_sustained: Duration::from_secs(0),
+            delay: Duration::from_secs(0),
+            last: None,
+            started: false,
+            overloaded: None,
+            jitter: None,
+        }
+    }
+}
+
+impl<S, R> Paced<S, R>
+where
+    S: CliffSearch,
+    R: RandomSource,
+{
+    /// Randomly lengthen or shorten every non-zero delay by up to `fraction` of it, using
+    /// randomness from `rng`.
+    ///
+    /// With a seeded generator like [`SplitMix64`], the delays are the same every time the search
+    /// is run with the same outcomes, and the seed ends up in the
+    /// [`Report`](crate::Report::seed).
+    ///
+    /// ```rust
+    /// use cliff::{ExponentialCliffSearcher, Paced, SplitMix64};
+    /// use std::time::Duration;
+    ///
+    /// let mut load = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(30))
+    ///     .jitter(0.1, SplitMix64::new(42));
+    /// assert_eq!(load.next(), Some(500));
+    /// assert_eq!(load.next(), Some(1000));
+    /// load.overloaded();
+    /// assert_eq!(load.next(), Some(750));
+    /// assert!(load.delay() >= Duration::from_secs(27));
+    /// assert!(load.delay() <= Duration::from_secs(33));
+    /// ```
+    pub fn jitter<R2>(self, fraction: f64, rng: R2) -> Paced<S, R2>
+    where
+        R2: RandomSource,
+    {
+        Paced {
+            inner: self.inner,
+            after_overload: self.after_overload,
+            after_sustained: self.after_sustained,
+            delay: self.delay,
+            last: self.last,
+            started: self.started,
+            overloaded: self.overloaded,
+            jitter: Some((fraction.clamp(0.0, 1.0), rng)),
+        }
+    }
+
+    /// Also delay probes that follow a probe the system kept up with.
+    ///
+    /// Defaults to no delay.
+    pub fn after_sustained(&mut self, delay: Duration) {
+        self.after_sustained = delay;
+    }
+
+    /// Give the minimum time to wait before running the load last yielded by [`Iterator::next`].
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = Some(1.0);
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// The delay before the next probe is scaled by `factor` (if it is greater than one), so that
+    /// a system that was wildly overloaded is given more time to recover. A delay that would be
+    /// too long for a [`Duration`], like for an infinite `factor`, is the longest one there is.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = Some(factor);
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// If `actual` is below the requested load, the next probe is delayed as if the previous one
+    /// was overloaded.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) && self.overloaded.is_none() {
+            self.overloaded = Some(1.0);
+        }
+        self.inner.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+}
+
+impl<S, R> CliffSearch for Paced<S, R>
+where
+    S: CliffSearch,
+    R: RandomSource,
+{
+    fn overloaded(&mut self) {
+        Paced::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Paced::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Paced::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Paced::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Paced::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Paced::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Paced::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.probes_left()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        match &self.jitter {
+            Some((_, rng)) => rng.seed(),
+            None => self.inner.seed(),
+        }
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+/// The state of a suspended [`Paced`] search.
+///
+/// See [`Suspend`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuspendedPaced<T, R = SplitMix64> {
+    /// The state of the wrapped search.
+    pub inner: T,
+    /// The delay after an overloaded probe.
+    pub after_overload: Duration,
+    /// The delay after a probe the system kept up with.
+    pub after_sustained: Duration,
+    /// The delay before the most recently yielded probe.
+    pub delay: Duration,
+    /// The probe whose outcome has yet to be taken into account.
+    pub pending: Option<usize>,
+    /// Whether the first probe has been yielded.
+    pub started: bool,
+    /// How badly the pending probe was overloaded, if it was.
+    pub overloaded: Option<f64>,
+    /// How much to jitter delays by, and the state of the random source to do so with.
+    pub jitter: Option<(f64, R)>,
+}
+
+impl<T, R> SuspendedPaced<T, R> {
+    /// Continue the suspended search.
+    pub fn resume<S>(self) -> Paced<S, R>
+    where
+        S: Suspend<Suspended = T>,
+    {
+        Paced {
+            inner: S::resume(self.inner),
+            after_overload: self.after_overload,
+            after_sustained: self.after_sustained,
+            delay: self.delay,
+            last: self.pending,
+            started: self.started,
+            overloaded: self.overloaded,
+            jitter: self.jitter,
+        }
+    }
+}
+
+impl<S, R> Suspend for Paced<S, R>
+where
+    S: Suspend,
+    R: RandomSource,
+{
+    type Suspended = SuspendedPaced<S::Suspended, R>;
+
+    fn suspend(self) -> Self::Suspended {
+        SuspendedPaced {
+            inner: self.inner.suspend(),
+            after_overload: self.after_overload,
+            after_sustained: self.after_sustained,
+            delay: self.delay,
+            pending: self.last,
+            started: self.started,
+            overloaded: self.overloaded,
+            jitter: self.jitter,
+        }
+    }
+
+    fn resume(suspended: Self::Suspended) -> Self {
+        suspended.resume()
+    }
+}
+
+// scale `delay` by `factor`, but give the longest possible delay rather than panic if that is too
+// long for a `Duration`, like for an infinitely bad overload
+fn saturating_mul_f64(delay: Duration, factor: f64) -> Duration {
+    let secs = delay.as_secs_f64() * factor;
+    if secs < u64::MAX as f64 {
+        Duration::from_secs_f64(secs)
+    } else {
+        Duration::new(u64::MAX, 999_999_999)
+    }
+}
+
+impl<S, R> Iterator for Paced<S, R>
+where
+    S: CliffSearch,
+    R: RandomSource,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.inner.next()?;
+        self.delay = if !self.started {
+            self.started = true;
+            Duration::from_secs(0)
+        } else if let Some(factor) = self.overloaded {
+            if factor > 1.0 {
+                saturating_mul_f64(self.after_overload, factor)
+            } else {
+                self.after_overload
+            }
+        } else {
+            self.after_sustained
+        };
+        if let Some((fraction, rng)) = &mut self.jitter {
+            if self.delay > Duration::from_secs(0) {
+                let scale = 1.0 + *fraction * (2.0 * rng.next_f64() - 1.0);
+                self.delay = saturating_mul_f64(self.delay, scale);
+            }
+        }
+        self.overloaded = None;
+        self.last = Some(next);
+        Some(next)
+    }
+}
+
+impl<S, R> Describe for Paced<S, R>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "paced"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("after_overload_ms", &self.after_overload.as_millis());
+        setting("after_sustained_ms", &self.after_sustained.as_millis());
+        if let Some((jitter, _)) = &self.jitter {
+            setting("jitter", jitter);
+        }
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn paced() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(10));
+    scale.after_sustained(Duration::from_secs(1));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.delay(), Duration::from_secs(0));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.delay(), Duration::from_secs(1));
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1500));
+    assert_eq!(scale.delay(), Duration::from_secs(10));
+    assert_eq!(scale.next(), Some(1750));
+    assert_eq!(scale.delay(), Duration::from_secs(1));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 1500..1750);
+}
+
+#[test]
+fn paced_by_severity() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(10));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded_by(3.0);
+    assert_eq!(scale.next(), Some(666));
+    assert_eq!(scale.delay(), Duration::from_secs(30));
+    scale.overloaded_by(0.5);
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.delay(), Duration::from_secs(30));
+}
+
+#[test]
+fn paced_by_huge_severity() {
+    use super::ExponentialCliffSearcher;
+
+    let longest = Duration::new(u64::MAX, 999_999_999);
+    for &factor in &[f64::INFINITY, 1e300, u64::MAX as f64] {
+        let mut scale =
+            Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(10))
+                .jitter(0.5, SplitMix64::new(1));
+        assert_eq!(scale.next(), Some(500));
+        assert_eq!(scale.next(), Some(1000));
+        scale.overloaded_by(factor);
+        // the search splits the estimate close to 500, and the delay is as long as it gets
+        assert!(scale.next().is_some());
+        assert!(scale.delay() >= longest / 2);
+    }
+
+    let mut scale = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(10));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded_by(f64::INFINITY);
+    assert!(scale.next().is_some());
+    assert_eq!(scale.delay(), longest);
+}
+
+#[test]
+fn suspend() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(10));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    let mut scale: Paced<ExponentialCliffSearcher> = scale.suspend().resume();
+    assert_eq!(scale.next(), Some(750));
+    assert_eq!(scale.delay(), Duration::from_secs(10));
+}
+
+#[test]
+fn jitter() {
+    use super::ExponentialCliffSearcher;
+
+    let run = |seed| {
+        let mut scale = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(10))
+            .jitter(0.5, SplitMix64::new(seed));
+        let mut delays = [Duration::from_secs(0); 4];
+        for delay in &mut delays {
+            if scale.next().unwrap() > 1600 {
+                scale.overloaded();
+            }
+            *delay = scale.delay();
+        }
+        assert_eq!(scale.seed(), Some(seed));
+        delays
+    };
+    let delays = run(7);
+    // the first probe is never delayed, and the following sustained ones have no delay to jitter
+    assert_eq!(delays[..3], [Duration::from_secs(0); 3]);
+    assert!(delays[3] >= Duration::from_secs(5) && delays[3] <= Duration::from_secs(15));
+    assert_ne!(delays[3], Duration::from_secs(10));
+    // the same seed gives the same delays
+    assert_eq!(run(7), delays);
+
+    // the random state survives suspension
+    let mut scale = Paced::new(ExponentialCliffSearcher::new(500), Duration::from_secs(10))
+        .jitter(0.5, SplitMix64::new(7));
+    scale.after_sustained(Duration::from_secs(10));
+    let mut resumed = scale.clone().suspend().resume::<ExponentialCliffSearcher>();
+    for _ in 0..3 {
+        scale.next();
+        resumed.next();
+        assert_eq!(scale.delay(), resumed.delay());
+    }
+}
@@ -0,0 +1,145 @@
+use super::{BinaryMinSearcher, CliffSearch, Describe, Refine};
+
+/// An iterator that determines the minimum percentage value of a system parameter.
+///
+/// This is a [`BinaryMinSearcher`] preconfigured for parameters in the domain `0..=100`, like a
+/// cache hit target or a CPU quota, that searches down to a precision of a single percentage
+/// point.
+///
+/// ```rust
+/// use cliff::PercentSearcher;
+///
+/// let mut quota = PercentSearcher::new();
+/// while let Some(pct) = quota.next() {
+///     if pct < 37 {
+///         quota.overloaded();
+///     }
+/// }
+/// assert_eq!(quota.estimate(), 36..37);
+/// ```
+///
+/// See also the [crate-level documentation](..) for details.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct PercentSearcher {
+    inner: BinaryMinSearcher,
+}
+
+impl Default for PercentSearcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PercentSearcher {
+    /// Perform a minimum search starting at 100%.
+    pub fn new() -> Self {
+        Self::starting_at(100)
+    }
+
+    /// Perform a minimum search starting at `start` percent.
+    ///
+    /// Values above 100 are clamped to 100.
+    pub fn starting_at(start: usize) -> Self {
+        PercentSearcher {
+            inner: BinaryMinSearcher::until(start.min(100), 1),
+        }
+    }
+
+    /// Indicate that the system could not keep up with the previous percentage yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the benchmark run for the previous percentage yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same percentage again, so that it can be re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inner.inconclusive();
+    }
+
+    /// Give the current estimate of the minimum percentage the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Change the number of percentage points to which the minimum should be determined, and
+    /// resume the search if it had ended with a wider estimate than that.
+    ///
+    /// This provides [`Refine::refine`] without having to `use` the trait.
+    pub fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl CliffSearch for PercentSearcher {
+    fn overloaded(&mut self) {
+        PercentSearcher::overloaded(self)
+    }
+
+    fn inconclusive(&mut self) {
+        PercentSearcher::inconclusive(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        PercentSearcher::estimate(self)
+    }
+}
+
+impl Refine for PercentSearcher {
+    fn refine(&mut self, min_width: usize) {
+        PercentSearcher::refine(self, min_width)
+    }
+}
+
+impl Iterator for PercentSearcher {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl Describe for PercentSearcher {
+    fn name(&self) -> &'static str {
+        "percent"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        self.inner.settings(setting);
+    }
+}
+
+#[test]
+fn search() {
+    let mut scale = PercentSearcher::new();
+    assert_eq!(scale.next(), Some(100));
+    assert_eq!(scale.next(), Some(50));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(75));
+    assert_eq!(scale.next(), Some(62));
+    assert_eq!(scale.next(), Some(56));
+    assert_eq!(scale.next(), Some(53));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(54));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(55));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 54..55);
+}
+
+#[test]
+fn clamped() {
+    let mut scale = PercentSearcher::starting_at(250);
+    assert_eq!(scale.next(), Some(100));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 100..100);
+}
@@ -0,0 +1,377 @@
+use super::{CliffSearch, Describe, Refine, Sample, Status};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A search that, once it has found the cliff, runs whatever extra loads it takes to plot a curve
+/// of roughly a given number of evenly spaced points.
+///
+/// The loads a search runs are picked to find the cliff, not to make a good plot: they bunch up
+/// around the cliff, and leave wide gaps below it and nothing at all far above it. Rather than
+/// tuning [`fill_left`](crate::ExponentialCliffSearcher::fill_left) and friends until the curve
+/// looks right, this takes the intent directly: `points` points spread evenly across `range`.
+/// Once the wrapped search ends, every point of that grid that has no sample within half a
+/// spacing of it is run, in increasing order, and all the samples together make up the curve.
+/// Samples the search took are reused, so the extra runs go where the curve has gaps.
+///
+/// The extra runs do not change the estimate, and are judged as usual, so that they can be
+/// plotted along with whether the system kept up. All of them are in
+/// [`samples`](Plotted::samples).
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Plotted};
+///
+/// // a 12-point curve from 100 to 4500
+/// let mut load = Plotted::new(ExponentialCliffSearcher::new(500), 12, 100..4501);
+/// while let Some(l) = load.next() {
+///     if l > 1600 {
+///         load.overloaded();
+///     }
+/// }
+/// assert_eq!(load.estimate(), 1500..1750);
+/// let mut loads: Vec<_> = load.samples().iter().map(|s| s.load).collect();
+/// loads.sort();
+/// assert_eq!(
+///     loads,
+///     [100, 500, 1000, 1500, 1750, 2000, 2500, 2900, 3300, 3700, 4100, 4500]
+/// );
+/// ```
+///
+/// This type is only available with the `alloc` feature.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Plotted<S> {
+    inner: S,
+    points: usize,
+    range: Range<usize>,
+    samples: Vec<Sample>,
+    // the loads left to fill in, highest first, once the search has ended
+    fill: Option<Vec<usize>>,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+}
+
+impl<S> Plotted<S>
+where
+    S: CliffSearch,
+{
+    /// Fill in the samples of `search` to a curve of about `points` evenly spaced points across
+    /// `range`.
+    ///
+    /// The first and last point are at either end of `range`. Fewer than `2` points are taken to
+    /// be `2`.
+    pub fn new(search: S, points: usize, range: Range<usize>) -> Self {
+        Plotted {
+            inner: search,
+            points: points.max(2),
+            range,
+            samples: Vec::new(),
+            fill: None,
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+        }
+    }
+
+    /// Give the outcome of every conclusive run so far, in the order they were run.
+    ///
+    /// The runs of the wrapped search come first, followed by those that fill in the curve.
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    /// Returns `true` once the wrapped search has ended, and the curve is being filled in.
+    pub fn is_filling(&self) -> bool {
+        self.fill.is_some()
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+        if self.fill.is_none() {
+            self.inner.overloaded();
+        }
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        if self.fill.is_none() {
+            self.inner.overloaded_by(factor);
+        }
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// A run that fills in the curve is run again, just like those of the wrapped search.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+        if self.fill.is_none() {
+            self.inner.inconclusive();
+        }
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// A run that fills in the curve counts as sustained.
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        if self.fill.is_none() {
+            self.inner.marginal();
+        }
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// A point of the curve that cannot be run is left out.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        match self.fill {
+            Some(_) => self.last = None,
+            None => {
+                self.inconclusive = true;
+                self.inner.skip_last();
+            }
+        }
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall is recorded as an overload.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+        if self.fill.is_none() {
+            self.inner.achieved(actual);
+        }
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    // the points of the grid that no sample is close enough to, highest first
+    fn gaps(&self) -> Vec<usize> {
+        let mut gaps = Vec::new();
+        if self.range.start >= self.range.end {
+            return gaps;
+        }
+        let last = self.range.end - 1;
+        // the grid is spaced (last - start) / (points - 1) apart, but kept in integers so that
+        // rounding does not need std
+        let span = (last - self.range.start) as u128;
+        let steps = (self.points - 1) as u128;
+        for i in (0..self.points).rev() {
+            let offset = (i as u128 * span + steps / 2) / steps;
+            let point = self.range.start + offset as usize;
+            let point = point.min(last);
+            let covered = self.samples.iter().any(|s| {
+                let distance = if s.load > point {
+                    s.load - point
+                } else {
+                    point - s.load
+                };
+                2 * distance as u128 * steps <= span
+            });
+            if !covered && gaps.last() != Some(&point) {
+                gaps.push(point);
+            }
+        }
+        gaps
+    }
+}
+
+impl<S> CliffSearch for Plotted<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Plotted::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Plotted::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Plotted::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Plotted::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Plotted::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Plotted::skip_last(self)
+    }
+
+    fn estimate(&self) -> Range<usize> {
+        Plotted::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        // until the search ends, how many points are left to fill in depends on where it probes
+        self.fill.as_ref().map(|fill| fill.len())
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for Plotted<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for Plotted<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(load) = self.last.take() {
+            if !self.inconclusive {
+                self.samples.push(Sample {
+                    load,
+                    overloaded: self.overloaded,
+                    reason: None,
+                });
+            } else if let Some(fill) = &mut self.fill {
+                // run the point again
+                fill.push(load);
+            }
+        }
+        self.overloaded = false;
+        self.inconclusive = false;
+
+        if self.fill.is_none() {
+            if let Some(load) = self.inner.next() {
+                self.last = Some(load);
+                return self.last;
+            }
+            self.fill = Some(self.gaps());
+        }
+        self.last = self.fill.as_mut().and_then(|fill| fill.pop());
+        self.last
+    }
+}
+
+impl<S> Describe for Plotted<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "plotted"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("points", &self.points);
+        setting("start", &self.range.start);
+        setting("end", &self.range.end);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn fill() {
+    use super::ExponentialCliffSearcher;
+
+    let mut load = Plotted::new(ExponentialCliffSearcher::new(500), 5, 0..2001);
+    let mut ran = Vec::new();
+    while let Some(l) = load.next() {
+        ran.push(l);
+        if l == 0 {
+            // a point that can't be run is left out
+            load.skip_last();
+            continue;
+        }
+        if l == 1500 && load.is_filling() {
+            panic!("1500 was already run by the search");
+        }
+        if l > 1600 {
+            load.overloaded();
+        }
+    }
+    // the search itself, and then 0, since 500, 1000, 1500, and 2000 were all run already
+    assert_eq!(ran, alloc::vec![500, 1000, 2000, 1500, 1750, 0]);
+    assert_eq!(load.samples().len(), 5);
+    assert_eq!(load.estimate(), 1500..1750);
+    assert_eq!(load.probes_left(), Some(0));
+
+    // the curve may also reach above the cliff, and inconclusive points are run again
+    let mut load = Plotted::new(ExponentialCliffSearcher::new(500), 3, 500..3001);
+    let mut ran = Vec::new();
+    let mut again = true;
+    while let Some(l) = load.next() {
+        ran.push(l);
+        if l == 3000 && core::mem::take(&mut again) {
+            load.inconclusive();
+        } else if l > 1600 {
+            load.overloaded();
+        }
+    }
+    assert_eq!(ran, alloc::vec![500, 1000, 2000, 1500, 1750, 3000, 3000]);
+    let last = load.samples().last().unwrap();
+    assert_eq!((last.load, last.overloaded), (3000, true));
+    assert_eq!(load.estimate(), 1500..1750);
+}
+
+#[test]
+fn uneven_spacing() {
+    use super::LoadIterator;
+
+    // 10 does not split evenly into 3 steps, so the points in between are rounded to 3 and 7
+    let load = Plotted::new(LoadIterator::from(Vec::<usize>::new()), 4, 0..11);
+    let ran: Vec<_> = load.collect();
+    assert_eq!(ran, alloc::vec![0, 3, 7, 10]);
+
+    // the grid does not overflow at the very top of the load range
+    let load = Plotted::new(LoadIterator::from(Vec::<usize>::new()), 3, 0..usize::MAX);
+    let ran: Vec<_> = load.collect();
+    assert_eq!(ran, alloc::vec![0, usize::MAX / 2, usize::MAX - 1]);
+}
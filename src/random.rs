@@ -0,0 +1,100 @@
+/// A source of randomness for the parts of a search that introduce it, like
+/// [`Paced::jitter`](crate::Paced::jitter).
+///
+/// This crate does not depend on any RNG crate, so that it stays `no_std` and dependency-free.
+/// Instead, any generator can be plugged in by implementing this trait, which mirrors
+/// `rand_core::RngCore::next_u64`. [`SplitMix64`] is a small built-in generator for when
+/// reproducibility matters more than the quality of the randomness.
+///
+/// ```rust
+/// use cliff::RandomSource;
+///
+/// // a wrapper around some other generator
+/// struct Counter(u64);
+/// impl RandomSource for Counter {
+///     fn next_u64(&mut self) -> u64 {
+///         self.0 += 1;
+///         self.0
+///     }
+/// }
+/// ```
+pub trait RandomSource {
+    /// Give the next random value, uniformly distributed over all of `u64`.
+    fn next_u64(&mut self) -> u64;
+
+    /// Give the seed this source started from, if it is reproducible from one.
+    ///
+    /// Searches include this in their [`Report`](crate::Report), so that a run can be replayed.
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+
+    /// Give a random value uniformly distributed in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        // the top 53 bits fill an f64 mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl<R> RandomSource for &mut R
+where
+    R: RandomSource + ?Sized,
+{
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        (**self).seed()
+    }
+}
+
+/// A small, fast, seedable generator that gives the same sequence for the same seed on every
+/// platform.
+///
+/// This is the [SplitMix64] generator. It is not suitable for anything security-related, but is
+/// plenty for spreading out benchmark runs.
+///
+///   [SplitMix64]: https://prng.di.unimi.it/splitmix64.c
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplitMix64 {
+    seed: u64,
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Create a generator that starts from `seed`.
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { seed, state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+}
+
+#[test]
+fn splitmix64() {
+    // reference values for seed 1234567 from the C implementation
+    let mut rng = SplitMix64::new(1234567);
+    assert_eq!(rng.next_u64(), 6457827717110365317);
+    assert_eq!(rng.next_u64(), 3203168211198807973);
+    assert_eq!(rng.seed(), Some(1234567));
+
+    let mut rng = SplitMix64::new(0);
+    for _ in 0..1000 {
+        let f = rng.next_f64();
+        assert!((0.0..1.0).contains(&f));
+    }
+}
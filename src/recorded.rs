@@ -0,0 +1,397 @@
+use super::{CliffSearch, Describe, Refine, Status};
+
+/// The outcome of a single benchmark run, as recorded by [`Recorded`].
+///
+/// With the `alloc` feature, the [`reason`](Sample::reason) is a `String`, so that it can be
+/// formatted at runtime. Without it, it is a `&'static str`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sample {
+    /// The load that was probed.
+    pub load: usize,
+    /// Whether the system failed to keep up with that load.
+    pub overloaded: bool,
+    /// Why the system was deemed overloaded, if that was given with
+    /// [`overloaded_because`](Recorded::overloaded_because).
+    #[cfg(feature = "alloc")]
+    pub reason: Option<alloc::string::String>,
+    /// Why the system was deemed overloaded, if that was given with
+    /// [`overloaded_because`](Recorded::overloaded_because).
+    #[cfg(not(feature = "alloc"))]
+    pub reason: Option<&'static str>,
+}
+
+impl Sample {
+    // a sample that has not been recorded yet, which fills the buffer of `Recorded`
+    const EMPTY: Sample = Sample {
+        load: 0,
+        overloaded: false,
+        reason: None,
+    };
+}
+
+/// A search that records the outcome of every benchmark run in a fixed-capacity buffer.
+///
+/// Up to `N` samples are kept, in the order they were run, without allocating, which makes this
+/// suitable for targets without `alloc`. If the search performs more than `N` runs, later samples
+/// are dropped, and [`dropped`](Recorded::dropped) says how many.
+///
+/// A sample is recorded once its outcome is known, which is when the next load is requested with
+/// [`Iterator::next`]. Runs that were marked [`inconclusive`](Recorded::inconclusive) are
+/// disregarded by the search, and so are not recorded either.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Recorded, Sample};
+///
+/// let mut load = Recorded::<_, 4>::new(ExponentialCliffSearcher::new(500));
+/// while let Some(l) = load.next() {
+///     if l > 1600 {
+///         load.overloaded();
+///     }
+/// }
+/// assert_eq!(load.samples()[2].load, 2000);
+/// assert!(load.samples()[2].overloaded);
+/// assert_eq!(load.samples().len(), 4);
+/// assert_eq!(load.dropped(), 1);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Recorded<S, const N: usize> {
+    inner: S,
+    samples: [Sample; N],
+    len: usize,
+    dropped: usize,
+    last: Option<usize>,
+    overloaded: bool,
+    #[cfg(feature = "alloc")]
+    reason: Option<alloc::string::String>,
+    #[cfg(not(feature = "alloc"))]
+    reason: Option<&'static str>,
+    inconclusive: bool,
+}
+
+impl<S, const N: usize> Recorded<S, N>
+where
+    S: CliffSearch,
+{
+    /// Record the outcome of every benchmark run performed by `search`.
+    pub fn new(search: S) -> Self {
+        Recorded {
+            inner: search,
+            samples: [Sample::EMPTY; N],
+            len: 0,
+            dropped: 0,
+            last: None,
+            overloaded: false,
+            reason: None,
+            inconclusive: false,
+        }
+    }
+
+    /// Give the samples recorded so far, in the order they were run.
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples[..self.len]
+    }
+
+    /// Give the number of samples that did not fit in the buffer.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns `true` if any samples did not fit in the buffer.
+    pub fn is_truncated(&self) -> bool {
+        self.dropped != 0
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and why.
+    ///
+    /// The reason, like `"p99 14ms > 10ms SLA"`, is kept in the recorded [`Sample`] so that it is
+    /// clear afterwards why each run failed. With the `alloc` feature, it can be anything that
+    /// converts into a `String`, like one formatted at runtime. Without it, it must be a
+    /// `&'static str`, since no allocation is done.
+    #[cfg(feature = "alloc")]
+    pub fn overloaded_because<R: Into<alloc::string::String>>(&mut self, reason: R) {
+        self.reason = Some(reason.into());
+        self.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and why.
+    ///
+    /// The reason, like `"p99 14ms > 10ms SLA"`, is kept in the recorded [`Sample`] so that it is
+    /// clear afterwards why each run failed. It must be `'static` since no allocation is done;
+    /// with the `alloc` feature, reasons can also be formatted at runtime.
+    #[cfg(not(feature = "alloc"))]
+    pub fn overloaded_because(&mut self, reason: &'static str) {
+        self.reason = Some(reason);
+        self.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// The run is recorded as sustained.
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        // a run that never happened is not recorded
+        self.inconclusive = true;
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall is recorded as an overload.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+        self.inner.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, const N: usize> CliffSearch for Recorded<S, N>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Recorded::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Recorded::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Recorded::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Recorded::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Recorded::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Recorded::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Recorded::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.probes_left()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S, const N: usize> Refine for Recorded<S, N>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S, const N: usize> Iterator for Recorded<S, N>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(load) = self.last.take() {
+            if !self.inconclusive {
+                let sample = Sample {
+                    load,
+                    overloaded: self.overloaded,
+                    reason: self.reason.take(),
+                };
+                if self.len < N {
+                    self.samples[self.len] = sample;
+                    self.len += 1;
+                } else {
+                    self.dropped += 1;
+                }
+            }
+        }
+        self.overloaded = false;
+        self.reason = None;
+        self.inconclusive = false;
+
+        self.last = self.inner.next();
+        self.last
+    }
+}
+
+impl<S, const N: usize> Describe for Recorded<S, N>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "recorded"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("capacity", &N);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn record() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Recorded::<_, 8>::new(ExponentialCliffSearcher::new(500));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.achieved(1700);
+    assert_eq!(scale.next(), Some(1350));
+    scale.overloaded_by(1.1);
+    assert_eq!(scale.next(), Some(1175));
+    assert_eq!(
+        scale.samples(),
+        &[
+            Sample {
+                load: 500,
+                overloaded: false,
+                reason: None,
+            },
+            Sample {
+                load: 1000,
+                overloaded: false,
+                reason: None,
+            },
+            Sample {
+                load: 2000,
+                overloaded: true,
+                reason: None,
+            },
+            Sample {
+                load: 1350,
+                overloaded: true,
+                reason: None,
+            },
+        ]
+    );
+    assert!(!scale.is_truncated());
+}
+
+#[test]
+fn truncated() {
+    use super::BinaryMinSearcher;
+
+    let mut scale = Recorded::<_, 0>::new(BinaryMinSearcher::until(1024, 64));
+    while scale.next().is_some() {}
+    assert_eq!(scale.samples(), &[]);
+    assert_eq!(scale.dropped(), 5);
+    assert!(scale.is_truncated());
+}
+
+#[test]
+// the reasons are `&'static str`s already without `alloc`
+#[allow(clippy::needless_option_as_deref)]
+fn reasons() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Recorded::<_, 4>::new(ExponentialCliffSearcher::new(500));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded_because("p99 14ms > 10ms SLA");
+    assert_eq!(scale.next(), Some(750));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    let reasons: [_; 3] = [
+        scale.samples()[0].reason.as_deref(),
+        scale.samples()[1].reason.as_deref(),
+        scale.samples()[2].reason.as_deref(),
+    ];
+    assert_eq!(reasons, [None, Some("p99 14ms > 10ms SLA"), None]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn formatted_reasons() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Recorded::<_, 4>::new(ExponentialCliffSearcher::new(500));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    let p99 = 14;
+    scale.overloaded_because(alloc::format!("p99 {}ms > 10ms SLA", p99));
+    assert_eq!(scale.next(), Some(750));
+    assert_eq!(
+        scale.samples()[1].reason.as_deref(),
+        Some("p99 14ms > 10ms SLA")
+    );
+}
@@ -0,0 +1,281 @@
+use super::{CliffSearch, Describe, Refine, Sample, Status};
+use alloc::vec::Vec;
+
+/// A search that records the load and outcome of every benchmark run it performs.
+///
+/// This is the growable counterpart to [`Recorded`](crate::Recorded): every sample is kept, so
+/// that once the search is done, each load that was tried can be plotted along with whether the
+/// system kept up with it, and not just the final estimate.
+///
+/// As with `Recorded`, a sample is recorded once its outcome is known, which is when the next
+/// load is requested with [`Iterator::next`], and runs that were marked
+/// [`inconclusive`](RecordingSearcher::inconclusive) or skipped are not recorded.
+///
+/// This type is only available with the `alloc` feature.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, RecordingSearcher};
+///
+/// let mut load = RecordingSearcher::new(ExponentialCliffSearcher::new(500));
+/// while let Some(l) = load.next() {
+///     if l > 1600 {
+///         load.overloaded();
+///     }
+/// }
+/// let tried: Vec<_> = load.history().iter().map(|s| (s.load, s.overloaded)).collect();
+/// assert_eq!(
+///     tried,
+///     [(500, false), (1000, false), (2000, true), (1500, false), (1750, true)]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct RecordingSearcher<S> {
+    inner: S,
+    history: Vec<Sample>,
+    last: Option<usize>,
+    overloaded: bool,
+    reason: Option<alloc::string::String>,
+    inconclusive: bool,
+}
+
+impl<S> RecordingSearcher<S>
+where
+    S: CliffSearch,
+{
+    /// Record the outcome of every benchmark run performed by `search`.
+    pub fn new(search: S) -> Self {
+        RecordingSearcher {
+            inner: search,
+            history: Vec::new(),
+            last: None,
+            overloaded: false,
+            reason: None,
+            inconclusive: false,
+        }
+    }
+
+    /// Give every sample recorded so far, in the order they were run.
+    pub fn history(&self) -> &[Sample] {
+        &self.history
+    }
+
+    /// Give back the wrapped search along with every sample recorded.
+    pub fn into_parts(self) -> (S, Vec<Sample>) {
+        (self.inner, self.history)
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and why.
+    ///
+    /// The reason, like `"p99 14ms > 10ms SLA"`, is kept in the recorded [`Sample`] so that it is
+    /// clear afterwards why each run failed. It can be anything that converts into a `String`,
+    /// like `format!("p99 {}ms > {}ms SLA", p99, sla)`.
+    pub fn overloaded_because<R: Into<alloc::string::String>>(&mut self, reason: R) {
+        self.reason = Some(reason.into());
+        self.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// The run is recorded as sustained.
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        // a run that never happened is not recorded
+        self.inconclusive = true;
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall is recorded as an overload.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+        self.inner.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> CliffSearch for RecordingSearcher<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        RecordingSearcher::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        RecordingSearcher::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        RecordingSearcher::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        RecordingSearcher::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        RecordingSearcher::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        RecordingSearcher::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        RecordingSearcher::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.probes_left()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for RecordingSearcher<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for RecordingSearcher<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(load) = self.last.take() {
+            if !self.inconclusive {
+                let sample = Sample {
+                    load,
+                    overloaded: self.overloaded,
+                    reason: self.reason.take(),
+                };
+                self.history.push(sample);
+            }
+        }
+        self.overloaded = false;
+        self.reason = None;
+        self.inconclusive = false;
+
+        self.last = self.inner.next();
+        self.last
+    }
+}
+
+impl<S> Describe for RecordingSearcher<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn history() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = RecordingSearcher::new(ExponentialCliffSearcher::new(500));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    scale.overloaded_because(alloc::format!("p99 {}ms > 10ms SLA", 14));
+    assert_eq!(scale.next(), Some(1500));
+    let (_, history) = scale.into_parts();
+    assert_eq!(
+        history,
+        [
+            Sample {
+                load: 500,
+                overloaded: false,
+                reason: None,
+            },
+            Sample {
+                load: 1000,
+                overloaded: false,
+                reason: None,
+            },
+            Sample {
+                load: 2000,
+                overloaded: true,
+                reason: Some("p99 14ms > 10ms SLA".into()),
+            },
+        ]
+    );
+}
@@ -0,0 +1,392 @@
+use super::{CliffSearch, Describe, Refine, Status};
+
+/// How the repeated runs of a single load came out.
+///
+/// See [`Repeated`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Votes {
+    /// The number of runs in which the system kept up.
+    pub sustained: usize,
+    /// The number of runs in which the system was overloaded.
+    pub overloaded: usize,
+}
+
+impl Votes {
+    /// Returns `true` if every run came out the same way.
+    pub fn is_unanimous(&self) -> bool {
+        self.sustained == 0 || self.overloaded == 0
+    }
+
+    /// Returns `true` if the runs disagreed, like a 2-1 vote.
+    pub fn is_split(&self) -> bool {
+        !self.is_unanimous()
+    }
+
+    fn total(&self) -> usize {
+        self.sustained + self.overloaded
+    }
+}
+
+/// The votes behind the current bounds of the estimate of a [`Repeated`] search.
+///
+/// A bound is `None` if it has not been established by a vote, like the upper bound while an
+/// [`ExponentialCliffSearcher`](crate::ExponentialCliffSearcher) is still ramping up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BoundaryVotes {
+    /// The votes for the load that established the lower bound of the estimate.
+    pub lower: Option<Votes>,
+    /// The votes for the load that established the upper bound of the estimate.
+    pub upper: Option<Votes>,
+}
+
+/// A search that runs every load several times, and goes with the majority.
+///
+/// Each load yielded by the wrapped search is yielded `runs` times in a row, and the wrapped
+/// search is told the outcome that most runs agreed on. A tie, which can only happen with an even
+/// number of runs, is taken as [inconclusive](CliffSearch::inconclusive). Inconclusive runs do
+/// not count towards the vote, and are simply run again.
+///
+/// A cliff that was established by split votes is marginal: the system is close to its limit at
+/// the boundary, and the estimate may not hold up. [`boundary_votes`](Repeated::boundary_votes)
+/// and [`is_marginal`](Repeated::is_marginal) tell whether that was the case, so that a longer
+/// confirmation run can be scheduled rather than publishing a shaky number.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Repeated};
+///
+/// let mut load = Repeated::new(ExponentialCliffSearcher::new(500), 3);
+/// let mut flaked = false;
+/// while let Some(l) = load.next() {
+///     // one of the runs at 1500 happens to fall over
+///     if l > 1600 || (l == 1500 && !std::mem::replace(&mut flaked, true)) {
+///         load.overloaded();
+///     }
+/// }
+/// assert_eq!(load.estimate(), 1500..1750);
+///
+/// let votes = load.boundary_votes();
+/// assert_eq!(votes.lower.map(|v| (v.sustained, v.overloaded)), Some((2, 1)));
+/// assert!(votes.upper.unwrap().is_unanimous());
+/// assert!(load.is_marginal());
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Repeated<S> {
+    inner: S,
+    runs: usize,
+    votes: Votes,
+    bounds: BoundaryVotes,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+    marginal: bool,
+    // how many of the sustained votes for the current load were marginal
+    marginals: usize,
+    skipped: bool,
+}
+
+impl<S> Repeated<S>
+where
+    S: CliffSearch,
+{
+    /// Run every load yielded by `search` `runs` times, and go with the majority.
+    ///
+    /// An odd number of runs avoids ties. A `runs` of `0` is treated as `1`.
+    pub fn new(search: S, runs: usize) -> Self {
+        Repeated {
+            inner: search,
+            runs: runs.max(1),
+            votes: Votes::default(),
+            bounds: BoundaryVotes::default(),
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+            marginal: false,
+            marginals: 0,
+            skipped: false,
+        }
+    }
+
+    /// Give the votes for the loads that established the current bounds of the estimate.
+    pub fn boundary_votes(&self) -> BoundaryVotes {
+        self.bounds
+    }
+
+    /// Returns `true` if either bound of the estimate was established by a split vote.
+    pub fn is_marginal(&self) -> bool {
+        let split = |v: Option<Votes>| v.map_or(false, |v| v.is_split());
+        split(self.bounds.lower) || split(self.bounds.upper)
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// The run counts as a vote for overloaded, but the factor is not passed on.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        let _ = factor;
+        self.overloaded = true;
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// The run does not count towards the vote, and is run again.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// The run counts as a vote for the load being sustained. If the majority goes that way, and
+    /// at least half of the runs that kept up were marginal, the wrapped search is told that the
+    /// load was marginal.
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.marginal = true;
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// Any votes for it so far are dropped, and the wrapped search is told right away.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.skipped = true;
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall counts as a vote for overloaded.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> CliffSearch for Repeated<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Repeated::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Repeated::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Repeated::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Repeated::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Repeated::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Repeated::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Repeated::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner
+            .probes_left()
+            .map(|loads| loads.saturating_mul(self.runs))
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for Repeated<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for Repeated<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut decided = None;
+        if let Some(load) = self.last {
+            if core::mem::replace(&mut self.skipped, false) {
+                self.votes = Votes::default();
+                self.marginals = 0;
+            } else {
+                if !self.inconclusive {
+                    if self.overloaded {
+                        self.votes.overloaded += 1;
+                    } else {
+                        self.votes.sustained += 1;
+                        self.marginals += self.marginal as usize;
+                    }
+                }
+                self.overloaded = false;
+                self.inconclusive = false;
+                self.marginal = false;
+                if self.votes.total() < self.runs {
+                    return Some(load);
+                }
+
+                let votes = core::mem::take(&mut self.votes);
+                let marginals = core::mem::take(&mut self.marginals);
+                match votes.overloaded.cmp(&votes.sustained) {
+                    core::cmp::Ordering::Greater => self.inner.overloaded(),
+                    core::cmp::Ordering::Less if 2 * marginals >= votes.sustained => {
+                        self.inner.marginal()
+                    }
+                    core::cmp::Ordering::Less => {}
+                    core::cmp::Ordering::Equal => self.inner.inconclusive(),
+                }
+                if votes.overloaded != votes.sustained {
+                    decided = Some((load, votes));
+                }
+            }
+        }
+        self.overloaded = false;
+        self.inconclusive = false;
+        self.marginal = false;
+
+        // the inner search takes the verdict into account when asked for the next load, so
+        // that's when we can see which bounds it moved.
+        let before = self.inner.estimate();
+        self.last = self.inner.next();
+        let after = self.inner.estimate();
+        if let Some((load, votes)) = decided {
+            if after.start != before.start || after.start == load {
+                self.bounds.lower = Some(votes);
+            }
+            if after.end != before.end || after.end == load {
+                self.bounds.upper = Some(votes);
+            }
+        }
+        self.last
+    }
+}
+
+impl<S> Describe for Repeated<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "repeated"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("runs", &self.runs);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn tie() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Repeated::new(ExponentialCliffSearcher::new(500), 2);
+    assert_eq!(scale.next(), Some(500));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1000));
+    // a 1-1 tie is run again from scratch
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(750));
+    assert_eq!(scale.estimate(), 500..1000);
+    let votes = scale.boundary_votes();
+    assert_eq!(
+        votes.lower,
+        Some(Votes {
+            sustained: 2,
+            overloaded: 0
+        })
+    );
+    assert_eq!(
+        votes.upper,
+        Some(Votes {
+            sustained: 0,
+            overloaded: 2
+        })
+    );
+    assert!(!scale.is_marginal());
+}
+
+#[test]
+fn marginal() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Repeated::new(ExponentialCliffSearcher::until(500, 100), 3);
+    assert_eq!(scale.next(), Some(500));
+    scale.marginal();
+    assert_eq!(scale.next(), Some(500));
+    scale.marginal();
+    assert_eq!(scale.next(), Some(500));
+    // most runs only just kept up, so the wrapped search steps just above 500 rather than doubling
+    assert_eq!(scale.next(), Some(600));
+    scale.marginal();
+    assert_eq!(scale.next(), Some(600));
+    assert_eq!(scale.next(), Some(600));
+    // a single marginal run out of three is not enough
+    assert_eq!(scale.next(), Some(1200));
+}
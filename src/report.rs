@@ -0,0 +1,886 @@
+#[cfg(feature = "alloc")]
+use crate::{json, Describe};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The outcome of running a search to completion.
+///
+/// See [`CliffSearch::run_to_completion`](crate::CliffSearch::run_to_completion).
+///
+/// With the `alloc` feature, reports can be written out as JSON with
+/// [`to_json`](Report::to_json), and read back with [`parse`](Report::parse). Every JSON report
+/// carries a `schema_version`, and `parse` upgrades reports written with older versions of the
+/// schema, so that reports kept around for a long time remain readable.
+///
+/// With the `alloc` feature, a report also has a [`timeline`](Report::timeline) of how the
+/// estimate narrowed with every probe, which shows how quickly the search converged.
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use cliff::{CliffSearch, ExponentialCliffSearcher, Report};
+///
+/// let report = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1600);
+/// let estimates: Vec<_> = report.timeline.iter().map(|s| s.estimate.clone()).collect();
+/// assert_eq!(
+///     estimates,
+///     [500..usize::MAX, 1000..usize::MAX, 1000..2000, 1500..2000, 1500..1750]
+/// );
+///
+/// let json = report.to_json();
+/// assert!(json.starts_with(
+///     r#"{"schema_version":7,"estimate":{"start":1500,"end":1750},"probes":5,"overloaded":2,"status":"completed","timeline":[{"load":500,"overloaded":false,"estimate":{"start":500,"end":null}},"#
+/// ));
+/// assert_eq!(Report::parse(&json), Ok(report));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+#[must_use = "a report is only useful if its estimate is looked at"]
+pub struct Report {
+    /// The final estimate of the search.
+    ///
+    /// This converts into an [`Estimate`](crate::Estimate) for further analysis.
+    pub estimate: Range<usize>,
+    /// The number of benchmark runs the search performed.
+    pub probes: usize,
+    /// The number of benchmark runs that overloaded the system.
+    pub overloaded: usize,
+    /// The number of benchmark runs whose outcome was inconclusive.
+    ///
+    /// These runs are not counted in [`probes`](Report::probes), and are not in the
+    /// [`timeline`](Report::timeline), since they did not move the estimate. They did take time
+    /// and load the system, though, so they count towards the cost of the search. Only
+    /// [`run_with_verdicts`](crate::CliffSearch::run_with_verdicts) records inconclusive runs.
+    pub inconclusive: usize,
+    /// How the search ended.
+    ///
+    /// Anything but [`Status::Completed`] means that the estimate should not be trusted.
+    pub status: Status,
+    /// The seed of the random source the search used, if any.
+    ///
+    /// See [`CliffSearch::seed`](crate::CliffSearch::seed).
+    pub seed: Option<u64>,
+    /// The ID of the search, if it had one.
+    ///
+    /// See [`CliffSearch::search_id`](crate::CliffSearch::search_id).
+    pub search_id: Option<u64>,
+    /// The settings of every layer of the search, as recorded by
+    /// [`record_config`](Report::record_config).
+    ///
+    /// Each setting is named by the layer it belongs to and its own name, like
+    /// `"repeated.runs"`, and the settings are in the order of the layers, from the outermost
+    /// inwards.
+    ///
+    /// This field is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub config: Vec<(String, String)>,
+    /// Anything that happened during the search that does not invalidate its estimate outright,
+    /// but that calls for a closer look, like the environment drifting under a
+    /// [`Canary`](crate::Canary).
+    ///
+    /// This field is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub warnings: Vec<String>,
+    /// Every benchmark run the search performed, in order, along with the estimate right after
+    /// the search took its outcome into account.
+    ///
+    /// This field is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub timeline: Vec<Snapshot>,
+}
+
+impl Report {
+    /// The version of the JSON schema written by [`to_json`](Report::to_json).
+    ///
+    /// This is bumped whenever the schema changes, and [`parse`](Report::parse) accepts reports
+    /// with any version up to and including this one.
+    pub const SCHEMA_VERSION: u64 = 7;
+
+    pub(crate) fn new() -> Self {
+        Report {
+            estimate: 0..0,
+            probes: 0,
+            overloaded: 0,
+            inconclusive: 0,
+            status: Status::Completed,
+            seed: None,
+            search_id: None,
+            #[cfg(feature = "alloc")]
+            config: Vec::new(),
+            #[cfg(feature = "alloc")]
+            warnings: Vec::new(),
+            #[cfg(feature = "alloc")]
+            timeline: Vec::new(),
+        }
+    }
+
+    // count a benchmark run at `load`, whose outcome the search has been told about
+    pub(crate) fn probed(&mut self, load: usize, overloaded: bool) {
+        self.probes += 1;
+        if overloaded {
+            self.overloaded += 1;
+        }
+        #[cfg(feature = "alloc")]
+        self.timeline.push(Snapshot {
+            load,
+            overloaded,
+            estimate: self.estimate.clone(),
+        });
+        #[cfg(not(feature = "alloc"))]
+        let _ = load;
+    }
+
+    // count a benchmark run whose outcome the search has been told was inconclusive
+    pub(crate) fn inconclusive_probe(&mut self) {
+        self.inconclusive += 1;
+    }
+
+    /// The number of benchmark runs the system kept up with.
+    ///
+    /// Together with [`overloaded_probes`](Report::overloaded_probes) and
+    /// [`inconclusive_probes`](Report::inconclusive_probes), this gives what the search cost, and
+    /// how much of that was spent pushing the system past what it could take.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher};
+    ///
+    /// let report = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1600);
+    /// assert_eq!(report.sustained_probes(), 3);
+    /// assert_eq!(report.overloaded_probes(), 2);
+    /// assert_eq!(report.inconclusive_probes(), 0);
+    /// ```
+    pub fn sustained_probes(&self) -> usize {
+        self.probes.saturating_sub(self.overloaded)
+    }
+
+    /// The number of benchmark runs that overloaded the system.
+    ///
+    /// This is the same as [`overloaded`](Report::overloaded).
+    pub fn overloaded_probes(&self) -> usize {
+        self.overloaded
+    }
+
+    /// The number of benchmark runs whose outcome was inconclusive.
+    ///
+    /// This is the same as [`inconclusive`](Report::inconclusive).
+    pub fn inconclusive_probes(&self) -> usize {
+        self.inconclusive
+    }
+
+    // record the estimate of the search, which has taken the outcome of the last run into
+    // account once it has been asked for the next load.
+    pub(crate) fn settle(&mut self, estimate: Range<usize>) {
+        #[cfg(feature = "alloc")]
+        if let Some(last) = self.timeline.last_mut() {
+            last.estimate = estimate.clone();
+        }
+        self.estimate = estimate;
+    }
+
+    /// Record the effective configuration of `search` in [`config`](Report::config), replacing
+    /// whatever was recorded before.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, ExponentialCliffSearcher, Repeated};
+    ///
+    /// let search = Repeated::new(ExponentialCliffSearcher::until(500, 100), 3);
+    /// let mut report = search.clone().run_to_completion(|load| load <= 1600);
+    /// report.record_config(&search);
+    /// assert_eq!(report.config[0], ("repeated.runs".to_string(), "3".to_string()));
+    /// assert_eq!(report.config[1], ("exponential.min_width".to_string(), "100".to_string()));
+    /// ```
+    ///
+    /// This method is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn record_config<D: Describe>(&mut self, search: &D) {
+        use alloc::string::ToString;
+
+        self.config.clear();
+        for layer in search.describe() {
+            let name = layer.name();
+            layer.settings(&mut |key, value| {
+                self.config
+                    .push((alloc::format!("{}.{}", name, key), value.to_string()));
+            });
+        }
+    }
+
+    /// Check that the estimate follows from the outcomes recorded in the [`timeline`].
+    ///
+    /// This re-derives the estimate from scratch: the highest load that was sustained is the
+    /// lower bound, and the lowest load that overloaded the system is the upper bound. That catches
+    /// harness bugs, like a forgotten call to [`overloaded`](crate::CliffSearch::overloaded),
+    /// before the numbers are published. The checks are:
+    ///
+    /// - the timeline covers every probe and overload counted in the report, and so cannot be
+    ///   missing, as it is for reports written before version 3 of the JSON schema;
+    /// - no load was sustained at or above a load that overloaded the system, which is reported
+    ///   as [`Error::Contradiction`](crate::Error::Contradiction);
+    /// - the estimate matches the re-derived bounds. A lower bound that was never probed, like
+    ///   one given to [`ExponentialCliffSearcher::within`](crate::ExponentialCliffSearcher::within),
+    ///   is taken on trust.
+    ///
+    /// This assumes a search for a maximum load. The estimate of a
+    /// [`BinaryMinSearcher`](crate::BinaryMinSearcher) does not follow these rules.
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, Error, ExponentialCliffSearcher};
+    ///
+    /// let mut report = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1600);
+    /// assert_eq!(report.validate(), Ok(()));
+    ///
+    /// // the run at 1500 was in fact overloaded, but the harness dropped that on the floor
+    /// report.timeline[3].overloaded = true;
+    /// report.overloaded += 1;
+    /// assert_eq!(
+    ///     report.validate(),
+    ///     Err(Error::Inconsistent("the estimate does not follow from the probe outcomes"))
+    /// );
+    /// ```
+    ///
+    /// This method is only available with the `alloc` feature.
+    ///
+    /// [`timeline`]: Report::timeline
+    #[cfg(feature = "alloc")]
+    pub fn validate(&self) -> Result<(), crate::Error> {
+        use crate::Error;
+
+        if self.timeline.len() != self.probes {
+            return Err(Error::Inconsistent(
+                "the timeline does not cover every probe",
+            ));
+        }
+        if self.timeline.iter().filter(|s| s.overloaded).count() != self.overloaded {
+            return Err(Error::Inconsistent(
+                "the overload count does not match the timeline",
+            ));
+        }
+
+        if self.timeline.is_empty() {
+            // with no probes, there is nothing to derive the estimate from
+            return Ok(());
+        }
+
+        let sustained = self
+            .timeline
+            .iter()
+            .filter(|s| !s.overloaded)
+            .map(|s| s.load)
+            .max();
+        let overloaded = self
+            .timeline
+            .iter()
+            .filter(|s| s.overloaded)
+            .map(|s| s.load)
+            .min();
+        if let (Some(supported), Some(overloaded)) = (sustained, overloaded) {
+            if supported >= overloaded {
+                return Err(Error::Contradiction {
+                    supported,
+                    overloaded,
+                });
+            }
+        }
+
+        let end = overloaded.unwrap_or(usize::MAX);
+        let start_ok = match sustained {
+            Some(start) => self.estimate.start == start,
+            None => self.estimate.start <= end,
+        };
+        if self.estimate.end != end || !start_ok {
+            return Err(Error::Inconsistent(
+                "the estimate does not follow from the probe outcomes",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Classify how the cliff moved from `baseline` to this report.
+    ///
+    /// The comparison uses the estimates of the two reports rather than single points, so that
+    /// differences within the precision of the searches do not count as changes. This report has
+    /// [regressed](Comparison::Regressed) only if its entire estimate lies more than a fraction
+    /// `min_effect` below the baseline's estimate, and [improved](Comparison::Improved) only if it
+    /// lies more than that fraction above it. Overlapping estimates are always
+    /// [unchanged](Comparison::Unchanged).
+    ///
+    /// ```rust
+    /// use cliff::{CliffSearch, Comparison, ExponentialCliffSearcher};
+    ///
+    /// let baseline = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1600);
+    /// let noisy = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1450);
+    /// let slower = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1000);
+    /// assert_eq!(noisy.compare(&baseline, 0.05), Comparison::Unchanged);
+    /// assert_eq!(slower.compare(&baseline, 0.05), Comparison::Regressed);
+    /// assert_eq!(baseline.compare(&slower, 0.05), Comparison::Improved);
+    /// ```
+    pub fn compare(&self, baseline: &Report, min_effect: f64) -> Comparison {
+        let this = &self.estimate;
+        let base = &baseline.estimate;
+        if (this.end as f64) < base.start as f64 * (1.0 - min_effect) {
+            Comparison::Regressed
+        } else if base.end != usize::MAX && this.start as f64 > base.end as f64 * (1.0 + min_effect)
+        {
+            Comparison::Improved
+        } else {
+            Comparison::Unchanged
+        }
+    }
+
+    /// Write this report as a single line of JSON.
+    ///
+    /// An estimate without an upper bound has an `end` of `null`. The `inconclusive` count is left
+    /// out if there were no inconclusive runs, and the `seed` and `search_id` if there is none.
+    /// The `config` is an object of strings, and `warnings` an array of strings, and either is
+    /// left out if nothing was recorded. The `timeline` is an array of objects with a `load`,
+    /// whether it was `overloaded`, and the `estimate` after it. Numbers are written as described
+    /// under [output formats](crate#output-formats).
+    ///
+    /// This method is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn write_json(&self, out: &mut String) {
+        use core::fmt::Write;
+
+        fn range(out: &mut String, r: &Range<usize>) {
+            let _ = write!(out, r#"{{"start":{},"end":"#, r.start);
+            if r.end == usize::MAX {
+                out.push_str("null}");
+            } else {
+                let _ = write!(out, "{}}}", r.end);
+            }
+        }
+
+        let _ = write!(
+            out,
+            r#"{{"schema_version":{},"estimate":"#,
+            Self::SCHEMA_VERSION
+        );
+        range(out, &self.estimate);
+        let _ = write!(
+            out,
+            r#","probes":{},"overloaded":{},"status":"{}""#,
+            self.probes,
+            self.overloaded,
+            self.status.as_str()
+        );
+        if self.inconclusive != 0 {
+            let _ = write!(out, r#","inconclusive":{}"#, self.inconclusive);
+        }
+        if let Some(seed) = self.seed {
+            let _ = write!(out, r#","seed":{}"#, seed);
+        }
+        if let Some(id) = self.search_id {
+            let _ = write!(out, r#","search_id":{}"#, id);
+        }
+        if !self.config.is_empty() {
+            out.push_str(r#","config":{"#);
+            for (i, (key, value)) in self.config.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                let _ = json::write_string(out, key);
+                out.push(':');
+                let _ = json::write_string(out, value);
+            }
+            out.push('}');
+        }
+        if !self.warnings.is_empty() {
+            out.push_str(r#","warnings":["#);
+            for (i, warning) in self.warnings.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                let _ = json::write_string(out, warning);
+            }
+            out.push(']');
+        }
+        out.push_str(r#","timeline":["#);
+        for (i, s) in self.timeline.iter().enumerate() {
+            if i != 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                r#"{{"load":{},"overloaded":{},"estimate":"#,
+                s.load, s.overloaded
+            );
+            range(out, &s.estimate);
+            out.push('}');
+        }
+        out.push_str("]}");
+    }
+
+    /// Read a report written by [`to_json`](Report::to_json), possibly by an older version of
+    /// this crate.
+    ///
+    /// This method is only available with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        Self::from_json(&json::parse(s).map_err(ParseError::Syntax)?)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_json(v: &json::Value) -> Result<Self, ParseError> {
+        let version = v
+            .get("schema_version")
+            .ok_or(ParseError::Missing("schema_version"))?
+            .as_u64()
+            .ok_or(ParseError::Invalid("schema_version"))?;
+        // when the schema changes, upgrade older versions here, one version at a time
+        let status = match version {
+            // version 1 predates `status`, and every search it recorded ran to completion
+            1 => Status::Completed,
+            2..=7 => v
+                .get("status")
+                .ok_or(ParseError::Missing("status"))?
+                .as_str()
+                .and_then(Status::from_str)
+                .ok_or(ParseError::Invalid("status"))?,
+            _ => return Err(ParseError::UnsupportedVersion(version)),
+        };
+
+        let field = |v: &json::Value, name: &'static str| -> Result<usize, ParseError> {
+            v.get(name)
+                .ok_or(ParseError::Missing(name))?
+                .as_usize()
+                .ok_or(ParseError::Invalid(name))
+        };
+        let range = |v: &json::Value| -> Result<Range<usize>, ParseError> {
+            let estimate = v.get("estimate").ok_or(ParseError::Missing("estimate"))?;
+            let start = field(estimate, "start")?;
+            let end = match estimate.get("end") {
+                Some(end) if end.is_null() => usize::MAX,
+                _ => field(estimate, "end")?,
+            };
+            Ok(start..end)
+        };
+        // versions before 3 predate `timeline`
+        let mut timeline = Vec::new();
+        if version >= 3 {
+            let snapshots = v
+                .get("timeline")
+                .ok_or(ParseError::Missing("timeline"))?
+                .as_array()
+                .ok_or(ParseError::Invalid("timeline"))?;
+            for s in snapshots {
+                timeline.push(Snapshot {
+                    load: field(s, "load")?,
+                    overloaded: s
+                        .get("overloaded")
+                        .ok_or(ParseError::Missing("overloaded"))?
+                        .as_bool()
+                        .ok_or(ParseError::Invalid("overloaded"))?,
+                    estimate: range(s)?,
+                });
+            }
+        }
+        Ok(Report {
+            estimate: range(v)?,
+            probes: field(v, "probes")?,
+            overloaded: field(v, "overloaded")?,
+            // versions before 6 predate `inconclusive`, and it is left out if there were none
+            inconclusive: match v.get("inconclusive") {
+                Some(_) => field(v, "inconclusive")?,
+                None => 0,
+            },
+            status,
+            seed: match v.get("seed") {
+                Some(seed) => Some(seed.as_u64().ok_or(ParseError::Invalid("seed"))?),
+                None => None,
+            },
+            // versions before 4 predate `search_id`, and so have none
+            search_id: match v.get("search_id") {
+                Some(id) => Some(id.as_u64().ok_or(ParseError::Invalid("search_id"))?),
+                None => None,
+            },
+            // versions before 5 predate `config`, and so have none
+            config: match v.get("config") {
+                Some(config) => config
+                    .as_object()
+                    .ok_or(ParseError::Invalid("config"))?
+                    .iter()
+                    .map(|(k, v)| {
+                        let v = v.as_str().ok_or(ParseError::Invalid("config"))?;
+                        Ok((k.clone(), String::from(v)))
+                    })
+                    .collect::<Result<_, _>>()?,
+                None => Vec::new(),
+            },
+            // versions before 7 predate `warnings`, and so have none
+            warnings: match v.get("warnings") {
+                Some(warnings) => warnings
+                    .as_array()
+                    .ok_or(ParseError::Invalid("warnings"))?
+                    .iter()
+                    .map(|w| {
+                        w.as_str()
+                            .map(String::from)
+                            .ok_or(ParseError::Invalid("warnings"))
+                    })
+                    .collect::<Result<_, _>>()?,
+                None => Vec::new(),
+            },
+            timeline,
+        })
+    }
+}
+
+/// A benchmark run in the [`timeline`](Report::timeline) of a report.
+///
+/// This type is only available with the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Snapshot {
+    /// The load that was probed.
+    pub load: usize,
+    /// Whether the system failed to keep up with that load.
+    pub overloaded: bool,
+    /// The estimate of the search right after it took the outcome of this run into account.
+    pub estimate: Range<usize>,
+}
+
+/// How a search ended.
+///
+/// See [`CliffSearch::status`](crate::CliffSearch::status).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Status {
+    /// The search ran its course, and its estimate can be trusted as far as the benchmark can.
+    Completed,
+    /// The system kept up with every load up to the search's sanity cap, which usually means
+    /// that the benchmark is not actually loading it.
+    ///
+    /// See [`ExponentialCliffSearcher::sanity_cap`](crate::ExponentialCliffSearcher::sanity_cap).
+    NeverOverloaded,
+    /// The system could not keep up with even the lowest load the search tried, which usually
+    /// means that the benchmark itself is broken.
+    ///
+    /// The estimate then only says that the cliff lies below that load, which should not be
+    /// mistaken for a measurement.
+    NothingSustained,
+    /// The search was [cancelled](crate::Cancel) before it finished, so the estimate is only as
+    /// narrow as the search got.
+    Cancelled,
+    /// No value up to the search's cap was sufficient, so the minimum lies beyond what can be
+    /// set at all.
+    ///
+    /// See [`BinaryMinSearcher::escalate`](crate::BinaryMinSearcher::escalate).
+    Unviable,
+    /// The system could not keep up with the trivial load of a smoke probe run before the search
+    /// started, so the harness or the system itself is broken.
+    ///
+    /// See [`Smoke`](crate::Smoke).
+    SmokeFailed,
+}
+
+impl Status {
+    #[cfg(feature = "alloc")]
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Completed => "completed",
+            Status::NeverOverloaded => "never_overloaded",
+            Status::NothingSustained => "nothing_sustained",
+            Status::Cancelled => "cancelled",
+            Status::Unviable => "unviable",
+            Status::SmokeFailed => "smoke_failed",
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "completed" => Some(Status::Completed),
+            "never_overloaded" => Some(Status::NeverOverloaded),
+            "nothing_sustained" => Some(Status::NothingSustained),
+            "cancelled" => Some(Status::Cancelled),
+            "unviable" => Some(Status::Unviable),
+            "smoke_failed" => Some(Status::SmokeFailed),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Status::Completed
+    }
+}
+
+/// How the cliff moved between two reports.
+///
+/// See [`Report::compare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// The cliff is clearly higher than in the baseline.
+    Improved,
+    /// The cliff is clearly lower than in the baseline.
+    Regressed,
+    /// The cliff has not moved by more than the precision of the searches and the minimum effect
+    /// size.
+    Unchanged,
+}
+
+/// An error encountered when reading a report with [`Report::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The input is not valid JSON; the error was found at the given byte offset.
+    Syntax(usize),
+    /// The report lacks a required field.
+    Missing(&'static str),
+    /// A field of the report has a value of the wrong type or range.
+    Invalid(&'static str),
+    /// The report (or [campaign](crate::campaign::Campaign)) was written with a schema version
+    /// this version of the crate does not know.
+    UnsupportedVersion(u64),
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::Syntax(at) => write!(f, "invalid JSON at byte {}", at),
+            ParseError::Missing(field) => write!(f, "missing field `{}`", field),
+            ParseError::Invalid(field) => write!(f, "invalid value for field `{}`", field),
+            ParseError::UnsupportedVersion(v) => write!(f, "unsupported schema version {}", v),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// The outcome of seeding a search with historical results.
+///
+/// See [`ExponentialCliffSearcher::warm_start`](crate::ExponentialCliffSearcher::warm_start).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+#[must_use = "historical results may have been ignored"]
+pub struct WarmStart {
+    /// The number of historical results that were consistent with each other.
+    pub consistent: usize,
+    /// The number of historical results that contradicted the others, and so were ignored.
+    pub inconsistent: usize,
+}
+
+#[test]
+fn compare() {
+    let report = |estimate: Range<usize>| {
+        let mut r = Report::new();
+        r.estimate = estimate;
+        r
+    };
+    let base = report(1000..1100);
+    assert_eq!(
+        report(1050..1150).compare(&base, 0.0),
+        Comparison::Unchanged
+    );
+    assert_eq!(report(900..1000).compare(&base, 0.0), Comparison::Unchanged);
+    assert_eq!(report(900..999).compare(&base, 0.0), Comparison::Regressed);
+    assert_eq!(report(900..960).compare(&base, 0.05), Comparison::Unchanged);
+    assert_eq!(report(900..940).compare(&base, 0.05), Comparison::Regressed);
+    assert_eq!(report(1101..1200).compare(&base, 0.0), Comparison::Improved);
+    assert_eq!(
+        report(1101..1200).compare(&base, 0.05),
+        Comparison::Unchanged
+    );
+    // an unbounded estimate can't be beaten
+    let base = report(1000..usize::MAX);
+    assert_eq!(
+        report(5000..6000).compare(&base, 0.0),
+        Comparison::Unchanged
+    );
+    assert_eq!(base.compare(&report(500..600), 0.0), Comparison::Improved);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn json() {
+    let mut report = Report::new();
+    report.estimate = 42..usize::MAX;
+    report.probes = 3;
+    let json = report.to_json();
+    assert_eq!(
+        json,
+        r#"{"schema_version":7,"estimate":{"start":42,"end":null},"probes":3,"overloaded":0,"status":"completed","timeline":[]}"#
+    );
+    assert_eq!(Report::parse(&json), Ok(report.clone()));
+    report.status = Status::NeverOverloaded;
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.status = Status::NothingSustained;
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.status = Status::Cancelled;
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.status = Status::SmokeFailed;
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.status = Status::Unviable;
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.seed = Some(u64::MAX);
+    assert!(report
+        .to_json()
+        .ends_with(r#""seed":18446744073709551615,"timeline":[]}"#));
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.search_id = Some(7);
+    assert!(report
+        .to_json()
+        .ends_with(r#""seed":18446744073709551615,"search_id":7,"timeline":[]}"#));
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.inconclusive_probe();
+    assert!(report
+        .to_json()
+        .contains(r#""status":"unviable","inconclusive":1,"seed""#));
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.config.push(("a.b".into(), "1".into()));
+    report.config.push(("a.\"c\"".into(), "x".into()));
+    assert!(report
+        .to_json()
+        .ends_with(r#""search_id":7,"config":{"a.b":"1","a.\"c\"":"x"},"timeline":[]}"#));
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.warnings.push("the \"canary\" drifted".into());
+    assert!(report
+        .to_json()
+        .ends_with(r#""x"},"warnings":["the \"canary\" drifted"],"timeline":[]}"#));
+    assert_eq!(Report::parse(&report.to_json()), Ok(report.clone()));
+    report.probed(42, true);
+    report.settle(40..42);
+    report.probed(41, false);
+    report.settle(41..42);
+    assert_eq!(report.estimate, 41..42);
+    assert_eq!((report.probes, report.overloaded), (5, 1));
+    assert!(report.to_json().ends_with(
+        r#""timeline":[{"load":42,"overloaded":true,"estimate":{"start":40,"end":42}},{"load":41,"overloaded":false,"estimate":{"start":41,"end":42}}]}"#
+    ));
+    assert_eq!(Report::parse(&report.to_json()), Ok(report));
+
+    // version 2 predates the timeline
+    let report = Report::parse(
+        r#"{"schema_version":2,"estimate":{"start":1,"end":2},"probes":1,"overloaded":0,"status":"cancelled"}"#,
+    )
+    .unwrap();
+    assert_eq!(report.status, Status::Cancelled);
+    assert!(report.timeline.is_empty());
+    assert_eq!(report.search_id, None);
+
+    // fields may come in any order, and unknown fields are ignored
+    let report = Report::parse(
+        r#"{ "probes": 7, "overloaded": 3, "comment": "hi",
+             "estimate": { "end": 20, "start": 10 }, "schema_version": 1 }"#,
+    )
+    .unwrap();
+    assert_eq!(report.estimate, 10..20);
+    assert_eq!(report.probes, 7);
+    assert_eq!(report.status, Status::Completed);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn json_upgrade() {
+    // a report written with version 6 of the schema, which predates `warnings`
+    let v6 = r#"{"schema_version":6,"estimate":{"start":1500,"end":1750},"probes":6,"overloaded":2,"status":"completed","inconclusive":1,"search_id":7,"config":{"exp.min_width":"250"},"timeline":[{"load":500,"overloaded":false,"estimate":{"start":500,"end":null}},{"load":1000,"overloaded":false,"estimate":{"start":1000,"end":null}},{"load":2000,"overloaded":true,"estimate":{"start":1000,"end":2000}},{"load":1500,"overloaded":false,"estimate":{"start":1500,"end":2000}},{"load":1750,"overloaded":true,"estimate":{"start":1500,"end":1750}}]}"#;
+    let report = Report::parse(v6).unwrap();
+    assert_eq!(report.estimate, 1500..1750);
+    assert_eq!(report.inconclusive, 1);
+    assert_eq!(report.search_id, Some(7));
+    assert!(report.warnings.is_empty());
+    assert_eq!(report.timeline.len(), 5);
+
+    // it is written back out with the current version, and reads back the same
+    let json = report.to_json();
+    assert!(json.starts_with(r#"{"schema_version":7,"#));
+    assert_eq!(json, v6.replace(r#""schema_version":6"#, r#""schema_version":7"#));
+    assert_eq!(Report::parse(&json), Ok(report));
+
+    // a report written with version 1, which predates `status` and `timeline`
+    let report = Report::parse(
+        r#"{"schema_version":1,"estimate":{"start":10,"end":20},"probes":3,"overloaded":1}"#,
+    )
+    .unwrap();
+    assert_eq!(report.status, Status::Completed);
+    assert!(report.timeline.is_empty());
+    let json = report.to_json();
+    assert_eq!(
+        json,
+        r#"{"schema_version":7,"estimate":{"start":10,"end":20},"probes":3,"overloaded":1,"status":"completed","timeline":[]}"#
+    );
+    assert_eq!(Report::parse(&json), Ok(report));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn json_errors() {
+    assert_eq!(Report::parse("{"), Err(ParseError::Syntax(1)));
+    assert_eq!(
+        Report::parse(r#"{"probes":1}"#),
+        Err(ParseError::Missing("schema_version"))
+    );
+    assert_eq!(
+        Report::parse(r#"{"schema_version":8}"#),
+        Err(ParseError::UnsupportedVersion(8))
+    );
+    assert_eq!(
+        Report::parse(
+            r#"{"schema_version":3,"estimate":{"start":1,"end":2},"probes":1,"overloaded":0,"status":"completed"}"#
+        ),
+        Err(ParseError::Missing("timeline"))
+    );
+    assert_eq!(
+        Report::parse(
+            r#"{"schema_version":3,"estimate":{"start":1,"end":2},"probes":1,"overloaded":0,"status":"completed","timeline":[{"load":1,"overloaded":0}]}"#
+        ),
+        Err(ParseError::Invalid("overloaded"))
+    );
+    assert_eq!(
+        Report::parse(
+            r#"{"schema_version":2,"estimate":{"start":1,"end":2},"probes":1,"overloaded":0,"status":"meh"}"#
+        ),
+        Err(ParseError::Invalid("status"))
+    );
+    assert_eq!(
+        Report::parse(r#"{"schema_version":1,"estimate":{"start":-1,"end":2}}"#),
+        Err(ParseError::Invalid("start"))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn validate() {
+    use crate::{CliffSearch, Error, ExponentialCliffSearcher, LoadIterator};
+
+    assert_eq!(Report::new().validate(), Ok(()));
+    let report = ExponentialCliffSearcher::new(500).run_to_completion(|load| load <= 1600);
+    assert_eq!(report.validate(), Ok(()));
+    let report = LoadIterator::from(&[1, 2, 3]).run_to_completion(|load| load < 3);
+    assert_eq!(report.validate(), Ok(()));
+    // the lower bound was never probed
+    let report = ExponentialCliffSearcher::within(1000..2000, 100).run_to_completion(|_| false);
+    assert_eq!(report.estimate, 1000..1062);
+    assert_eq!(report.validate(), Ok(()));
+
+    let mut contradiction = report.clone();
+    contradiction.timeline[1].overloaded = false;
+    contradiction.overloaded -= 1;
+    assert_eq!(
+        contradiction.validate(),
+        Err(Error::Contradiction {
+            supported: 1250,
+            overloaded: 1062
+        })
+    );
+
+    let mut miscounted = report.clone();
+    miscounted.overloaded += 1;
+    assert!(matches!(miscounted.validate(), Err(Error::Inconsistent(_))));
+
+    let mut missing = report;
+    missing.timeline.clear();
+    assert!(matches!(missing.validate(), Err(Error::Inconsistent(_))));
+}
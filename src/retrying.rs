@@ -0,0 +1,328 @@
+use super::{CliffSearch, Describe, Refine, Status, Votes};
+
+/// A search that retries every load a few times, and only calls it overloaded if enough of the
+/// trials fail.
+///
+/// Each load yielded by the wrapped search is yielded up to `trials` times in a row. If
+/// `failures` of those trials overload the system, the wrapped search is told the load was
+/// overloaded. As soon as that can no longer happen, the load counts as sustained, and the
+/// remaining trials are not run. So with a `failures` of `2` out of `3` trials, a load the system
+/// keeps up with takes only two runs, and a single spurious failure costs one more run rather
+/// than a bogus upper bound.
+///
+/// Unlike [`Repeated`](crate::Repeated), which always runs every load the same number of times
+/// and goes with the majority, this stops a load as soon as its outcome is settled, and the bar
+/// for an overload can be set anywhere between one failed trial and all of them.
+///
+/// Inconclusive trials do not count, and are simply run again.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, RetryingSearcher};
+///
+/// let mut load = RetryingSearcher::new(ExponentialCliffSearcher::new(500), 2, 3);
+/// let mut flaked = false;
+/// let mut runs = 0;
+/// while let Some(l) = load.next() {
+///     runs += 1;
+///     // one of the runs at 1000 happens to fall over
+///     if l > 1600 || (l == 1000 && !std::mem::replace(&mut flaked, true)) {
+///         load.overloaded();
+///     }
+/// }
+/// assert_eq!(load.estimate(), 1500..1750);
+/// assert_eq!(runs, 11);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct RetryingSearcher<S> {
+    inner: S,
+    failures: usize,
+    trials: usize,
+    votes: Votes,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+    marginal: bool,
+    // how many of the sustained trials of the current load were marginal
+    marginals: usize,
+    skipped: bool,
+}
+
+impl<S> RetryingSearcher<S>
+where
+    S: CliffSearch,
+{
+    /// Run every load yielded by `search` up to `trials` times, and tell `search` it was
+    /// overloaded only if `failures` of those trials were.
+    ///
+    /// A `trials` of `0` is treated as `1`, and `failures` is kept between `1` and `trials`.
+    pub fn new(search: S, failures: usize, trials: usize) -> Self {
+        let trials = trials.max(1);
+        RetryingSearcher {
+            inner: search,
+            failures: failures.max(1).min(trials),
+            trials,
+            votes: Votes::default(),
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+            marginal: false,
+            marginals: 0,
+            skipped: false,
+        }
+    }
+
+    /// Give the outcomes of the trials of the current load so far.
+    ///
+    /// This does not include the trial of the load most recently yielded by [`Iterator::next`],
+    /// which is counted when the next load is asked for.
+    pub fn votes(&self) -> Votes {
+        self.votes
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// The trial counts as failed, but the factor is not passed on.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        let _ = factor;
+        self.overloaded = true;
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// The trial does not count, and is run again.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// The trial counts as sustained. If the load turns out to be sustained, and at least half of
+    /// its sustained trials were marginal, the wrapped search is told that the load was marginal.
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.marginal = true;
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// Any trials of it so far are dropped, and the wrapped search is told right away.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.skipped = true;
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall counts as a failed trial.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> CliffSearch for RetryingSearcher<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        RetryingSearcher::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        RetryingSearcher::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        RetryingSearcher::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        RetryingSearcher::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        RetryingSearcher::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        RetryingSearcher::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        RetryingSearcher::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner
+            .probes_left()
+            .map(|loads| loads.saturating_mul(self.trials))
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for RetryingSearcher<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for RetryingSearcher<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(load) = self.last {
+            if core::mem::replace(&mut self.skipped, false) {
+                self.votes = Votes::default();
+                self.marginals = 0;
+            } else {
+                if !self.inconclusive {
+                    if self.overloaded {
+                        self.votes.overloaded += 1;
+                    } else {
+                        self.votes.sustained += 1;
+                        self.marginals += self.marginal as usize;
+                    }
+                }
+                self.overloaded = false;
+                self.inconclusive = false;
+                self.marginal = false;
+
+                if self.votes.overloaded >= self.failures {
+                    self.inner.overloaded();
+                } else if self.votes.sustained <= self.trials - self.failures {
+                    // enough trials are left that the load may still turn out overloaded
+                    return Some(load);
+                } else if 2 * self.marginals >= self.votes.sustained {
+                    self.inner.marginal();
+                }
+                self.votes = Votes::default();
+                self.marginals = 0;
+            }
+        }
+        self.overloaded = false;
+        self.inconclusive = false;
+        self.marginal = false;
+        self.last = self.inner.next();
+        self.last
+    }
+}
+
+impl<S> Describe for RetryingSearcher<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "retrying"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("failures", &self.failures);
+        setting("trials", &self.trials);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn retries() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = RetryingSearcher::new(ExponentialCliffSearcher::new(500), 2, 3);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(500));
+    // two sustained trials settle it, since two failures are no longer possible
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(1000));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.votes().overloaded, 1);
+    scale.overloaded();
+    // the second failure settles it
+    assert_eq!(scale.next(), Some(750));
+    assert_eq!(scale.estimate(), 500..1000);
+    assert_eq!(scale.votes(), Votes::default());
+}
+
+#[test]
+fn single_failure() {
+    use super::ExponentialCliffSearcher;
+
+    // with a single failure out of any number of trials, a load the system keeps up with runs
+    // every trial, and an overload ends it right away
+    let mut scale = RetryingSearcher::new(ExponentialCliffSearcher::new(500), 0, 2);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(750));
+    assert_eq!(scale.votes(), Votes::default());
+}
+
+#[test]
+fn marginal() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = RetryingSearcher::new(ExponentialCliffSearcher::until(500, 100), 2, 3);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(500));
+    scale.marginal();
+    // one of the two sustained trials only just kept up, so the wrapped search is told
+    assert_eq!(scale.next(), Some(600));
+    assert_eq!(scale.next(), Some(600));
+    assert_eq!(scale.next(), Some(1200));
+}
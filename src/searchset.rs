@@ -0,0 +1,772 @@
+use crate::{Cancel, CliffSearch, Estimate, Verdict};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// How a [`SearchSet`] decides which search gets to run the next benchmark.
+///
+/// Whatever the policy, searches that have finished are never picked again, so their share of the
+/// benchmark runs automatically goes to the searches that remain. The policy only decides among
+/// the unfinished searches with the highest [priority](SearchSet::set_priority), and takes their
+/// [weights](SearchSet::set_weight) into account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Policy {
+    /// Take turns, in the order the searches were added.
+    ///
+    /// A search with a weight of `w` takes `w` runs in a row when it is its turn.
+    RoundRobin,
+    /// Pick the search whose estimate is widest relative to its lower bound.
+    ///
+    /// Searches that have yet to find an upper bound are infinitely wide, and so go first. Ties
+    /// go to the search that was added first. A search's relative width is multiplied by its
+    /// weight before comparing, so a search with a weight of `2` is picked until its estimate is
+    /// half as wide as the others'.
+    WidestRelative,
+    /// Run at most this many benchmarks in total, and spread them as evenly as possible.
+    ///
+    /// The search that has run the fewest benchmarks so far, relative to its weight, goes next,
+    /// with ties broken as for [`Policy::WidestRelative`]. This way, no search is left without an
+    /// estimate when the budget runs out just because another search got there first, and a
+    /// search with a weight of `2` gets twice the runs of one with a weight of `1`.
+    Deadline(usize),
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::RoundRobin
+    }
+}
+
+type MetricsJudge<'a> = Box<dyn FnMut(&[f64]) -> Verdict + Send + 'a>;
+
+struct Slot<'a> {
+    search: Box<dyn CliffSearch + Send + 'a>,
+    judge: Option<MetricsJudge<'a>>,
+    queued: Option<usize>,
+    started: bool,
+    probes: usize,
+    priority: u32,
+    weight: u32,
+}
+
+/// Several searches that share a single stream of benchmark runs.
+///
+/// Each call to [`Iterator::next`] yields the index of the search that should run next (as
+/// returned by [`SearchSet::push`]) along with the load it wants to run at. The outcome of that
+/// run is reported back through [`SearchSet::overloaded`] and friends, just as for a single
+/// search. Which search goes next is decided by the set's [`Policy`].
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Policy, SearchSet};
+///
+/// let mut set = SearchSet::new();
+/// let reads = set.push(ExponentialCliffSearcher::new(500));
+/// let writes = set.push(ExponentialCliffSearcher::new(100));
+/// set.policy(Policy::WidestRelative);
+/// while let Some((search, load)) = set.next() {
+///     let cliff = if search == reads { 1600 } else { 300 };
+///     if load > cliff {
+///         set.overloaded();
+///     }
+/// }
+/// assert_eq!(set.estimate(reads), 1500..1750);
+/// assert_eq!(set.estimate(writes), 300..350);
+/// ```
+///
+/// # Priorities and weights
+///
+/// Not all searches are equally important. A search with a higher
+/// [priority](SearchSet::set_priority) always goes before those with a lower one, so the
+/// configuration that matters most converges first, and background searches only get the runs it
+/// no longer needs. Among searches of the same priority, the [weight](SearchSet::set_weight) of
+/// each search decides how large a share of the runs it gets under the set's [`Policy`].
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, SearchSet};
+///
+/// let mut set = SearchSet::new();
+/// let background = set.push(ExponentialCliffSearcher::new(100));
+/// let critical = set.push(ExponentialCliffSearcher::new(500));
+/// set.set_priority(critical, 1);
+/// let mut order = Vec::new();
+/// while let Some((search, load)) = set.next() {
+///     order.push(search);
+///     let cliff = if search == critical { 1600 } else { 300 };
+///     if load > cliff {
+///         set.overloaded();
+///     }
+/// }
+/// // the critical search ran all of its benchmarks first
+/// assert_eq!(order[..5], [critical; 5]);
+/// assert!(order[5..].iter().all(|&s| s == background));
+/// ```
+///
+/// Schedulers of your own can look at the same information the policies use, through
+/// [`estimate`](SearchSet::estimate), [`relative_width`](SearchSet::relative_width), and
+/// [`probes`](SearchSet::probes).
+///
+/// # Sharing measurements
+///
+/// Searches of the same system often differ only in how they judge a run, like one for a p95 SLA
+/// and one for a p99 SLA, and so end up wanting to run the same loads. Searches added with
+/// [`push_judged`](SearchSet::push_judged) are judged on the metrics reported with
+/// [`measured`](SearchSet::measured) rather than told about overloads directly. With
+/// [`share_measurements`](SearchSet::share_measurements), the set also remembers the metrics of
+/// every load it has run, and a judged search that wants to run a load that has already been
+/// measured is judged on those metrics instead of running it again.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Judge, SearchSet};
+///
+/// // the system's latency percentiles, in ms, at a given load
+/// let measure = |load: usize| [load as f64 / 10.0, load as f64 / 5.0];
+///
+/// let mut set = SearchSet::new();
+/// let p95 = Judge::at_most(160.0);
+/// let p99 = Judge::at_most(160.0);
+/// let a = set.push_judged(ExponentialCliffSearcher::new(500), move |m| p95.judge(m[0]));
+/// let b = set.push_judged(ExponentialCliffSearcher::new(500), move |m| p99.judge(m[1]));
+/// set.share_measurements();
+/// let mut runs = 0;
+/// while let Some((_, load)) = set.next() {
+///     runs += 1;
+///     set.measured(&measure(load));
+/// }
+/// assert_eq!(set.estimate(a), 1500..1750);
+/// assert_eq!(set.estimate(b), 750..1000);
+/// // 500 and 1000 were only run once
+/// assert_eq!((runs, set.reused()), (6, 2));
+/// ```
+///
+/// This type is only available with the `alloc` feature.
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct SearchSet<'a> {
+    searches: Vec<Slot<'a>>,
+    policy: Policy,
+    turn: usize,
+    spent: usize,
+    streak: u32,
+    last: Option<usize>,
+    last_load: Option<usize>,
+    cancel: Option<Box<dyn Cancel + Send + 'a>>,
+    measurements: Option<Vec<(usize, Vec<f64>)>>,
+    reused: usize,
+}
+
+impl core::fmt::Debug for SearchSet<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SearchSet")
+            .field("policy", &self.policy)
+            .field("spent", &self.spent)
+            .field("last", &self.last)
+            .field("cancelled", &self.is_cancelled())
+            .field("reused", &self.reused)
+            .finish()
+    }
+}
+
+impl Default for SearchSet<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> SearchSet<'a> {
+    /// Make an empty set that schedules its searches [round-robin](Policy::RoundRobin).
+    pub fn new() -> Self {
+        SearchSet {
+            searches: Vec::new(),
+            policy: Policy::default(),
+            turn: 0,
+            spent: 0,
+            streak: 0,
+            last: None,
+            last_load: None,
+            cancel: None,
+            measurements: None,
+            reused: 0,
+        }
+    }
+
+    /// Add a search to the set, and return the index it is identified by.
+    pub fn push<S>(&mut self, search: S) -> usize
+    where
+        S: CliffSearch + Send + 'a,
+    {
+        self.searches.push(Slot {
+            search: search.boxed(),
+            judge: None,
+            queued: None,
+            started: false,
+            probes: 0,
+            priority: 0,
+            weight: 1,
+        });
+        self.searches.len() - 1
+    }
+
+    /// Add a search to the set that is judged by `judge` on the metrics of each run, and return
+    /// the index it is identified by.
+    ///
+    /// The metrics of a run are reported with [`measured`](SearchSet::measured), and `judge`
+    /// decides the outcome for this search from them. See [sharing
+    /// measurements](SearchSet#sharing-measurements).
+    pub fn push_judged<S, J>(&mut self, search: S, judge: J) -> usize
+    where
+        S: CliffSearch + Send + 'a,
+        J: FnMut(&[f64]) -> Verdict + Send + 'a,
+    {
+        let i = self.push(search);
+        self.searches[i].judge = Some(Box::new(judge));
+        i
+    }
+
+    /// Remember the metrics of every load that is run, and judge searches added with
+    /// [`push_judged`](SearchSet::push_judged) on them rather than running the same load again.
+    ///
+    /// A remembered run that a search judges [inconclusive](Verdict::Inconclusive) is run again
+    /// as usual, and its new metrics replace the old ones.
+    pub fn share_measurements(&mut self) {
+        self.measurements.get_or_insert_with(Vec::new);
+    }
+
+    /// The number of times a search was judged on the remembered metrics of an earlier run,
+    /// rather than running its load.
+    pub fn reused(&self) -> usize {
+        self.reused
+    }
+
+    /// Report the metrics measured for the previous load yielded by [`Iterator::next`].
+    ///
+    /// If the search that yielded the load was added with
+    /// [`push_judged`](SearchSet::push_judged), it is judged on `metrics`. Otherwise, the outcome
+    /// must also be reported with [`overloaded`](SearchSet::overloaded) and friends. Either way,
+    /// the metrics are remembered if [measurements are
+    /// shared](SearchSet::share_measurements).
+    pub fn measured(&mut self, metrics: &[f64]) {
+        let (i, load) = match (self.last, self.last_load) {
+            (Some(i), Some(load)) => (i, load),
+            _ => return,
+        };
+        if let Some(measurements) = &mut self.measurements {
+            match measurements.iter_mut().find(|(l, _)| *l == load) {
+                Some((_, m)) => *m = metrics.to_vec(),
+                None => measurements.push((load, metrics.to_vec())),
+            }
+        }
+        let slot = &mut self.searches[i];
+        if let Some(judge) = &mut slot.judge {
+            judge(metrics).apply(&mut *slot.search);
+        }
+    }
+
+    /// Change the priority of the search with the given index.
+    ///
+    /// Searches start out with a priority of `0`. As long as a search with a higher priority is
+    /// unfinished, searches with a lower priority are not picked to run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no search with that index.
+    pub fn set_priority(&mut self, search: usize, priority: u32) {
+        self.searches[search].priority = priority;
+    }
+
+    /// Change the weight of the search with the given index.
+    ///
+    /// Searches start out with a weight of `1`. A search with a higher weight gets a
+    /// proportionally larger share of the runs among searches of the same priority; how exactly
+    /// depends on the [`Policy`]. A weight of `0` is taken to be `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no search with that index.
+    pub fn set_weight(&mut self, search: usize, weight: u32) {
+        self.searches[search].weight = weight.max(1);
+    }
+
+    /// Change how the set picks which search runs next.
+    ///
+    /// This takes effect from the next call to [`Iterator::next`].
+    pub fn policy(&mut self, policy: Policy) {
+        self.policy = policy;
+    }
+
+    /// Stop handing out benchmark runs once `cancel` says so.
+    ///
+    /// Cancellation is checked on every call to [`Iterator::next`], after the outcome of the
+    /// previous run has been passed on, so every estimate takes all completed runs into account.
+    pub fn cancel_on<C>(&mut self, cancel: C)
+    where
+        C: Cancel + Send + 'a,
+    {
+        self.cancel = Some(Box::new(cancel));
+    }
+
+    /// Returns `true` if the set was [cancelled](SearchSet::cancel_on).
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().map_or(false, |c| c.is_cancelled())
+    }
+
+    /// The number of searches in the set.
+    pub fn len(&self) -> usize {
+        self.searches.len()
+    }
+
+    /// Returns `true` if the set holds no searches.
+    pub fn is_empty(&self) -> bool {
+        self.searches.is_empty()
+    }
+
+    /// The current estimate of the search with the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no search with that index.
+    pub fn estimate(&self, search: usize) -> core::ops::Range<usize> {
+        self.searches[search].search.estimate()
+    }
+
+    /// The number of benchmarks the search with the given index has been given to run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no search with that index.
+    pub fn probes(&self, search: usize) -> usize {
+        self.searches[search].probes
+    }
+
+    /// Returns `true` if the search with the given index has finished.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no search with that index.
+    pub fn is_done(&self, search: usize) -> bool {
+        let slot = &self.searches[search];
+        slot.started && slot.queued.is_none() && self.last != Some(search)
+    }
+
+    /// Indicate that the system could not keep up with the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This is passed on to [`CliffSearch::overloaded`] of the search that yielded the load.
+    pub fn overloaded(&mut self) {
+        if let Some(i) = self.last {
+            self.searches[i].search.overloaded();
+        }
+    }
+
+    /// Indicate that the system could not keep up with the previous load yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This is passed on to [`CliffSearch::overloaded_by`] of the search that yielded the load.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        if let Some(i) = self.last {
+            self.searches[i].search.overloaded_by(factor);
+        }
+    }
+
+    /// Indicate that the benchmark run for the previous load yielded by [`Iterator::next`] was
+    /// inconclusive.
+    ///
+    /// This is passed on to [`CliffSearch::inconclusive`] of the search that yielded the load.
+    pub fn inconclusive(&mut self) {
+        if let Some(i) = self.last {
+            self.searches[i].search.inconclusive();
+        }
+    }
+
+    /// Indicate that the system only just kept up with the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This is passed on to [`CliffSearch::marginal`] of the search that yielded the load.
+    pub fn marginal(&mut self) {
+        if let Some(i) = self.last {
+            self.searches[i].search.marginal();
+        }
+    }
+
+    /// Indicate that the previous load yielded by [`Iterator::next`] could not be run at all.
+    ///
+    /// This is passed on to [`CliffSearch::skip_last`] of the search that yielded the load.
+    pub fn skip_last(&mut self) {
+        if let Some(i) = self.last {
+            self.searches[i].search.skip_last();
+        }
+    }
+
+    /// Indicate the load the load generator actually achieved for the previous load yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This is passed on to [`CliffSearch::achieved`] of the search that yielded the load.
+    pub fn achieved(&mut self, actual: usize) {
+        if let Some(i) = self.last {
+            self.searches[i].search.achieved(actual);
+        }
+    }
+
+    /// The width of the current estimate of the search with the given index, relative to its
+    /// lower bound.
+    ///
+    /// This is what [`Policy::WidestRelative`] compares (before weighting), and is infinite for
+    /// searches that have yet to find an upper bound. See [`Estimate::relative_width`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no search with that index.
+    pub fn relative_width(&self, search: usize) -> f64 {
+        Estimate::from(self.searches[search].search.estimate()).relative_width()
+    }
+
+    fn widest(&self, candidates: impl Iterator<Item = usize>) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for i in candidates {
+            let width = self.relative_width(i) * f64::from(self.searches[i].weight);
+            if best.map_or(true, |(_, w)| width > w) {
+                best = Some((i, width));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    fn pick(&mut self) -> Option<usize> {
+        let n = self.searches.len();
+        // only the unfinished searches with the highest priority are in the running
+        let top = self
+            .searches
+            .iter()
+            .filter(|s| s.queued.is_some())
+            .map(|s| s.priority)
+            .max()?;
+        let live = |s: &Slot<'_>| s.queued.is_some() && s.priority == top;
+        match self.policy {
+            Policy::RoundRobin => {
+                if self.turn > 0 {
+                    let prev = self.turn - 1;
+                    let slot = &self.searches[prev];
+                    if live(slot) && self.streak < slot.weight {
+                        self.streak += 1;
+                        return Some(prev);
+                    }
+                }
+                let i = (0..n)
+                    .map(|k| (self.turn + k) % n)
+                    .find(|&i| live(&self.searches[i]))?;
+                self.turn = i + 1;
+                self.streak = 1;
+                Some(i)
+            }
+            Policy::WidestRelative => self.widest((0..n).filter(|&i| live(&self.searches[i]))),
+            Policy::Deadline(_) => {
+                // compare probes per unit of weight without dividing, by cross-multiplying
+                let share = |s: &Slot<'_>| (s.probes as u64, u64::from(s.weight));
+                let (p, w) = self
+                    .searches
+                    .iter()
+                    .filter(|s| live(s))
+                    .map(share)
+                    .min_by(|(p1, w1), (p2, w2)| (p1 * w2).cmp(&(p2 * w1)))?;
+                self.widest((0..n).filter(|&i| {
+                    let slot = &self.searches[i];
+                    let (pi, wi) = share(slot);
+                    live(slot) && pi * w == p * wi
+                }))
+            }
+        }
+    }
+}
+
+impl Iterator for SearchSet<'_> {
+    type Item = (usize, usize);
+    fn next(&mut self) -> Option<Self::Item> {
+        // let the search that ran last take its outcome into account before we compare estimates
+        if let Some(i) = self.last.take() {
+            let slot = &mut self.searches[i];
+            slot.queued = slot.search.next();
+        }
+        for slot in &mut self.searches {
+            if !slot.started {
+                slot.started = true;
+                slot.queued = slot.search.next();
+            }
+        }
+
+        if let Policy::Deadline(budget) = self.policy {
+            if self.spent >= budget {
+                return None;
+            }
+        }
+        if self.is_cancelled() {
+            return None;
+        }
+
+        let (i, load) = loop {
+            let i = self.pick()?;
+            let slot = &mut self.searches[i];
+            let load = slot.queued.take()?;
+            let known = self
+                .measurements
+                .as_ref()
+                .and_then(|ms| ms.iter().find(|(l, _)| *l == load));
+            if let (Some(judge), Some((_, metrics))) = (&mut slot.judge, known) {
+                let verdict = judge(metrics);
+                if verdict != Verdict::Inconclusive {
+                    // no need to run the load again just for this search
+                    verdict.apply(&mut *slot.search);
+                    slot.queued = slot.search.next();
+                    self.reused += 1;
+                    continue;
+                }
+            }
+            break (i, load);
+        };
+        self.searches[i].probes += 1;
+        self.spent += 1;
+        self.last = Some(i);
+        self.last_load = Some(load);
+        Some((i, load))
+    }
+}
+
+#[cfg(test)]
+fn drive(set: &mut SearchSet<'_>, cliffs: &[usize]) -> Vec<(usize, usize)> {
+    let mut ran = Vec::new();
+    while let Some((i, load)) = set.next() {
+        ran.push((i, load));
+        if load > cliffs[i] {
+            set.overloaded();
+        }
+    }
+    ran
+}
+
+#[test]
+fn round_robin() {
+    use crate::ExponentialCliffSearcher;
+
+    let mut set = SearchSet::new();
+    set.push(ExponentialCliffSearcher::new(500));
+    set.push(ExponentialCliffSearcher::new(100));
+    // the second search finishes right away, so the first gets all the remaining runs
+    assert_eq!(
+        drive(&mut set, &[1600, 50]),
+        alloc::vec![
+            (0, 500),
+            (1, 100),
+            (0, 1000),
+            (0, 2000),
+            (0, 1500),
+            (0, 1750)
+        ]
+    );
+    assert_eq!(set.estimate(0), 1500..1750);
+    assert_eq!(set.estimate(1), 100..100);
+    assert_eq!(set.probes(0), 5);
+    assert_eq!(set.probes(1), 1);
+    assert!(set.is_done(0));
+    assert!(set.is_done(1));
+}
+
+#[test]
+fn widest_relative() {
+    use crate::ExponentialCliffSearcher;
+
+    let mut set = SearchSet::new();
+    set.push(ExponentialCliffSearcher::new(500));
+    set.push(ExponentialCliffSearcher::new(1000));
+    set.policy(Policy::WidestRelative);
+    assert_eq!(
+        drive(&mut set, &[1600, 3000]),
+        alloc::vec![
+            // both are unbounded, so the first one goes until it is bounded
+            (0, 500),
+            (0, 1000),
+            (0, 2000),
+            (1, 1000),
+            (1, 2000),
+            (1, 4000),
+            // and from then on, whichever is less precise
+            (0, 1500),
+            (1, 3000),
+            (0, 1750),
+            (1, 3500),
+        ]
+    );
+    assert_eq!(set.estimate(0), 1500..1750);
+    assert_eq!(set.estimate(1), 3000..3500);
+}
+
+#[test]
+fn deadline() {
+    use crate::ExponentialCliffSearcher;
+
+    let mut set = SearchSet::new();
+    set.push(ExponentialCliffSearcher::new(500));
+    set.push(ExponentialCliffSearcher::new(1000));
+    set.policy(Policy::Deadline(4));
+    assert_eq!(
+        drive(&mut set, &[1600, 3000]),
+        alloc::vec![(0, 500), (1, 1000), (0, 1000), (1, 2000)]
+    );
+    assert!(!set.is_done(0));
+    assert_eq!(set.estimate(1), 2000..usize::MAX);
+
+    // extending the deadline picks up where we left off
+    set.policy(Policy::Deadline(6));
+    assert_eq!(
+        drive(&mut set, &[1600, 3000]),
+        alloc::vec![(0, 2000), (1, 4000)]
+    );
+    assert_eq!(set.estimate(0), 1000..2000);
+}
+
+#[test]
+fn inconclusive() {
+    use crate::ExponentialCliffSearcher;
+
+    let mut set = SearchSet::new();
+    set.push(ExponentialCliffSearcher::new(500));
+    set.push(ExponentialCliffSearcher::new(100));
+    assert_eq!(set.next(), Some((0, 500)));
+    set.inconclusive();
+    assert_eq!(set.next(), Some((1, 100)));
+    assert_eq!(set.next(), Some((0, 500)));
+}
+
+#[test]
+fn cancel() {
+    use crate::ExponentialCliffSearcher;
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut set = SearchSet::new();
+    set.push(ExponentialCliffSearcher::new(500));
+    set.push(ExponentialCliffSearcher::new(100));
+    set.cancel_on(Arc::clone(&stop));
+    assert_eq!(set.next(), Some((0, 500)));
+    assert_eq!(set.next(), Some((1, 100)));
+    set.overloaded();
+    stop.store(true, Ordering::SeqCst);
+    assert_eq!(set.next(), None);
+    assert!(set.is_cancelled());
+    // the last outcome still counts
+    assert_eq!(set.estimate(1), 100..100);
+}
+
+#[test]
+fn shared_measurements() {
+    use crate::{ExponentialCliffSearcher, Judge};
+
+    let mut set = SearchSet::new();
+    let judge = Judge::at_most(100.0);
+    set.push_judged(ExponentialCliffSearcher::new(500), move |m| {
+        judge.judge(m[0])
+    });
+    set.push_judged(ExponentialCliffSearcher::new(500), |m| {
+        if m[0] < 50.0 {
+            Verdict::Sustained
+        } else {
+            // too close to call, so should be run again rather than taken from memory
+            Verdict::Inconclusive
+        }
+    });
+    set.push(ExponentialCliffSearcher::new(500));
+    set.share_measurements();
+
+    assert_eq!(set.next(), Some((0, 500)));
+    set.measured(&[60.0]);
+    // the second search judges the remembered run inconclusive, so runs it again
+    assert_eq!(set.next(), Some((1, 500)));
+    set.measured(&[40.0]);
+    // searches without a judge do not use remembered metrics
+    assert_eq!(set.next(), Some((2, 500)));
+    set.measured(&[60.0]);
+    assert_eq!(set.next(), Some((0, 1000)));
+    set.measured(&[200.0]);
+    assert_eq!(set.next(), Some((1, 1000)));
+    assert_eq!(set.reused(), 0);
+    set.measured(&[200.0]);
+    assert_eq!(set.next(), Some((2, 1000)));
+    set.overloaded();
+    // the first search now wants 750, which nobody has run yet
+    assert_eq!(set.next(), Some((0, 750)));
+    assert_eq!(set.probes(1), 2);
+}
+
+#[test]
+fn priorities() {
+    use crate::ExponentialCliffSearcher;
+
+    let mut set = SearchSet::new();
+    set.push(ExponentialCliffSearcher::new(100));
+    set.push(ExponentialCliffSearcher::new(500));
+    set.push(ExponentialCliffSearcher::new(1000));
+    set.set_priority(1, 2);
+    set.set_priority(2, 1);
+    set.policy(Policy::WidestRelative);
+    assert_eq!(
+        drive(&mut set, &[300, 1600, 1200]),
+        alloc::vec![
+            (1, 500),
+            (1, 1000),
+            (1, 2000),
+            (1, 1500),
+            (1, 1750),
+            (2, 1000),
+            (2, 2000),
+            (2, 1500),
+            (0, 100),
+            (0, 200),
+            (0, 400),
+            (0, 300),
+            (0, 350),
+        ]
+    );
+}
+
+#[test]
+fn weights() {
+    use crate::ExponentialCliffSearcher;
+
+    let mut set = SearchSet::new();
+    set.push(ExponentialCliffSearcher::new(500));
+    set.push(ExponentialCliffSearcher::new(1000));
+    set.set_weight(0, 2);
+    // a weight of 0 would starve the search, so it is taken to be 1
+    set.set_weight(1, 0);
+    assert_eq!(
+        drive(&mut set, &[1600, 3000]),
+        alloc::vec![
+            (0, 500),
+            (0, 1000),
+            (1, 1000),
+            (0, 2000),
+            (0, 1500),
+            (1, 2000),
+            (0, 1750),
+            (1, 4000),
+            (1, 3000),
+            (1, 3500),
+        ]
+    );
+
+    let mut set = SearchSet::new();
+    set.push(ExponentialCliffSearcher::new(500));
+    set.push(ExponentialCliffSearcher::new(1000));
+    set.set_weight(1, 2);
+    set.policy(Policy::Deadline(6));
+    assert_eq!(
+        drive(&mut set, &[1600, 3000]),
+        alloc::vec![
+            (0, 500),
+            (1, 1000),
+            (1, 2000),
+            (0, 1000),
+            (1, 4000),
+            (1, 3000)
+        ]
+    );
+    assert_eq!((set.probes(0), set.probes(1)), (2, 4));
+    assert_eq!(set.relative_width(1), 1.0 / 3.0);
+}
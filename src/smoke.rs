@@ -0,0 +1,327 @@
+use super::{CliffSearch, Describe, Refine, Status};
+
+/// A search that first runs a trivial load, and gives up straight away if the system cannot keep
+/// up with it.
+///
+/// When a search finds nothing sustained, it takes some digging to tell whether the starting load
+/// was just too aggressive or whether the benchmark never worked in the first place. Running a
+/// load far below the starting load first, like 1% of it, settles that up front: if the system
+/// cannot keep up with the smoke probe either, the search ends right there with
+/// [`Status::SmokeFailed`], and the estimate is `0..load`. Otherwise, the wrapped search runs as
+/// usual, and does not learn the outcome of the smoke probe.
+///
+/// A smoke probe that is inconclusive is run again, but one that cannot be run at all counts as
+/// failed, since the harness then cannot even run a trivial load.
+///
+/// ```rust
+/// use cliff::{CliffSearch, ExponentialCliffSearcher, Smoke, Status};
+///
+/// let mut load = Smoke::new(ExponentialCliffSearcher::new(500), 5);
+/// assert_eq!(load.next(), Some(5));
+/// // the benchmark is misconfigured, and nothing gets through
+/// load.overloaded();
+/// assert_eq!(load.next(), None);
+/// assert_eq!(load.status(), Status::SmokeFailed);
+/// assert_eq!(load.estimate(), 0..5);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Smoke<S> {
+    inner: S,
+    load: usize,
+    // `None` until the smoke probe has concluded, and then whether it passed
+    passed: Option<bool>,
+    smoking: bool,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+}
+
+impl<S> Smoke<S>
+where
+    S: CliffSearch,
+{
+    /// Run `load` before `search` starts, and only start the search if the system keeps up.
+    pub fn new(search: S, load: usize) -> Self {
+        Smoke {
+            inner: search,
+            load,
+            passed: None,
+            smoking: false,
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+        }
+    }
+
+    /// Returns `true` if the most recently yielded load is the smoke probe.
+    pub fn is_smoke(&self) -> bool {
+        self.smoking && self.last.is_some()
+    }
+
+    /// Whether the system kept up with the smoke probe, or `None` if it has not concluded yet.
+    pub fn passed(&self) -> Option<bool> {
+        self.passed
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        if self.smoking {
+            self.overloaded = true;
+        } else {
+            self.inner.overloaded();
+        }
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        if self.smoking {
+            self.overloaded = true;
+        } else {
+            self.inner.overloaded_by(factor);
+        }
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// An inconclusive smoke probe is run again.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        if self.smoking {
+            self.inconclusive = true;
+        } else {
+            self.inner.inconclusive();
+        }
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// A marginal smoke probe counts as passed.
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        if !self.smoking {
+            self.inner.marginal();
+        }
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// A smoke probe that could not be run counts as failed.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        if self.smoking {
+            self.overloaded = true;
+        } else {
+            self.inner.skip_last();
+        }
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall on the smoke probe counts as an overload.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.smoking {
+            if actual < self.load {
+                self.overloaded = true;
+            }
+        } else {
+            self.inner.achieved(actual);
+        }
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// If the smoke probe failed, this is `0..load`.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        match self.passed {
+            Some(false) => 0..self.load,
+            _ => self.inner.estimate(),
+        }
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> CliffSearch for Smoke<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Smoke::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Smoke::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Smoke::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Smoke::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Smoke::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Smoke::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Smoke::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        match self.passed {
+            Some(false) => Status::SmokeFailed,
+            _ => self.inner.status(),
+        }
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        match self.passed {
+            Some(false) => Some(0),
+            Some(true) => self.inner.probes_left(),
+            None => self
+                .inner
+                .probes_left()
+                .map(|probes| probes.saturating_add(1)),
+        }
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for Smoke<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for Smoke<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.smoking && self.last.take().is_some() && !self.inconclusive {
+            self.passed = Some(!self.overloaded);
+        }
+        self.overloaded = false;
+        self.inconclusive = false;
+
+        match self.passed {
+            Some(false) => {
+                self.smoking = false;
+                self.last = None;
+            }
+            Some(true) => {
+                self.smoking = false;
+                self.last = self.inner.next();
+            }
+            None => {
+                self.smoking = true;
+                self.last = Some(self.load);
+            }
+        }
+        self.last
+    }
+}
+
+impl<S> Describe for Smoke<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "smoke"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("load", &self.load);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn passes() {
+    use super::ExponentialCliffSearcher;
+
+    let mut load = Smoke::new(ExponentialCliffSearcher::new(500), 5);
+    assert_eq!(load.probes_left(), None);
+    assert_eq!(load.next(), Some(5));
+    assert!(load.is_smoke());
+    assert_eq!(load.passed(), None);
+    // an inconclusive smoke probe is run again
+    load.inconclusive();
+    assert_eq!(load.next(), Some(5));
+    load.achieved(5);
+    let mut runs = 0;
+    while let Some(l) = load.next() {
+        assert!(!load.is_smoke());
+        runs += 1;
+        if l > 1600 {
+            load.overloaded();
+        }
+    }
+    assert_eq!(runs, 5);
+    assert_eq!(load.passed(), Some(true));
+    assert_eq!(load.estimate(), 1500..1750);
+    assert_eq!(load.status(), Status::Completed);
+}
+
+#[test]
+fn fails() {
+    use super::ExponentialCliffSearcher;
+
+    let mut load = Smoke::new(ExponentialCliffSearcher::new(500), 5);
+    assert_eq!(load.next(), Some(5));
+    load.achieved(4);
+    assert_eq!(load.next(), None);
+    assert_eq!(load.next(), None);
+    assert_eq!(load.passed(), Some(false));
+    assert_eq!(load.status(), Status::SmokeFailed);
+    assert_eq!(load.estimate(), 0..5);
+    assert_eq!(load.probes_left(), Some(0));
+
+    // a smoke probe that can't be run at all fails too
+    let mut load = Smoke::new(ExponentialCliffSearcher::new(500), 5);
+    assert_eq!(load.next(), Some(5));
+    load.skip_last();
+    assert_eq!(load.next(), None);
+    assert_eq!(load.status(), Status::SmokeFailed);
+}
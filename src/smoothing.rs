@@ -0,0 +1,117 @@
+/// Smooth the metric of a load over several runs with an exponential moving average.
+///
+/// Searchers that steer by a metric, rather than only by whether the system kept up, can be
+/// thrown off by a single outlier: one unlucky throughput measurement makes a concurrency look
+/// like it stopped scaling, or makes a hill climb turn around. With smoothing, every load is run
+/// `runs` times in a row, and the searcher goes by the moving average of the metrics of those
+/// runs, where each new run is weighted by `alpha` and the average so far by `1 - alpha`. A lower
+/// `alpha` leans more on earlier runs.
+///
+/// Runs whose metric is not a number do not count towards the average, and are run again.
+///
+/// See [`ConcurrencySearcher::smooth`](crate::ConcurrencySearcher::smooth) and
+/// [`HillClimbSearcher::smooth`](crate::HillClimbSearcher::smooth). The smoothing is part of the
+/// settings [described](crate::Describe) by those searchers, and so ends up in a
+/// [`Report`](crate::Report) along with the rest of their configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Smoothing {
+    alpha: f64,
+    runs: usize,
+}
+
+impl Smoothing {
+    /// Run every load `runs` times, and weigh each run by `alpha` in the moving average.
+    ///
+    /// `alpha` is clamped to between `0.0` and `1.0`, where an `alpha` of `1.0` goes by the last
+    /// run only, and fewer than `1` runs are taken to be `1`.
+    pub fn new(alpha: f64, runs: usize) -> Self {
+        let alpha = if alpha.is_nan() {
+            1.0
+        } else {
+            alpha.clamp(0.0, 1.0)
+        };
+        Smoothing {
+            alpha,
+            runs: runs.max(1),
+        }
+    }
+
+    /// The weight of each new run in the moving average.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// The number of runs of every load.
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+}
+
+// the moving average of the runs of the load that is currently being run
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Smoother {
+    smoothing: Option<Smoothing>,
+    average: f64,
+    runs: usize,
+}
+
+impl Smoother {
+    pub(crate) fn set(&mut self, smoothing: Smoothing) {
+        self.smoothing = Some(smoothing);
+    }
+
+    // the smoothed metric once every run of the load is in, and `None` while it should run again
+    pub(crate) fn observe(&mut self, metric: f64) -> Option<f64> {
+        let smoothing = match self.smoothing {
+            Some(smoothing) => smoothing,
+            None => return Some(metric),
+        };
+        if metric.is_nan() {
+            return None;
+        }
+        self.average = if self.runs == 0 {
+            metric
+        } else {
+            smoothing.alpha * metric + (1.0 - smoothing.alpha) * self.average
+        };
+        self.runs += 1;
+        if self.runs < smoothing.runs {
+            return None;
+        }
+        self.runs = 0;
+        Some(self.average)
+    }
+
+    // forget the runs so far, like when the load turned out to overload the system anyway
+    pub(crate) fn reset(&mut self) {
+        self.runs = 0;
+    }
+
+    pub(crate) fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        if let Some(smoothing) = self.smoothing {
+            setting("smoothing_alpha", &smoothing.alpha);
+            setting("smoothing_runs", &smoothing.runs);
+        }
+    }
+}
+
+#[test]
+fn average() {
+    let mut smoother = Smoother::default();
+    assert_eq!(smoother.observe(5.0), Some(5.0));
+
+    smoother.set(Smoothing::new(0.5, 3));
+    assert_eq!(smoother.observe(100.0), None);
+    assert_eq!(smoother.observe(f64::NAN), None);
+    assert_eq!(smoother.observe(100.0), None);
+    // an outlier only moves the average half way
+    assert_eq!(smoother.observe(20.0), Some(60.0));
+    // and the next load starts afresh
+    assert_eq!(smoother.observe(10.0), None);
+    smoother.reset();
+    assert_eq!(smoother.observe(30.0), None);
+
+    let smoothing = Smoothing::new(f64::NAN, 0);
+    assert_eq!((smoothing.alpha(), smoothing.runs()), (1.0, 1));
+    assert_eq!(Smoothing::new(-1.0, 2).alpha(), 0.0);
+}
@@ -0,0 +1,252 @@
+use super::{CliffSearch, Describe, ExponentialCliffSearcher, Refine, Status};
+use core::time::Duration;
+
+/// An iterator that determines how long a system holds up under a fixed load.
+///
+/// This turns the usual search around: the load stays put, and the probed value is the length of
+/// the benchmark run, to find the time-to-failure cliff of problems like memory leaks or
+/// filling disks. It is an [`ExponentialCliffSearcher`] over whole milliseconds, so the values
+/// it yields through [`Iterator::next`] are run lengths in milliseconds, and
+/// [`next_duration`](SoakSearcher::next_duration) gives them as a [`Duration`] instead.
+///
+/// Since long runs are the norm here, a failure partway through a run is best reported with
+/// [`failed_after`](SoakSearcher::failed_after), which makes the time of the failure the upper
+/// bound rather than the length of the run. The search gives up on ever seeing the system fail
+/// once runs would get longer than its [cap](SoakSearcher::cap), which defaults to
+/// [`DEFAULT_CAP`](SoakSearcher::DEFAULT_CAP), and then has [`Status::NeverOverloaded`].
+///
+/// ```rust
+/// use cliff::SoakSearcher;
+/// use std::time::Duration;
+///
+/// let hour = Duration::from_secs(3600);
+/// let mut soak = SoakSearcher::new(hour);
+/// while let Some(length) = soak.next_duration() {
+///     // the system runs out of memory after 5.5 hours
+///     if length > hour * 11 / 2 {
+///         soak.failed_after(hour * 11 / 2);
+///     }
+/// }
+/// assert_eq!(soak.longest_sustained(), hour * 41 / 8);
+/// assert_eq!(soak.shortest_failed(), Some(hour * 11 / 2));
+/// ```
+///
+/// See also the [crate-level documentation](..) for details.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct SoakSearcher {
+    inner: ExponentialCliffSearcher,
+}
+
+// this fits in a 32-bit usize for run lengths up to 49 days, which is more than the default cap
+fn millis(d: Duration) -> usize {
+    let ms = d.as_millis();
+    if ms > usize::MAX as u128 {
+        usize::MAX
+    } else {
+        ms as usize
+    }
+}
+
+fn duration(ms: usize) -> Duration {
+    Duration::from_millis(ms as u64)
+}
+
+impl SoakSearcher {
+    /// The longest run the search tries by default, which is 30 days.
+    pub const DEFAULT_CAP: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+    /// Perform a soak search starting with runs of length `start`, and ending when the time to
+    /// failure has been determined to within `start / 2`.
+    pub fn new(start: Duration) -> Self {
+        Self::until(start, start / 2)
+    }
+
+    /// Perform a soak search starting with runs of length `start`, and ending when the time to
+    /// failure has been determined to within `min_width`.
+    ///
+    /// Both are rounded down to whole milliseconds, and a `start` of less than a millisecond is
+    /// taken to be one millisecond.
+    pub fn until(start: Duration, min_width: Duration) -> Self {
+        let mut inner = ExponentialCliffSearcher::until(millis(start).max(1), millis(min_width));
+        inner.sanity_cap(millis(Self::DEFAULT_CAP));
+        SoakSearcher { inner }
+    }
+
+    /// Give up on seeing the system fail once runs would get longer than `cap`.
+    ///
+    /// See [`ExponentialCliffSearcher::sanity_cap`].
+    pub fn cap(&mut self, cap: Duration) {
+        self.inner.sanity_cap(millis(cap));
+    }
+
+    /// Give the length of the next run, or `None` if the search has ended.
+    ///
+    /// This is the same as [`Iterator::next`], but as a [`Duration`].
+    pub fn next_duration(&mut self) -> Option<Duration> {
+        self.inner.next().map(duration)
+    }
+
+    /// Indicate that the system failed at some point during the previous run.
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system failed `elapsed` into the previous run.
+    ///
+    /// The system is then known not to last for `elapsed`, which narrows the estimate more than
+    /// knowing that it does not last for the whole run. If `elapsed` is no shorter than the run,
+    /// the run is taken to have been sustained.
+    pub fn failed_after(&mut self, elapsed: Duration) {
+        self.inner.achieved(millis(elapsed));
+    }
+
+    /// Indicate that the previous run was inconclusive.
+    ///
+    /// The next call to [`Iterator::next`] yields the same length again, so that it can be
+    /// re-run.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Give the current estimate of how many milliseconds the system-under-test holds up for.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// The longest run the system is known to have held up for.
+    pub fn longest_sustained(&self) -> Duration {
+        duration(self.inner.estimate().start)
+    }
+
+    /// The shortest time the system is known to have failed after, or `None` if it has not failed
+    /// yet.
+    pub fn shortest_failed(&self) -> Option<Duration> {
+        let end = self.inner.estimate().end;
+        if end == usize::MAX {
+            None
+        } else {
+            Some(duration(end))
+        }
+    }
+
+    /// Change the width to which the time to failure should be determined, and resume the search
+    /// if it had ended with a wider estimate than that.
+    ///
+    /// This provides [`Refine::refine`] without having to `use` the trait.
+    pub fn refine(&mut self, min_width: Duration) {
+        self.inner.refine(millis(min_width));
+    }
+}
+
+impl CliffSearch for SoakSearcher {
+    fn overloaded(&mut self) {
+        SoakSearcher::overloaded(self)
+    }
+
+    fn inconclusive(&mut self) {
+        SoakSearcher::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        SoakSearcher::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        self.inner.achieved(actual)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        SoakSearcher::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.probes_left()
+    }
+}
+
+impl Refine for SoakSearcher {
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl Iterator for SoakSearcher {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl Describe for SoakSearcher {
+    fn name(&self) -> &'static str {
+        "soak"
+    }
+
+    /// The settings are those of the underlying search, in milliseconds.
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        self.inner.settings(setting);
+    }
+}
+
+#[test]
+fn search() {
+    let minute = Duration::from_secs(60);
+    let mut soak = SoakSearcher::until(minute, Duration::from_secs(10));
+    assert_eq!(soak.next_duration(), Some(minute));
+    assert_eq!(soak.next_duration(), Some(minute * 2));
+    assert_eq!(soak.next(), Some(240_000));
+    soak.overloaded();
+    assert_eq!(soak.next_duration(), Some(minute * 3));
+    // failing after the run ended means it held up
+    soak.failed_after(minute * 4);
+    assert_eq!(soak.next_duration(), Some(minute * 7 / 2));
+    soak.failed_after(Duration::from_secs(200));
+    assert_eq!(soak.next_duration(), Some(Duration::from_secs(190)));
+    assert_eq!(soak.next_duration(), None);
+    assert_eq!(soak.longest_sustained(), Duration::from_secs(190));
+    assert_eq!(soak.shortest_failed(), Some(Duration::from_secs(200)));
+    assert_eq!(soak.status(), Status::Completed);
+
+    soak.refine(Duration::from_secs(5));
+    assert_eq!(soak.next_duration(), Some(Duration::from_secs(195)));
+}
+
+#[test]
+fn never_fails() {
+    let mut soak = SoakSearcher::new(Duration::from_secs(3600));
+    let mut runs = 0;
+    while soak.next().is_some() {
+        runs += 1;
+    }
+    // 1h, 2h, 4h, ..., 512h, and then 1024h would exceed 30 days
+    assert_eq!(runs, 10);
+    assert_eq!(soak.longest_sustained(), Duration::from_secs(512 * 3600));
+    assert_eq!(soak.shortest_failed(), None);
+    assert_eq!(soak.status(), Status::NeverOverloaded);
+
+    let mut soak = SoakSearcher::new(Duration::from_micros(10));
+    soak.cap(Duration::from_millis(4));
+    assert_eq!(soak.next_duration(), Some(Duration::from_millis(1)));
+    assert_eq!(soak.next(), Some(2));
+    assert_eq!(soak.next(), Some(4));
+    assert_eq!(soak.next(), None);
+}
@@ -0,0 +1,424 @@
+use super::{CliffSearch, Describe, Refine, Status};
+use alloc::string::String;
+use std::io::{self, Write};
+
+/// The format in which [`Streamed`] writes out benchmark runs.
+///
+/// Either way, numbers are written as described under [output formats](crate#output-formats).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    /// Comma-separated values, with a `probe,load,overloaded,reason` header line.
+    ///
+    /// The reason is empty unless one was given, and quoted if need be.
+    Csv,
+    /// One JSON object per line, like `{"probe":1,"load":500,"overloaded":false}`.
+    ///
+    /// A `"reason"` is included if one was given, and a `"search_id"` if the search has
+    /// [one](CliffSearch::search_id).
+    JsonLines,
+}
+
+/// A search that writes the outcome of every benchmark run to an [`io::Write`] as it happens.
+///
+/// This is the streaming counterpart to [`Recorded`](crate::Recorded): nothing is kept in memory,
+/// so it is suitable for campaigns with many thousands of runs. As with `Recorded`, a run is
+/// written once its outcome is known, which is when the next load is requested with
+/// [`Iterator::next`], and inconclusive runs are not written. Runs are numbered from `1`.
+///
+/// Since [`Iterator::next`] cannot return an error, the first error from the writer is kept and
+/// available through [`error`](Streamed::error), and nothing more is written after it. The search
+/// itself carries on regardless.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Format, Streamed};
+///
+/// let mut load = Streamed::new(ExponentialCliffSearcher::new(500), Vec::new(), Format::Csv);
+/// while let Some(l) = load.next() {
+///     if l > 1600 {
+///         load.overloaded();
+///     }
+/// }
+/// assert!(load.error().is_none());
+/// let (_, csv) = load.into_parts();
+/// assert_eq!(
+///     String::from_utf8(csv).unwrap(),
+///     "probe,load,overloaded,reason\n\
+///      1,500,false,\n\
+///      2,1000,false,\n\
+///      3,2000,true,\n\
+///      4,1500,false,\n\
+///      5,1750,true,\n"
+/// );
+/// ```
+///
+/// This type is only available with the `std` feature.
+#[derive(Debug)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Streamed<S, W> {
+    inner: S,
+    out: W,
+    format: Format,
+    written: usize,
+    error: Option<io::Error>,
+    last: Option<usize>,
+    overloaded: bool,
+    reason: Option<String>,
+    inconclusive: bool,
+}
+
+impl<S, W> Streamed<S, W>
+where
+    S: CliffSearch,
+    W: Write,
+{
+    /// Write the outcome of every benchmark run performed by `search` to `out` in `format`.
+    pub fn new(search: S, out: W, format: Format) -> Self {
+        Streamed {
+            inner: search,
+            out,
+            format,
+            written: 0,
+            error: None,
+            last: None,
+            overloaded: false,
+            reason: None,
+            inconclusive: false,
+        }
+    }
+
+    /// Give the number of runs written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Give the first error returned by the writer, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Give access to the writer, for example to flush it.
+    pub fn writer(&mut self) -> &mut W {
+        &mut self.out
+    }
+
+    /// Give back the wrapped search and the writer.
+    pub fn into_parts(self) -> (S, W) {
+        (self.inner, self.out)
+    }
+
+    fn write(&mut self, load: usize, overloaded: bool, reason: Option<&str>) -> io::Result<()> {
+        let probe = self.written + 1;
+        match self.format {
+            Format::Csv => {
+                if probe == 1 {
+                    writeln!(self.out, "probe,load,overloaded,reason")?;
+                }
+                write!(self.out, "{},{},{},", probe, load, overloaded)?;
+                match reason {
+                    Some(r) if r.contains(&[',', '"', '\n', '\r'][..]) => {
+                        writeln!(self.out, "\"{}\"", r.replace('"', "\"\""))
+                    }
+                    Some(r) => writeln!(self.out, "{}", r),
+                    None => writeln!(self.out),
+                }
+            }
+            Format::JsonLines => {
+                write!(
+                    self.out,
+                    r#"{{"probe":{},"load":{},"overloaded":{}"#,
+                    probe, load, overloaded
+                )?;
+                if let Some(id) = self.inner.search_id() {
+                    write!(self.out, r#","search_id":{}"#, id)?;
+                }
+                if let Some(r) = reason {
+                    let mut json = String::new();
+                    let _ = crate::json::write_string(&mut json, r);
+                    write!(self.out, r#","reason":{}"#, json)?;
+                }
+                writeln!(self.out, "}}")
+            }
+        }
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and why.
+    ///
+    /// The reason, like `format!("p99 {}ms > {}ms SLA", p99, sla)`, is written along with the
+    /// run, so that it is clear afterwards why each run failed.
+    pub fn overloaded_because<R: Into<String>>(&mut self, reason: R) {
+        self.reason = Some(reason.into());
+        self.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        // a run that never happened is not written
+        self.inconclusive = true;
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall is written as an overload.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+        self.inner.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+}
+
+impl<S, W> CliffSearch for Streamed<S, W>
+where
+    S: CliffSearch,
+    W: Write,
+{
+    fn overloaded(&mut self) {
+        Streamed::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Streamed::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Streamed::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Streamed::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Streamed::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Streamed::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Streamed::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.probes_left()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S, W> Refine for Streamed<S, W>
+where
+    S: Refine,
+    W: Write,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S, W> Iterator for Streamed<S, W>
+where
+    S: CliffSearch,
+    W: Write,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(load) = self.last.take() {
+            if !self.inconclusive && self.error.is_none() {
+                let reason = self.reason.take();
+                match self.write(load, self.overloaded, reason.as_deref()) {
+                    Ok(()) => self.written += 1,
+                    Err(e) => self.error = Some(e),
+                }
+            }
+        }
+        self.overloaded = false;
+        self.reason = None;
+        self.inconclusive = false;
+
+        self.last = self.inner.next();
+        self.last
+    }
+}
+
+impl<S, W> Describe for Streamed<S, W>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "streamed"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        let format = match self.format {
+            Format::Csv => "csv",
+            Format::JsonLines => "json_lines",
+        };
+        setting("format", &format);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn json_lines() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = Streamed::new(
+        ExponentialCliffSearcher::new(500),
+        std::vec::Vec::new(),
+        Format::JsonLines,
+    );
+    assert_eq!(scale.next(), Some(500));
+    scale.inconclusive();
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    scale.achieved(900);
+    assert_eq!(scale.next(), Some(700));
+    assert_eq!(scale.written(), 2);
+    let (_, out) = scale.into_parts();
+    assert_eq!(
+        std::string::String::from_utf8(out).unwrap(),
+        "{\"probe\":1,\"load\":500,\"overloaded\":false}\n\
+         {\"probe\":2,\"load\":1000,\"overloaded\":true}\n"
+    );
+}
+
+#[test]
+fn search_id() {
+    use super::{ExponentialCliffSearcher, Identified, Identity};
+
+    let mut scale = Streamed::new(
+        Identified::new(ExponentialCliffSearcher::new(500), Identity::new(9)),
+        std::vec::Vec::new(),
+        Format::JsonLines,
+    );
+    assert_eq!(scale.search_id(), Some(9));
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    let (_, out) = scale.into_parts();
+    assert_eq!(
+        std::string::String::from_utf8(out).unwrap(),
+        "{\"probe\":1,\"load\":500,\"overloaded\":false,\"search_id\":9}\n"
+    );
+}
+
+#[test]
+fn write_error() {
+    use super::ExponentialCliffSearcher;
+
+    let mut buf = [0u8; 44];
+    let mut scale = Streamed::new(
+        ExponentialCliffSearcher::new(500),
+        &mut buf[..],
+        Format::Csv,
+    );
+    while let Some(load) = scale.next() {
+        if load > 1600 {
+            scale.overloaded();
+        }
+    }
+    // the search is unaffected
+    assert_eq!(scale.estimate(), 1500..1750);
+    assert_eq!(scale.error().unwrap().kind(), io::ErrorKind::WriteZero);
+    assert_eq!(scale.written(), 1);
+}
+
+#[test]
+fn reasons() {
+    use super::ExponentialCliffSearcher;
+
+    for &(format, expected) in &[
+        (
+            Format::Csv,
+            "probe,load,overloaded,reason\n\
+             1,500,false,\n\
+             2,1000,true,\"p99 \"\"slow\"\", errors\"\n\
+             3,750,true,error rate 2%\n",
+        ),
+        (
+            Format::JsonLines,
+            "{\"probe\":1,\"load\":500,\"overloaded\":false}\n\
+             {\"probe\":2,\"load\":1000,\"overloaded\":true,\"reason\":\"p99 \\\"slow\\\", errors\"}\n\
+             {\"probe\":3,\"load\":750,\"overloaded\":true,\"reason\":\"error rate 2%\"}\n",
+        ),
+    ] {
+        let mut scale = Streamed::new(
+            ExponentialCliffSearcher::new(500),
+            std::vec::Vec::new(),
+            format,
+        );
+        assert_eq!(scale.next(), Some(500));
+        assert_eq!(scale.next(), Some(1000));
+        scale.overloaded_because("p99 \"slow\", errors");
+        assert_eq!(scale.next(), Some(750));
+        scale.overloaded_because(std::format!("error rate {}%", 2));
+        assert_eq!(scale.next(), None);
+        let (_, out) = scale.into_parts();
+        assert_eq!(std::string::String::from_utf8(out).unwrap(), expected);
+    }
+}
@@ -0,0 +1,387 @@
+use super::{CliffSearch, Describe, Refine, Status};
+use alloc::vec::Vec;
+
+/// The outcome of a single sub-run, as recorded by [`SubRuns`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubRun {
+    /// The load that was run.
+    pub load: usize,
+    /// The tag of the sub-run, as given in the schedule.
+    pub tag: &'static str,
+    /// Whether the system failed to keep up with the load in this sub-run.
+    pub overloaded: bool,
+}
+
+/// A search that runs every load several times in a row, according to a small schedule of tagged
+/// sub-runs, and lets only one of them decide the outcome.
+///
+/// Some benchmarks have to run each load more than once to get a meaningful result, like once
+/// with a cold cache and then once with a warm one. Every load the wrapped search asks for is
+/// yielded once for each tag in the schedule, in order, and [`tag`](SubRuns::tag) says which
+/// sub-run the most recently yielded load is. The outcome of every sub-run is recorded in
+/// [`runs`](SubRuns::runs), but only that of the deciding sub-run is passed on to the search. By
+/// default, that is the last one in the schedule; see [`decide_with`](SubRuns::decide_with).
+///
+/// An inconclusive sub-run is run again on its own, except for an inconclusive deciding sub-run,
+/// which the search asks to run again, and which so goes through the whole schedule again. If a
+/// sub-run cannot be run at all, the probe is [skipped](CliffSearch::skip_last), and the rest of
+/// its schedule is dropped.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, SubRuns};
+///
+/// let mut load = SubRuns::new(ExponentialCliffSearcher::new(500), &["cold", "warm"]);
+/// while let Some(l) = load.next() {
+///     // with a cold cache, the system falls over much sooner
+///     let cliff = if load.tag() == Some("cold") { 800 } else { 1600 };
+///     if l > cliff {
+///         load.overloaded();
+///     }
+/// }
+/// // only the warm runs decided where the cliff is
+/// assert_eq!(load.estimate(), 1500..1750);
+/// assert_eq!(load.runs().len(), 10);
+/// let cold_overloads = load
+///     .runs()
+///     .iter()
+///     .filter(|r| r.tag == "cold" && r.overloaded)
+///     .count();
+/// assert_eq!(cold_overloads, 4);
+/// ```
+///
+/// This type is only available with the `alloc` feature.
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct SubRuns<S> {
+    inner: S,
+    schedule: &'static [&'static str],
+    decides: usize,
+    runs: Vec<SubRun>,
+    load: Option<usize>,
+    // the sub-run of `load` that was yielded last
+    sub: usize,
+    last: Option<usize>,
+    overloaded: bool,
+    inconclusive: bool,
+    skipped: bool,
+}
+
+impl<S> SubRuns<S>
+where
+    S: CliffSearch,
+{
+    /// Run every load of `search` once for each tag in `schedule`, in order, and let the last
+    /// sub-run decide the outcome.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `schedule` is empty.
+    pub fn new(search: S, schedule: &'static [&'static str]) -> Self {
+        assert!(
+            !schedule.is_empty(),
+            "a schedule of sub-runs must have at least one sub-run"
+        );
+        SubRuns {
+            inner: search,
+            schedule,
+            decides: schedule.len() - 1,
+            runs: Vec::new(),
+            load: None,
+            sub: 0,
+            last: None,
+            overloaded: false,
+            inconclusive: false,
+            skipped: false,
+        }
+    }
+
+    /// Let the sub-run with the given tag decide the outcome of each probe.
+    ///
+    /// If several sub-runs have that tag, the first of them decides.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no sub-run in the schedule has that tag.
+    pub fn decide_with(&mut self, tag: &'static str) {
+        self.decides = self
+            .schedule
+            .iter()
+            .position(|&t| t == tag)
+            .expect("the deciding sub-run must be in the schedule");
+    }
+
+    /// Give the tag of the sub-run the most recently yielded load is for.
+    ///
+    /// Returns `None` if no load has been yielded yet, or if the search has ended.
+    pub fn tag(&self) -> Option<&'static str> {
+        self.last.map(|_| self.schedule[self.sub])
+    }
+
+    /// Give the outcome of every conclusive sub-run so far, in the order they were run.
+    pub fn runs(&self) -> &[SubRun] {
+        &self.runs
+    }
+
+    fn deciding(&self) -> bool {
+        self.sub == self.decides
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This is only passed on to the search for the deciding sub-run.
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.overloaded = true;
+        if self.deciding() {
+            self.inner.overloaded();
+        }
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This is only passed on to the search for the deciding sub-run.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.overloaded = true;
+        if self.deciding() {
+            self.inner.overloaded_by(factor);
+        }
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inconclusive = true;
+        if self.deciding() {
+            self.inner.inconclusive();
+        }
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        if self.deciding() {
+            self.inner.marginal();
+        }
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This skips the whole probe, whichever sub-run it was.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.skipped = true;
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// A shortfall is recorded as an overload, and is only passed on to the search for the
+    /// deciding sub-run.
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        if self.last.map_or(false, |last| actual < last) {
+            self.overloaded = true;
+        }
+        if self.deciding() {
+            self.inner.achieved(actual);
+        }
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+
+    /// Give back the wrapped search.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> CliffSearch for SubRuns<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        SubRuns::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        SubRuns::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        SubRuns::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        SubRuns::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        SubRuns::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        SubRuns::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        SubRuns::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.probes_left().map(|probes| {
+            let pending = match self.last {
+                Some(_) => self.schedule.len() - 1 - self.sub,
+                None => 0,
+            };
+            probes.saturating_mul(self.schedule.len()) + pending
+        })
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for SubRuns<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+impl<S> Iterator for SubRuns<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        let skipped = core::mem::replace(&mut self.skipped, false);
+        let repeat = match self.last.take() {
+            Some(_) if skipped => false,
+            Some(_) if self.inconclusive => {
+                // the search runs an inconclusive deciding sub-run again itself
+                !self.deciding()
+            }
+            Some(load) => {
+                self.runs.push(SubRun {
+                    load,
+                    tag: self.schedule[self.sub],
+                    overloaded: self.overloaded,
+                });
+                false
+            }
+            None => false,
+        };
+        let inconclusive = core::mem::replace(&mut self.inconclusive, false);
+        self.overloaded = false;
+
+        if repeat {
+            self.last = self.load;
+            return self.last;
+        }
+        match self.load {
+            Some(load) if !skipped && !inconclusive && self.sub + 1 < self.schedule.len() => {
+                self.sub += 1;
+                self.last = Some(load);
+            }
+            _ => {
+                self.sub = 0;
+                self.load = self.inner.next();
+                self.last = self.load;
+            }
+        }
+        self.last
+    }
+}
+
+impl<S> Describe for SubRuns<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "sub_runs"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("runs", &self.schedule.len());
+        setting("decided_by", &self.schedule[self.decides]);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn schedule() {
+    use super::ExponentialCliffSearcher;
+
+    let mut load = SubRuns::new(ExponentialCliffSearcher::new(500), &["cold", "warm", "hot"]);
+    load.decide_with("warm");
+    assert_eq!(load.tag(), None);
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.tag(), Some("cold"));
+    assert_eq!(load.probes_left(), None);
+    // only the warm run counts
+    load.overloaded();
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.tag(), Some("warm"));
+    assert_eq!(load.next(), Some(500));
+    assert_eq!(load.tag(), Some("hot"));
+    load.overloaded();
+    assert_eq!(load.next(), Some(1000));
+    assert_eq!(load.estimate(), 500..usize::MAX);
+    assert_eq!(
+        load.runs()
+            .iter()
+            .map(|r| (r.tag, r.overloaded))
+            .collect::<Vec<_>>(),
+        alloc::vec![("cold", true), ("warm", false), ("hot", true)]
+    );
+
+    // an inconclusive sub-run is run again on its own
+    load.inconclusive();
+    assert_eq!(load.next(), Some(1000));
+    assert_eq!(load.tag(), Some("cold"));
+    assert_eq!(load.next(), Some(1000));
+    assert_eq!(load.tag(), Some("warm"));
+    // unless it is the deciding one, which takes the whole schedule again
+    load.inconclusive();
+    assert_eq!(load.next(), Some(1000));
+    assert_eq!(load.tag(), Some("cold"));
+    // a sub-run that can't be run skips the whole probe
+    load.skip_last();
+    let next = load.next();
+    assert_ne!(next, Some(1000));
+    assert_eq!(load.tag(), Some("cold"));
+    // the second cold run at 1000 was the only other one to conclude
+    assert_eq!(load.runs().len(), 4);
+}
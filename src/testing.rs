@@ -0,0 +1,280 @@
+//! Helpers for testing code that builds on cliff.
+//!
+//! These make it easy to check that a search, or a stack of wrappers around one, converges on the
+//! right answer against a simulated system, or behaves exactly as expected given a scripted
+//! sequence of outcomes.
+
+use super::CliffSearch;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Drive `search` to completion against a simulated system, and return its final estimate.
+///
+/// `keeps_up` is called with every load the search yields, and should return `false` if the
+/// system would be overloaded at that load.
+///
+/// ```rust
+/// use cliff::{testing, ExponentialCliffSearcher};
+///
+/// let estimate = testing::drive(ExponentialCliffSearcher::new(500), |load| load <= 1600);
+/// assert_eq!(estimate, 1500..1750);
+/// ```
+pub fn drive<S, F>(mut search: S, mut keeps_up: F) -> core::ops::Range<usize>
+where
+    S: CliffSearch,
+    F: FnMut(usize) -> bool,
+{
+    while let Some(load) = search.next() {
+        if !keeps_up(load) {
+            search.overloaded();
+        }
+    }
+    search.estimate()
+}
+
+/// Assert that a search, driven to completion against a simulated system, ends up with an
+/// estimate that lies entirely within the expected range.
+///
+/// The first argument is the search, the second a closure that returns `false` if the simulated
+/// system would be overloaded at the given load (see [`testing::drive`](crate::testing::drive)),
+/// and the third is the range the final estimate must fall within.
+///
+/// ```rust
+/// use cliff::{assert_cliff_within, ExponentialCliffSearcher};
+///
+/// let cliff = 1234;
+/// assert_cliff_within!(ExponentialCliffSearcher::new(100), |load| load <= cliff, 1200..1300);
+/// ```
+#[macro_export]
+macro_rules! assert_cliff_within {
+    ($search:expr, $keeps_up:expr, $expected:expr $(,)?) => {{
+        let estimate = $crate::testing::drive($search, $keeps_up);
+        let expected: ::core::ops::Range<usize> = $expected;
+        assert!(
+            expected.start <= estimate.start && estimate.end <= expected.end,
+            "estimate {:?} is not within {:?}",
+            estimate,
+            expected
+        );
+    }};
+}
+
+/// The outcome of a single benchmark run in a [`Script`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    /// The system kept up.
+    Sustained,
+    /// The system did not keep up, as reported by [`CliffSearch::overloaded`].
+    Overloaded,
+    /// The system did not keep up by the given factor, as reported by
+    /// [`CliffSearch::overloaded_by`].
+    OverloadedBy(f64),
+    /// The system only achieved the given load, as reported by [`CliffSearch::achieved`].
+    Achieved(usize),
+    /// The run was inconclusive, as reported by [`CliffSearch::inconclusive`].
+    Inconclusive,
+}
+
+impl Step {
+    /// Report this outcome to `search` for the load it last yielded.
+    pub fn apply<S>(self, search: &mut S)
+    where
+        S: CliffSearch + ?Sized,
+    {
+        match self {
+            Step::Sustained => {}
+            Step::Overloaded => search.overloaded(),
+            Step::OverloadedBy(factor) => search.overloaded_by(factor),
+            Step::Achieved(actual) => search.achieved(actual),
+            Step::Inconclusive => search.inconclusive(),
+        }
+    }
+}
+
+impl core::fmt::Display for Step {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Step::Sustained => write!(f, "sustained"),
+            Step::Overloaded => write!(f, "overloaded"),
+            Step::OverloadedBy(factor) => write!(f, "overloaded by {}", factor),
+            Step::Achieved(actual) => write!(f, "achieved {}", actual),
+            Step::Inconclusive => write!(f, "inconclusive"),
+        }
+    }
+}
+
+/// A scripted sequence of outcomes to feed to a search.
+///
+/// Running a script against a search (or a stack of wrappers around one) reports each scripted
+/// outcome for the next load the search yields, and records the resulting sequence of probes in a
+/// [`Transcript`]. Since the outcomes do not depend on the loads, scripts can easily express
+/// inconclusive and contradictory results, and the transcript's [`Display`](core::fmt::Display)
+/// output is well suited for comparison against a golden file.
+///
+/// This type is only available with the `alloc` feature.
+///
+/// ```rust
+/// use cliff::testing::{Script, Step};
+/// use cliff::ExponentialCliffSearcher;
+///
+/// let transcript = Script::new(&[
+///     Step::Sustained,
+///     Step::Overloaded,
+///     Step::Inconclusive,
+///     Step::Sustained,
+/// ])
+/// .run(ExponentialCliffSearcher::new(500));
+///
+/// assert_eq!(
+///     transcript.to_string(),
+///     "500 sustained\n\
+///      1000 overloaded\n\
+///      750 inconclusive\n\
+///      750 sustained\n\
+///      estimate 750..1000 (done)\n"
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Script<'a> {
+    steps: &'a [Step],
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Script<'a> {
+    /// Create a script that reports the given outcomes in order.
+    pub fn new(steps: &'a [Step]) -> Self {
+        Script { steps }
+    }
+
+    /// Feed this script to `search`, and record what it does.
+    ///
+    /// The run ends when the script or the search runs out, whichever happens first.
+    pub fn run<S>(&self, mut search: S) -> Transcript
+    where
+        S: CliffSearch,
+    {
+        let mut probes = Vec::with_capacity(self.steps.len());
+        for &step in self.steps {
+            let load = match search.next() {
+                Some(load) => load,
+                None => {
+                    return Transcript {
+                        probes,
+                        next: None,
+                        estimate: search.estimate(),
+                    };
+                }
+            };
+            step.apply(&mut search);
+            probes.push((load, step));
+        }
+
+        // let the search take the last outcome into account
+        let next = search.next();
+        Transcript {
+            probes,
+            next,
+            estimate: search.estimate(),
+        }
+    }
+}
+
+/// The probes a search yielded when driven by a [`Script`].
+///
+/// This type is only available with the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    /// Every load the search yielded, along with the outcome that was reported for it.
+    pub probes: Vec<(usize, Step)>,
+    /// The load the search would have yielded next, or `None` if it had finished.
+    pub next: Option<usize>,
+    /// The search's estimate at the end of the script.
+    pub estimate: core::ops::Range<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for Transcript {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (load, step) in &self.probes {
+            writeln!(f, "{} {}", load, step)?;
+        }
+        write!(f, "estimate {}..{}", self.estimate.start, self.estimate.end)?;
+        match self.next {
+            Some(next) => writeln!(f, " (next {})", next),
+            None => writeln!(f, " (done)"),
+        }
+    }
+}
+
+#[test]
+fn within() {
+    use super::{BinaryMinSearcher, ExponentialCliffSearcher};
+
+    assert_cliff_within!(
+        ExponentialCliffSearcher::until(500, 10),
+        |load| load < 4321,
+        4300..4330
+    );
+    assert_cliff_within!(BinaryMinSearcher::until(1024, 8), |v| v >= 100, 90..110);
+}
+
+#[test]
+#[should_panic]
+fn not_within() {
+    use super::ExponentialCliffSearcher;
+
+    assert_cliff_within!(
+        ExponentialCliffSearcher::new(500),
+        |load| load < 4321,
+        4300..4330
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn script_wrapper_stack() {
+    use super::{ExponentialCliffSearcher, Paced, Tracked};
+    use alloc::string::ToString;
+
+    let search = Tracked::new(Paced::new(
+        ExponentialCliffSearcher::new(500),
+        core::time::Duration::from_secs(1),
+    ));
+    let transcript = Script::new(&[
+        Step::Sustained,
+        Step::Sustained,
+        Step::Achieved(1600),
+        Step::Inconclusive,
+        // contradicts the earlier success at 1000
+        Step::OverloadedBy(4.0),
+        Step::Sustained,
+    ])
+    .run(search);
+
+    assert_eq!(
+        transcript.to_string(),
+        "500 sustained\n\
+         1000 sustained\n\
+         2000 achieved 1600\n\
+         1300 inconclusive\n\
+         1300 overloaded by 4\n\
+         1075 sustained\n\
+         estimate 1075..1300 (done)\n"
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn script_outlasts_search() {
+    use super::LoadIterator;
+
+    let transcript = Script::new(&[Step::Sustained; 5]).run(LoadIterator::from(&[1, 2]));
+    assert_eq!(
+        transcript.probes,
+        [(1, Step::Sustained), (2, Step::Sustained)]
+    );
+    assert_eq!(transcript.next, None);
+    assert_eq!(transcript.estimate, 2..usize::MAX);
+}
@@ -0,0 +1,333 @@
+use super::{CliffSearch, Describe, Refine, Status, Suspend};
+
+/// A benchmark run that was yielded by a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Probe {
+    /// How many probes the search yielded before this one.
+    pub index: usize,
+    /// The load that was probed.
+    pub load: usize,
+    /// When the probe was yielded.
+    ///
+    /// This field is only available with the `std` feature.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(with = "system_time"))]
+    pub at: std::time::SystemTime,
+}
+
+// serde only knows how to write out a `SystemTime` with its own `std` feature, which would keep it
+// from being used without `std` here, so write it out as the time since the Unix epoch instead.
+#[cfg(all(feature = "serde", feature = "std"))]
+mod system_time {
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    pub(super) fn serialize<S: Serializer>(at: &SystemTime, s: S) -> Result<S::Ok, S::Error> {
+        at.duration_since(UNIX_EPOCH)
+            .map_err(ser::Error::custom)?
+            .serialize(s)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<SystemTime, D::Error> {
+        UNIX_EPOCH
+            .checked_add(Duration::deserialize(d)?)
+            .ok_or_else(|| de::Error::custom("time is out of range"))
+    }
+}
+
+/// The probes that established the current bounds of an estimate.
+///
+/// A bound is `None` if it has not been moved by any probe, such as the upper bound during the
+/// exponential phase of an [`ExponentialCliffSearcher`](crate::ExponentialCliffSearcher).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundProvenance {
+    /// The probe that established the lower bound of the estimate.
+    pub lower: Option<Probe>,
+    /// The probe that established the upper bound of the estimate.
+    pub upper: Option<Probe>,
+}
+
+/// A search that keeps track of which probe established each bound of its estimate.
+///
+/// When the results of a search are questioned, this lets you point to the exact benchmark runs
+/// to re-examine.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, Tracked};
+///
+/// let mut load = Tracked::new(ExponentialCliffSearcher::new(500));
+/// assert_eq!(load.next(), Some(500));
+/// assert_eq!(load.next(), Some(1000));
+/// assert_eq!(load.next(), Some(2000));
+/// load.overloaded();
+/// assert_eq!(load.next(), Some(1500));
+/// load.overloaded();
+/// assert_eq!(load.next(), Some(1250));
+/// assert_eq!(load.next(), None);
+/// assert_eq!(load.estimate(), 1250..1500);
+///
+/// let bounds = load.bound_provenance();
+/// assert_eq!(bounds.lower.map(|p| (p.index, p.load)), Some((4, 1250)));
+/// assert_eq!(bounds.upper.map(|p| (p.index, p.load)), Some((3, 1500)));
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct Tracked<S> {
+    inner: S,
+    probes: usize,
+    last: Option<Probe>,
+    bounds: BoundProvenance,
+}
+
+impl<S> Tracked<S>
+where
+    S: CliffSearch,
+{
+    /// Track where the bounds of `search`'s estimate come from.
+    pub fn new(search: S) -> Self {
+        Tracked {
+            inner: search,
+            probes: 0,
+            last: None,
+            bounds: BoundProvenance::default(),
+        }
+    }
+
+    /// Give the probes that established the current lower and upper bound of the estimate.
+    pub fn bound_provenance(&self) -> BoundProvenance {
+        self.bounds
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        self.inner.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+}
+
+impl<S> CliffSearch for Tracked<S>
+where
+    S: CliffSearch,
+{
+    fn overloaded(&mut self) {
+        Tracked::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        Tracked::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        Tracked::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        Tracked::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        Tracked::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        Tracked::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        Tracked::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        self.inner.probes_left()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S> Refine for Tracked<S>
+where
+    S: Refine,
+{
+    fn refine(&mut self, min_width: usize) {
+        self.inner.refine(min_width);
+    }
+}
+
+/// The state of a suspended [`Tracked`] search.
+///
+/// See [`Suspend`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuspendedTracked<T> {
+    /// The state of the wrapped search.
+    pub inner: T,
+    /// The number of probes yielded so far.
+    pub probes: usize,
+    /// The probe whose outcome has yet to be taken into account.
+    pub pending: Option<Probe>,
+    /// The probes that established the current bounds.
+    pub bounds: BoundProvenance,
+}
+
+impl<T> SuspendedTracked<T> {
+    /// Continue the suspended search.
+    pub fn resume<S>(self) -> Tracked<S>
+    where
+        S: Suspend<Suspended = T>,
+    {
+        Tracked {
+            inner: S::resume(self.inner),
+            probes: self.probes,
+            last: self.pending,
+            bounds: self.bounds,
+        }
+    }
+}
+
+impl<S> Suspend for Tracked<S>
+where
+    S: Suspend,
+{
+    type Suspended = SuspendedTracked<S::Suspended>;
+
+    fn suspend(self) -> Self::Suspended {
+        SuspendedTracked {
+            inner: self.inner.suspend(),
+            probes: self.probes,
+            pending: self.last,
+            bounds: self.bounds,
+        }
+    }
+
+    fn resume(suspended: Self::Suspended) -> Self {
+        suspended.resume()
+    }
+}
+
+impl<S> Iterator for Tracked<S>
+where
+    S: CliffSearch,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        // the inner search takes the outcome of the last probe into account when asked for the
+        // next one, so that's when we can see which bounds it moved.
+        let before = self.inner.estimate();
+        let next = self.inner.next();
+        let after = self.inner.estimate();
+        if let Some(last) = self.last.take() {
+            // a probe may also confirm a bound without moving it, like the first probe does
+            if after.start != before.start || after.start == last.load {
+                self.bounds.lower = Some(last);
+            }
+            if after.end != before.end || after.end == last.load {
+                self.bounds.upper = Some(last);
+            }
+        }
+
+        let load = next?;
+        self.last = Some(Probe {
+            index: self.probes,
+            load,
+            #[cfg(feature = "std")]
+            at: std::time::SystemTime::now(),
+        });
+        self.probes += 1;
+        Some(load)
+    }
+}
+
+impl<S> Describe for Tracked<S>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "tracked"
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn min_provenance() {
+    use super::BinaryMinSearcher;
+
+    let mut scale = Tracked::new(BinaryMinSearcher::until(1024, 64));
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(512));
+    assert_eq!(scale.bound_provenance().upper.map(|p| p.index), Some(0));
+    assert_eq!(scale.next(), Some(256));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(384));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(448));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 384..448);
+
+    let bounds = scale.bound_provenance();
+    assert_eq!(bounds.lower.map(|p| (p.index, p.load)), Some((3, 384)));
+    assert_eq!(bounds.upper.map(|p| (p.index, p.load)), Some((4, 448)));
+}
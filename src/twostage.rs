@@ -0,0 +1,249 @@
+use super::{CliffSearch, Describe, Refine, Status};
+
+/// A search that first converges to a coarse estimate, and then continues to a finer estimate if a
+/// predicate approves.
+///
+/// The wrapped search determines the coarse fidelity. Once it has finished, the predicate is called
+/// with the coarse estimate. If it returns `true` (say, because there is enough time budget left),
+/// the search is [refined](Refine::refine) to the fine fidelity and continues. Otherwise, the
+/// search ends with the coarse estimate.
+///
+/// ```rust
+/// use cliff::{ExponentialCliffSearcher, TwoStage};
+///
+/// let mut load = TwoStage::new(ExponentialCliffSearcher::until(500, 1000), 250, |coarse| {
+///     assert_eq!(coarse, 4000..5000);
+///     true
+/// });
+/// while let Some(l) = load.next() {
+///     if l > 4321 {
+///         load.overloaded();
+///     }
+/// }
+/// assert_eq!(load.coarse_estimate(), Some(4000..5000));
+/// assert_eq!(load.estimate(), 4250..4500);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "a search does nothing unless it is iterated and told about overloads"]
+pub struct TwoStage<S, F> {
+    inner: S,
+    fine: usize,
+    approve: F,
+    coarse: Option<core::ops::Range<usize>>,
+}
+
+impl<S, F> TwoStage<S, F>
+where
+    S: Refine,
+    F: FnMut(core::ops::Range<usize>) -> bool,
+{
+    /// Run `search` to completion, and then continue it until its estimate is no wider than
+    /// `min_width` if `approve` returns `true` for the coarse estimate.
+    pub fn new(search: S, min_width: usize, approve: F) -> Self {
+        TwoStage {
+            inner: search,
+            fine: min_width,
+            approve,
+            coarse: None,
+        }
+    }
+
+    /// Give the estimate the first stage of the search ended with, if it has ended.
+    pub fn coarse_estimate(&self) -> Option<core::ops::Range<usize>> {
+        self.coarse.clone()
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::overloaded`] without having to `use` the trait.
+    pub fn overloaded(&mut self) {
+        self.inner.overloaded();
+    }
+
+    /// Indicate that the system could not keep up with the previous load factor yielded by
+    /// [`Iterator::next`], and by how much.
+    ///
+    /// This provides [`CliffSearch::overloaded_by`] without having to `use` the trait.
+    pub fn overloaded_by(&mut self, factor: f64) {
+        self.inner.overloaded_by(factor);
+    }
+
+    /// Indicate that the benchmark run for the previous load factor yielded by [`Iterator::next`]
+    /// was inconclusive.
+    ///
+    /// This provides [`CliffSearch::inconclusive`] without having to `use` the trait.
+    pub fn inconclusive(&mut self) {
+        self.inner.inconclusive();
+    }
+
+    /// Indicate that the system only just kept up with the previous load factor yielded by
+    /// [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::marginal`] without having to `use` the trait.
+    pub fn marginal(&mut self) {
+        self.inner.marginal();
+    }
+
+    /// Indicate that the previous load factor yielded by [`Iterator::next`] could not be run at
+    /// all.
+    ///
+    /// This provides [`CliffSearch::skip_last`] without having to `use` the trait.
+    pub fn skip_last(&mut self) {
+        self.inner.skip_last();
+    }
+
+    /// Indicate the load the system actually achieved when running the previous load factor
+    /// yielded by [`Iterator::next`].
+    ///
+    /// This provides [`CliffSearch::achieved`] without having to `use` the trait.
+    pub fn achieved(&mut self, actual: usize) {
+        self.inner.achieved(actual);
+    }
+
+    /// Give the current estimate of the maximum load the system-under-test can support.
+    ///
+    /// This provides [`CliffSearch::estimate`] without having to `use` the trait.
+    pub fn estimate(&self) -> core::ops::Range<usize> {
+        self.inner.estimate()
+    }
+}
+
+impl<S, F> CliffSearch for TwoStage<S, F>
+where
+    S: Refine,
+    F: FnMut(core::ops::Range<usize>) -> bool,
+{
+    fn overloaded(&mut self) {
+        TwoStage::overloaded(self)
+    }
+
+    fn overloaded_by(&mut self, factor: f64) {
+        TwoStage::overloaded_by(self, factor)
+    }
+
+    fn inconclusive(&mut self) {
+        TwoStage::inconclusive(self)
+    }
+
+    fn marginal(&mut self) {
+        TwoStage::marginal(self)
+    }
+
+    fn achieved(&mut self, actual: usize) {
+        TwoStage::achieved(self, actual)
+    }
+
+    fn skip_last(&mut self) {
+        TwoStage::skip_last(self)
+    }
+
+    fn estimate(&self) -> core::ops::Range<usize> {
+        TwoStage::estimate(self)
+    }
+
+    fn status(&self) -> Status {
+        self.inner.status()
+    }
+
+    fn probes_left(&self) -> Option<usize> {
+        // the first stage can't know whether the second will happen
+        self.coarse.as_ref()?;
+        self.inner.probes_left()
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed()
+    }
+
+    fn search_id(&self) -> Option<u64> {
+        self.inner.search_id()
+    }
+}
+
+impl<S, F> Iterator for TwoStage<S, F>
+where
+    S: Refine,
+    F: FnMut(core::ops::Range<usize>) -> bool,
+{
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(next) = self.inner.next() {
+            return Some(next);
+        }
+        if self.coarse.is_some() {
+            return None;
+        }
+
+        let coarse = self.inner.estimate();
+        self.coarse = Some(coarse.clone());
+        if (self.approve)(coarse) {
+            self.inner.refine(self.fine);
+            self.inner.next()
+        } else {
+            None
+        }
+    }
+}
+
+impl<S, F> Describe for TwoStage<S, F>
+where
+    S: Describe,
+{
+    fn name(&self) -> &'static str {
+        "two_stage"
+    }
+
+    fn settings(&self, setting: &mut dyn FnMut(&'static str, &dyn core::fmt::Display)) {
+        setting("fine", &self.fine);
+    }
+
+    fn inner(&self) -> Option<&dyn Describe> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn approved() {
+    use super::ExponentialCliffSearcher;
+
+    let mut scale = TwoStage::new(ExponentialCliffSearcher::until(500, 1000), 250, |_| true);
+    assert_eq!(scale.next(), Some(500));
+    assert_eq!(scale.next(), Some(1000));
+    assert_eq!(scale.next(), Some(2000));
+    assert_eq!(scale.next(), Some(4000));
+    assert_eq!(scale.next(), Some(8000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(6000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(5000));
+    scale.overloaded();
+    assert_eq!(scale.coarse_estimate(), None);
+    // the coarse search ends here, but we continue
+    assert_eq!(scale.next(), Some(4500));
+    assert_eq!(scale.coarse_estimate(), Some(4000..5000));
+    scale.overloaded();
+    assert_eq!(scale.next(), Some(4250));
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 4250..4500);
+
+    // check that it continues to be terminated
+    assert_eq!(scale.next(), None);
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.estimate(), 4250..4500);
+}
+
+#[test]
+fn declined() {
+    use super::BinaryMinSearcher;
+
+    let mut scale = TwoStage::new(BinaryMinSearcher::until(1024, 512), 8, |_| false);
+    assert_eq!(scale.next(), Some(1024));
+    assert_eq!(scale.next(), Some(512));
+    scale.overloaded();
+    assert_eq!(scale.next(), None);
+    assert_eq!(scale.coarse_estimate(), Some(512..1024));
+    assert_eq!(scale.estimate(), 512..1024);
+    assert_eq!(scale.next(), None);
+}
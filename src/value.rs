@@ -0,0 +1,203 @@
+/// A numeric type that loads can be searched over.
+///
+/// The searchers only need a handful of operations on loads: finding the point halfway between
+/// two loads when bisecting, doubling a load when ramping up, and measuring how wide the range of
+/// an estimate is. This trait provides exactly those, so that the search arithmetic does not
+/// depend on loads being `usize`.
+///
+/// It is implemented for `usize`, `u32`, `u64`, and `f64`. [`CliffSearch`](crate::CliffSearch),
+/// [`ExponentialCliffSearcher`](crate::ExponentialCliffSearcher),
+/// [`BinaryMinSearcher`](crate::BinaryMinSearcher), and [`LoadIterator`](crate::LoadIterator)
+/// are generic over it, and default to `usize`.
+///
+/// ```rust
+/// use cliff::SearchValue;
+///
+/// assert_eq!(1000usize.bisect(1500), 1250);
+/// assert_eq!(10.0f64.bisect(15.0), 12.5);
+/// assert_eq!(u32::MAX.doubled(), None);
+/// assert_eq!(2.5f64.width(4.0), 1.5);
+/// assert_eq!(1000u64.scaled(3, 4), 750);
+/// ```
+pub trait SearchValue:
+    Copy
+    + PartialOrd
+    + core::fmt::Debug
+    + core::fmt::Display
+    + core::ops::Add<Output = Self>
+    + core::ops::Sub<Output = Self>
+{
+    /// The smallest load.
+    const ZERO: Self;
+
+    /// The smallest step between two loads that the searchers take, such as when a load that
+    /// would not ramp up otherwise is raised by a little.
+    const ONE: Self;
+
+    /// The largest load, which also stands in for "no upper bound".
+    const UNBOUNDED: Self;
+
+    /// The load halfway between `self` and `end`, rounded towards `self`.
+    ///
+    /// `self` must not be greater than `end`.
+    fn bisect(self, end: Self) -> Self;
+
+    /// Twice this load, or `None` if that is not representable.
+    fn doubled(self) -> Option<Self>;
+
+    /// The distance from `self` to `end`, or zero if `end` is not greater than `self`.
+    fn width(self, end: Self) -> Self;
+
+    /// This load plus `by`, or `None` if that is not representable.
+    fn offset(self, by: Self) -> Option<Self>;
+
+    /// This load times `numerator / denominator`, rounded down, or
+    /// [`UNBOUNDED`](SearchValue::UNBOUNDED) if that is not representable.
+    fn scaled(self, numerator: usize, denominator: usize) -> Self;
+
+    /// This load as an `f64`, which may round it.
+    fn to_f64(self) -> f64;
+
+    /// The load closest to `value` that is not above it, saturating at either end of the range
+    /// of loads.
+    fn from_f64(value: f64) -> Self;
+}
+
+// the larger of two loads, which may only be partially ordered
+pub(crate) fn max<V: PartialOrd>(a: V, b: V) -> V {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+// the smaller of two loads, which may only be partially ordered
+pub(crate) fn min<V: PartialOrd>(a: V, b: V) -> V {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+macro_rules! integer_value {
+    ($($t:ty),*) => {$(
+        impl SearchValue for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const UNBOUNDED: Self = <$t>::MAX;
+
+            fn bisect(self, end: Self) -> Self {
+                self + (end - self) / 2
+            }
+
+            fn doubled(self) -> Option<Self> {
+                self.checked_mul(2)
+            }
+
+            fn width(self, end: Self) -> Self {
+                end.saturating_sub(self)
+            }
+
+            fn offset(self, by: Self) -> Option<Self> {
+                self.checked_add(by)
+            }
+
+            fn scaled(self, numerator: usize, denominator: usize) -> Self {
+                // widen so that large loads times large numerators don't overflow
+                let scaled = self as u128 * numerator as u128 / denominator as u128;
+                if scaled > <$t>::MAX as u128 {
+                    <$t>::MAX
+                } else {
+                    scaled as $t
+                }
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                // float casts saturate, and take NaN to 0
+                value as $t
+            }
+        }
+    )*};
+}
+
+integer_value!(usize, u32, u64);
+
+impl SearchValue for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const UNBOUNDED: Self = f64::INFINITY;
+
+    fn bisect(self, end: Self) -> Self {
+        self + (end - self) / 2.0
+    }
+
+    fn doubled(self) -> Option<Self> {
+        let doubled = self * 2.0;
+        if doubled.is_finite() {
+            Some(doubled)
+        } else {
+            None
+        }
+    }
+
+    fn width(self, end: Self) -> Self {
+        if end > self {
+            end - self
+        } else {
+            0.0
+        }
+    }
+
+    fn offset(self, by: Self) -> Option<Self> {
+        let sum = self + by;
+        if sum.is_finite() {
+            Some(sum)
+        } else {
+            None
+        }
+    }
+
+    fn scaled(self, numerator: usize, denominator: usize) -> Self {
+        self * numerator as f64 / denominator as f64
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+#[test]
+fn integers() {
+    assert_eq!(0usize.bisect(1), 0);
+    assert_eq!((usize::MAX - 1).bisect(usize::MAX), usize::MAX - 1);
+    assert_eq!(0u64.bisect(u64::MAX), u64::MAX / 2);
+    assert_eq!(500u32.doubled(), Some(1000));
+    assert_eq!((u64::MAX / 2 + 1).doubled(), None);
+    assert_eq!(7usize.width(3), 0);
+    assert_eq!(3u32.width(7), 4);
+    assert_eq!(u32::MAX.offset(1), None);
+    assert_eq!(u64::MAX.scaled(3, 4), u64::MAX / 4 * 3 + 2);
+    assert_eq!(u32::MAX.scaled(2, 1), u32::MAX);
+    assert_eq!(usize::from_f64(-1.0), 0);
+    assert_eq!(usize::from_f64(f64::INFINITY), usize::MAX);
+}
+
+#[test]
+fn floats() {
+    assert_eq!(0.0f64.bisect(1.0), 0.5);
+    assert_eq!(f64::MAX.doubled(), None);
+    assert_eq!(4.0f64.width(2.5), 0.0);
+    assert_eq!(f64::ZERO.width(f64::UNBOUNDED), f64::INFINITY);
+    assert_eq!(f64::MAX.offset(f64::MAX), None);
+    assert_eq!(1000.0f64.scaled(1, 3), 1000.0 / 3.0);
+}